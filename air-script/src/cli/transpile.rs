@@ -2,8 +2,9 @@ use std::{fs, path::PathBuf};
 use structopt::StructOpt;
 
 use codegen_winter::CodeGenerator;
+use diagnostics::{render_files, Diagnostic, OutputFormat, SourceFiles, ToDiagnostic};
 use ir::AirIR;
-use parser::parse;
+use parser::parse_program;
 
 #[derive(StructOpt, Debug)]
 #[structopt(
@@ -17,18 +18,36 @@ pub struct TranspileCmd {
     /// Path to output file
     #[structopt(short = "o", long = "output", parse(from_os_str))]
     output_file: Option<PathBuf>,
+    /// Format of the reported errors: "plain" or "json"
+    #[structopt(long = "error-format", default_value = "plain")]
+    error_format: OutputFormat,
 }
 
 impl TranspileCmd {
     pub fn execute(&self) -> Result<(), String> {
-        println!("============================================================");
-        println!("Transpiling...");
+        self.log("============================================================");
+        self.log("Transpiling...");
 
+        let mut files = SourceFiles::default();
+        let output_path = self
+            .transpile(&mut files)
+            .map_err(|diagnostics| render_files(self.error_format, &files, &diagnostics))?;
+
+        self.log(&format!("Success! Transpiled to {}", output_path.display()));
+        self.log("============================================================");
+
+        Ok(())
+    }
+
+    /// Transpiles the input file and writes the generated code to the output file, whose path is
+    /// returned. The source files of the program are added to `files` as they are loaded, so that
+    /// the returned diagnostics can be rendered against them.
+    fn transpile(&self, files: &mut SourceFiles) -> Result<PathBuf, Vec<Diagnostic>> {
         // get the input path
         let input_path = match &self.input_file {
             Some(path) => path.clone(),
             None => {
-                return Err("No input file specified".to_string());
+                return Err(vec![Diagnostic::error("No input file specified")]);
             }
         };
 
@@ -43,38 +62,40 @@ impl TranspileCmd {
         };
 
         // load source input from file
-        let source = fs::read_to_string(input_path).map_err(|err| {
-            format!(
+        let source = fs::read_to_string(&input_path).map_err(|err| {
+            vec![Diagnostic::error(format!(
                 "Failed to open input file `{:?}` - {}",
                 &self.input_file, err
-            )
+            ))]
         })?;
 
-        // parse the input file to the internal representation
-        let parsed = parse(source.as_str());
-        if let Err(err) = parsed {
-            return Err(format!("{:?}", err));
-        }
-        let parsed = parsed.unwrap();
+        // parse the input file and the modules it imports to the internal representation. The
+        // modules are loaded from the directory of the input file.
+        let parsed = parse_program(&input_path, &source, files)
+            .map_err(|errors| errors.iter().map(|e| e.to_diagnostic()).collect::<Vec<_>>())?;
 
-        let ir = AirIR::from_source(&parsed);
-        if let Err(err) = ir {
-            return Err(format!("{:?}", err));
-        }
-        let ir = ir.unwrap();
+        let ir = AirIR::from_program(&parsed)
+            .map_err(|errors| errors.iter().map(|e| e.to_diagnostic()).collect::<Vec<_>>())?;
 
         // generate Rust code targeting Winterfell
-        let codegen = CodeGenerator::new(&ir);
+        let codegen =
+            CodeGenerator::new(&ir).map_err(|err| vec![Diagnostic::error(err.to_string())])?;
 
         // write transpiled output to the output path
         let result = fs::write(output_path.clone(), codegen.generate());
         if let Err(err) = result {
-            return Err(format!("{:?}", err));
+            return Err(vec![Diagnostic::error(format!("{:?}", err))]);
         }
 
-        println!("Success! Transpiled to {}", output_path.display());
-        println!("============================================================");
+        Ok(output_path)
+    }
 
-        Ok(())
+    /// Prints a progress message. When errors are reported as JSON, the messages are printed to
+    /// stderr, so that the JSON array of diagnostics is the only output on stdout.
+    fn log(&self, message: &str) {
+        match self.error_format {
+            OutputFormat::Plain => println!("{}", message),
+            OutputFormat::Json => eprintln!("{}", message),
+        }
     }
 }
@@ -4,6 +4,10 @@
 /// AirScript parse method to generate an AST from AirScript source files
 pub use parser::parse;
 
+/// AirScript parse method to generate the ASTs of a root AirScript file and of the library modules
+/// it imports
+pub use parser::parse_program;
+
 /// AirScript intermediate representation
 pub use ir::AirIR;
 
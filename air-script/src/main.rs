@@ -35,8 +35,9 @@ pub fn main() {
     // read command-line args
     let cli = Cli::from_args();
 
-    // execute cli action
+    // execute cli action, exiting with a non-zero status if it fails
     if let Err(error) = cli.execute() {
         println!("{}", error);
+        std::process::exit(1);
     }
 }
@@ -1,6 +1,8 @@
 use winter_air::{Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, ProofOptions as WinterProofOptions, TransitionConstraintDegree, TraceInfo};
-use winter_math::{fields, ExtensionOf, FieldElement};
-use winter_utils::{collections, ByteWriter, Serializable};
+use winter_math::fields::f64::BaseElement as Felt;
+use winter_math::{ExtensionOf, FieldElement};
+use winter_utils::collections::Vec;
+use winter_utils::{ByteWriter, Serializable};
 
 pub struct PublicInputs {
     stack_inputs: [Felt; 16],
@@ -27,6 +29,10 @@ impl AuxiliaryAir {
     pub fn last_step(&self) -> usize {
         self.trace_length() - self.context().num_transition_exemptions()
     }
+
+    pub fn aux_segment_rand_elements() -> [usize; 1] {
+        [2]
+    }
 }
 
 impl Air for AuxiliaryAir {
@@ -38,10 +44,14 @@ impl Air for AuxiliaryAir {
     }
 
     fn new(trace_info: TraceInfo, public_inputs: PublicInputs, options: WinterProofOptions) -> Self {
-        let main_degrees = vec![TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(1)];
-        let aux_degrees = Vec::new();
-        let num_main_assertions = 2;
-        let num_aux_assertions = 0;
+        let main_degrees = vec![TransitionConstraintDegree::new(1)];
+        let aux_degrees = vec![TransitionConstraintDegree::new(2), TransitionConstraintDegree::new(1)];
+        let num_main_assertions = 1;
+        let num_aux_assertions = 4;
+        assert_eq!(trace_info.layout().main_trace_width(), 2, "invalid main trace width");
+        assert_eq!(trace_info.layout().num_aux_segments(), 1, "invalid number of auxiliary trace segments");
+        assert_eq!(trace_info.layout().get_aux_segment_width(0), 2, "invalid width of auxiliary trace segment 0");
+        assert!(trace_info.layout().get_aux_segment_rand_elements(0) >= 2, "not enough random elements for auxiliary trace segment 0");
 
         let context = AirContext::new_multi_segment(
             trace_info,
@@ -61,8 +71,7 @@ impl Air for AuxiliaryAir {
 
     fn get_assertions(&self) -> Vec<Assertion<Felt>> {
         let mut result = Vec::new();
-        result.push(Assertion::single(0, 0, Felt::new(1)));
-        result.push(Assertion::single(1, 0, Felt::new(1)));
+        result.push(Assertion::single(0, 0, self.stack_inputs[0]));
         result
     }
 
@@ -72,25 +81,25 @@ impl Air for AuxiliaryAir {
         result.push(Assertion::single(1, 0, aux_rand_elements.get_segment_elements(0)[0]));
         let last_step = self.last_step();
         result.push(Assertion::single(0, last_step, E::from(1_u64)));
-        result.push(Assertion::single(1, last_step, E::from(1_u64)));
+        result.push(Assertion::single(1, last_step, aux_rand_elements.get_segment_elements(0)[0]));
         result
     }
 
     fn evaluate_transition<E: FieldElement<BaseField = Felt>>(&self, frame: &EvaluationFrame<E>, periodic_values: &[E], result: &mut [E]) {
         let current = frame.current();
         let next = frame.next();
-        result[0] = next[0] - (current[1] + current[2]);
-        result[1] = next[1] - (current[2] + next[0]);
-        result[2] = current[2] - (current[0] + current[1]);
+        result[0] = next[0] - (current[0] + E::from(1_u64));
     }
 
-    fn evaluate_aux_transition<F, E>(&self, main_frame: &EvaluationFrame<F>, aux_frame: &EvaluationFrame<E>, _periodic_values: &[F], aux_rand_elements: &AuxTraceRandElements<E>, result: &mut [E])
+    fn evaluate_aux_transition<F, E>(&self, main_frame: &EvaluationFrame<F>, aux_frame: &EvaluationFrame<E>, periodic_values: &[F], aux_rand_elements: &AuxTraceRandElements<E>, result: &mut [E])
     where F: FieldElement<BaseField = Felt>,
           E: FieldElement<BaseField = Felt> + ExtensionOf<F>,
     {
-        let current = aux_frame.current();
-        let next = aux_frame.next();
-        result[0] = next[0] - ((current[0]) * (current[0] + aux_rand_elements.get_segment_elements(0)[0] + current[1] + aux_rand_elements.get_segment_elements(0)[1]));
-        result[1] = current[1] - ((next[1]) * (current[2] + aux_rand_elements.get_segment_elements(0)[0]));
+        let main_current = main_frame.current();
+        let main_next = main_frame.next();
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
+        result[0] = (aux_next[0]) * (E::from(main_current[1]) + aux_rand_elements.get_segment_elements(0)[0]) - ((aux_current[0]) * (E::from(main_current[0]) + aux_rand_elements.get_segment_elements(0)[0]));
+        result[1] = aux_next[1] - (aux_current[1] + (aux_rand_elements.get_segment_elements(0)[1]) * (E::from(main_current[0]) - (E::from(main_current[1]))));
     }
 }
\ No newline at end of file
@@ -0,0 +1,57 @@
+use serde_json::Value;
+use std::{env, process::Command};
+
+// CLI TESTS
+// ================================================================================================
+
+/// Runs the transpile command of the CLI with errors reported as JSON, and returns whether it
+/// succeeded along with its output on stdout.
+fn transpile_json(input_path: &str, output_name: &str) -> (bool, String) {
+    let output_path = env::temp_dir().join(output_name);
+    let output = Command::new(env!("CARGO_BIN_EXE_airc"))
+        .args(["transpile", "-i", input_path, "-o"])
+        .arg(&output_path)
+        .args(["--error-format", "json"])
+        .output()
+        .expect("failed to run the CLI");
+
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).expect("the output of the CLI is not valid utf8"),
+    )
+}
+
+#[test]
+fn json_errors_are_the_only_output() {
+    let (success, stdout) = transpile_json("tests/cli/undeclared_column.air", "undeclared.rs");
+    assert!(!success);
+
+    let diagnostics: Value = serde_json::from_str(&stdout).expect("the output is not JSON");
+    let diagnostics = diagnostics
+        .as_array()
+        .expect("the output is not a JSON array");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["code"], "E0100");
+    assert_eq!(diagnostics[0]["file"], "tests/cli/undeclared_column.air");
+    assert_eq!(diagnostics[0]["labels"][0]["start"]["line"], 13);
+}
+
+#[test]
+fn json_codegen_errors() {
+    let (success, stdout) = transpile_json("tests/aux_segments/aux_segments.air", "segments.rs");
+    assert!(!success);
+
+    let diagnostics: Value = serde_json::from_str(&stdout).expect("the output is not JSON");
+    assert_eq!(diagnostics[0]["severity"], "error");
+    assert!(diagnostics[0]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("The auxiliary trace is split into 2 segments"));
+}
+
+#[test]
+fn json_success_has_no_output() {
+    let (success, stdout) = transpile_json("tests/binary/binary.air", "binary.rs");
+    assert!(success);
+    assert!(stdout.is_empty());
+}
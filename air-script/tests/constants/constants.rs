@@ -0,0 +1,108 @@
+use winter_air::{Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, ProofOptions as WinterProofOptions, TransitionConstraintDegree, TraceInfo};
+use winter_math::fields::f64::BaseElement as Felt;
+use winter_math::{ExtensionOf, FieldElement};
+use winter_utils::collections::Vec;
+use winter_utils::{ByteWriter, Serializable};
+
+pub struct PublicInputs {
+    program_hash: [Felt; 4],
+    stack_inputs: [Felt; 4],
+}
+
+impl PublicInputs {
+    pub fn new(program_hash: [Felt; 4], stack_inputs: [Felt; 4]) -> Self {
+        Self { program_hash, stack_inputs }
+    }
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.program_hash.as_slice());
+        target.write(self.stack_inputs.as_slice());
+    }
+}
+
+pub struct ConstantsAir {
+    context: AirContext<Felt>,
+    program_hash: [Felt; 4],
+    stack_inputs: [Felt; 4],
+}
+
+impl ConstantsAir {
+    pub fn last_step(&self) -> usize {
+        self.trace_length() - self.context().num_transition_exemptions()
+    }
+
+    pub fn aux_segment_rand_elements() -> [usize; 1] {
+        [1]
+    }
+}
+
+impl Air for ConstantsAir {
+    type BaseField = Felt;
+    type PublicInputs = PublicInputs;
+
+    fn context(&self) -> &AirContext<Felt> {
+        &self.context
+    }
+
+    fn new(trace_info: TraceInfo, public_inputs: PublicInputs, options: WinterProofOptions) -> Self {
+        let main_degrees = vec![TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(1)];
+        let aux_degrees = vec![TransitionConstraintDegree::new(1)];
+        let num_main_assertions = 3;
+        let num_aux_assertions = 1;
+        assert_eq!(trace_info.layout().main_trace_width(), 3, "invalid main trace width");
+        assert_eq!(trace_info.layout().num_aux_segments(), 1, "invalid number of auxiliary trace segments");
+        assert_eq!(trace_info.layout().get_aux_segment_width(0), 1, "invalid width of auxiliary trace segment 0");
+        assert!(trace_info.layout().get_aux_segment_rand_elements(0) >= 1, "not enough random elements for auxiliary trace segment 0");
+
+        let context = AirContext::new_multi_segment(
+            trace_info,
+            main_degrees,
+            aux_degrees,
+            num_main_assertions,
+            num_aux_assertions,
+            options,
+        )
+        .set_num_transition_exemptions(1);
+        Self { context, program_hash: public_inputs.program_hash, stack_inputs: public_inputs.stack_inputs }
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Felt>> {
+        vec![]
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Felt>> {
+        let mut result = Vec::new();
+        result.push(Assertion::single(0, 0, Felt::new(1)));
+        result.push(Assertion::single(1, 0, (Felt::new(1)) + ((Felt::new(1)) * (Felt::new(2)))));
+        let last_step = self.last_step();
+        result.push(Assertion::single(2, last_step, (Felt::new(1)) - (Felt::new(6))));
+        result
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Felt>>(&self, aux_rand_elements: &AuxTraceRandElements<E>) -> Vec<Assertion<E>> {
+        let mut result = Vec::new();
+        result.push(Assertion::single(0, 0, (E::from(0_u64)) + (aux_rand_elements.get_segment_elements(0)[0])));
+        result
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Felt>>(&self, frame: &EvaluationFrame<E>, periodic_values: &[E], result: &mut [E]) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + E::from(1_u64));
+        result[1] = next[1];
+        result[2] = next[2] - (E::from(1_u64));
+    }
+
+    fn evaluate_aux_transition<F, E>(&self, main_frame: &EvaluationFrame<F>, aux_frame: &EvaluationFrame<E>, periodic_values: &[F], aux_rand_elements: &AuxTraceRandElements<E>, result: &mut [E])
+    where F: FieldElement<BaseField = Felt>,
+          E: FieldElement<BaseField = Felt> + ExtensionOf<F>,
+    {
+        let main_current = main_frame.current();
+        let main_next = main_frame.next();
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
+        result[0] = aux_next[0] - (aux_current[0] + E::from(1_u64) + aux_rand_elements.get_segment_elements(0)[0]);
+    }
+}
\ No newline at end of file
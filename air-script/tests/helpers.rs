@@ -1,13 +1,17 @@
 use codegen_winter::CodeGenerator;
+use diagnostics::SourceFiles;
 use ir::AirIR;
-use parser::parse;
-use std::fs;
+use parser::parse_program;
+use std::{fs, path::Path};
 
+// the error messages are only read through the Debug output of failed tests.
+#[allow(dead_code)]
 #[derive(Debug)]
 pub enum TestError {
     IO(String),
     Parse(String),
     IR(String),
+    Codegen(String),
 }
 
 pub struct Test {
@@ -28,15 +32,16 @@ impl Test {
             ))
         })?;
 
-        // parse the input file to the internal representation
-        let parsed = parse(source.as_str()).map_err(|_| {
+        // parse the input file and the modules it imports to the internal representation
+        let path = Path::new(&self.input_path);
+        let parsed = parse_program(path, &source, &mut SourceFiles::default()).map_err(|_| {
             TestError::Parse(format!(
                 "Failed to parse the input air file at {}",
                 &self.input_path
             ))
         })?;
 
-        let ir = AirIR::from_source(&parsed).map_err(|_| {
+        let ir = AirIR::from_program(&parsed).map_err(|_| {
             TestError::IR(format!(
                 "Failed to convert the input air file at {} to IR representation",
                 &self.input_path
@@ -44,7 +49,12 @@ impl Test {
         })?;
 
         // generate Rust code targeting Winterfell
-        let codegen = CodeGenerator::new(&ir);
+        let codegen = CodeGenerator::new(&ir).map_err(|err| {
+            TestError::Codegen(format!(
+                "Failed to generate Winterfell code for the input air file at {} - {}",
+                &self.input_path, err
+            ))
+        })?;
         Ok(codegen.generate())
     }
 }
@@ -1,11 +1,21 @@
 use expect_test::expect_file;
 
 mod helpers;
-use helpers::Test;
+use helpers::{Test, TestError};
 
 // TESTS
 // ================================================================================================
 
+#[test]
+fn assertions() {
+    let generated_air = Test::new("tests/assertions/assertions.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["assertions/assertions.rs"];
+    expected.assert_eq(&generated_air);
+}
+
 #[test]
 fn aux_trace() {
     let generated_air = Test::new("tests/aux_trace/aux_trace.air".to_string())
@@ -16,6 +26,23 @@ fn aux_trace() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn err_aux_trace_without_constraints() {
+    let result =
+        Test::new("tests/aux_trace/no_aux_transition_constraints.air".to_string()).transpile();
+    assert!(matches!(result, Err(TestError::Codegen(_))));
+
+    let result =
+        Test::new("tests/aux_trace/no_aux_boundary_constraints.air".to_string()).transpile();
+    assert!(matches!(result, Err(TestError::Codegen(_))));
+}
+
+#[test]
+fn err_aux_segments_unsupported_by_winterfell() {
+    let result = Test::new("tests/aux_segments/aux_segments.air".to_string()).transpile();
+    assert!(matches!(result, Err(TestError::Codegen(_))));
+}
+
 #[test]
 fn binary() {
     let generated_air = Test::new("tests/binary/binary.air".to_string())
@@ -26,6 +53,76 @@ fn binary() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn constants() {
+    let generated_air = Test::new("tests/constants/constants.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["constants/constants.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn division() {
+    let generated_air = Test::new("tests/division/division.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["division/division.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn evaluators() {
+    let generated_air = Test::new("tests/evaluators/evaluators.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["evaluators/evaluators.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn fields() {
+    let generated_air = Test::new("tests/fields/fields.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["fields/fields.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn list_comprehension() {
+    let generated_air = Test::new("tests/list_comprehension/list_comprehension.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["list_comprehension/list_comprehension.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn modules() {
+    let generated_air = Test::new("tests/modules/modules.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["modules/modules.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn negation() {
+    let generated_air = Test::new("tests/negation/negation.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["negation/negation.rs"];
+    expected.assert_eq(&generated_air);
+}
+
 #[test]
 fn periodic_columns() {
     let generated_air = Test::new("tests/periodic_columns/periodic_columns.air".to_string())
@@ -46,6 +143,43 @@ fn pub_inputs() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn row_offsets() {
+    let generated_air = Test::new("tests/row_offsets/row_offsets.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["row_offsets/row_offsets.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn err_row_offsets_outside_of_winterfell_frame() {
+    let result = Test::new("tests/row_offsets/wide_frame.air".to_string()).transpile();
+    assert!(matches!(result, Err(TestError::Codegen(_))));
+}
+
+#[test]
+fn selectors() {
+    let generated_air = Test::new("tests/selectors/selectors.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["selectors/selectors.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn shared_subexpressions() {
+    let generated_air =
+        Test::new("tests/shared_subexpressions/shared_subexpressions.air".to_string())
+            .transpile()
+            .unwrap();
+
+    let expected = expect_file!["shared_subexpressions/shared_subexpressions.rs"];
+    expected.assert_eq(&generated_air);
+}
+
 #[test]
 fn system() {
     let generated_air = Test::new("tests/system/system.air".to_string())
@@ -55,3 +189,23 @@ fn system() {
     let expected = expect_file!["system/system.rs"];
     expected.assert_eq(&generated_air);
 }
+
+#[test]
+fn trace_column_groups() {
+    let generated_air = Test::new("tests/trace_column_groups/trace_column_groups.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["trace_column_groups/trace_column_groups.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn variables() {
+    let generated_air = Test::new("tests/variables/variables.air".to_string())
+        .transpile()
+        .unwrap();
+
+    let expected = expect_file!["variables/variables.rs"];
+    expected.assert_eq(&generated_air);
+}
@@ -1,6 +1,8 @@
 use winter_air::{Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, ProofOptions as WinterProofOptions, TransitionConstraintDegree, TraceInfo};
-use winter_math::{fields, ExtensionOf, FieldElement};
-use winter_utils::{collections, ByteWriter, Serializable};
+use winter_math::fields::f64::BaseElement as Felt;
+use winter_math::{ExtensionOf, FieldElement};
+use winter_utils::collections::Vec;
+use winter_utils::{ByteWriter, Serializable};
 
 pub struct PublicInputs {
     program_hash: [Felt; 4],
@@ -36,6 +38,10 @@ impl PubInputsAir {
     pub fn last_step(&self) -> usize {
         self.trace_length() - self.context().num_transition_exemptions()
     }
+
+    pub fn aux_segment_rand_elements() -> [usize; 0] {
+        []
+    }
 }
 
 impl Air for PubInputsAir {
@@ -47,10 +53,12 @@ impl Air for PubInputsAir {
     }
 
     fn new(trace_info: TraceInfo, public_inputs: PublicInputs, options: WinterProofOptions) -> Self {
-        let main_degrees = vec![TransitionConstraintDegree::new(1)];
-        let aux_degrees = Vec::new();
+        let main_degrees = vec![TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(1)];
+        let aux_degrees = vec![];
         let num_main_assertions = 8;
         let num_aux_assertions = 0;
+        assert_eq!(trace_info.layout().main_trace_width(), 4, "invalid main trace width");
+        assert_eq!(trace_info.layout().num_aux_segments(), 0, "invalid number of auxiliary trace segments");
 
         let context = AirContext::new_multi_segment(
             trace_info,
@@ -60,7 +68,7 @@ impl Air for PubInputsAir {
             num_aux_assertions,
             options,
         )
-        .set_num_transition_exemptions(2);
+        .set_num_transition_exemptions(1);
         Self { context, program_hash: public_inputs.program_hash, stack_inputs: public_inputs.stack_inputs, stack_outputs: public_inputs.stack_outputs, overflow_addrs: public_inputs.overflow_addrs }
     }
 
@@ -91,13 +99,16 @@ impl Air for PubInputsAir {
         let current = frame.current();
         let next = frame.next();
         result[0] = next[0] - (current[1] + current[2]);
+        result[1] = next[3] - ((current[3]) * (E::from(self.program_hash[0])) + E::from(self.overflow_addrs[3]));
     }
 
-    fn evaluate_aux_transition<F, E>(&self, main_frame: &EvaluationFrame<F>, aux_frame: &EvaluationFrame<E>, _periodic_values: &[F], aux_rand_elements: &AuxTraceRandElements<E>, result: &mut [E])
+    fn evaluate_aux_transition<F, E>(&self, main_frame: &EvaluationFrame<F>, aux_frame: &EvaluationFrame<E>, periodic_values: &[F], aux_rand_elements: &AuxTraceRandElements<E>, result: &mut [E])
     where F: FieldElement<BaseField = Felt>,
           E: FieldElement<BaseField = Felt> + ExtensionOf<F>,
     {
-        let current = aux_frame.current();
-        let next = aux_frame.next();
+        let main_current = main_frame.current();
+        let main_next = main_frame.next();
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
     }
 }
\ No newline at end of file
@@ -0,0 +1,168 @@
+use winter_air::{EvaluationFrame, FieldExtension, HashFunction, ProofOptions, TraceLayout};
+use winter_math::{fields::f64::BaseElement as Felt, FieldElement};
+use winter_prover::{Matrix, Prover, Trace};
+
+// GENERATED AIRS
+// ================================================================================================
+
+/// The Air generated from `tests/aux_trace/aux_trace.air`, which is kept up to date by the
+/// `aux_trace` test in `main.rs`.
+#[allow(dead_code, unused_variables, clippy::all)]
+mod aux_trace {
+    include!("aux_trace/aux_trace.rs");
+}
+
+use aux_trace::{AuxiliaryAir, PublicInputs};
+
+// AUX TRACE
+// ================================================================================================
+
+const TRACE_LENGTH: usize = 32;
+
+/// An execution trace for [AuxiliaryAir], where the main trace contains a counter `a` starting at
+/// `start` and a column `b` holding the same values in reverse order, and the auxiliary trace
+/// contains the running product `p0` and the running sum `p1` which check that the values of `b`
+/// are a permutation of the values of `a`.
+///
+/// The values of `b` in the steps which are exempt from the transition constraints are zeros, so
+/// that the constraints don't hold over the whole trace. Otherwise, the degrees of the constraint
+/// evaluations would be lower than the degrees declared by the Air.
+struct AuxTrace {
+    layout: TraceLayout,
+    main: Matrix<Felt>,
+    meta: Vec<u8>,
+}
+
+impl AuxTrace {
+    fn new(start: u64) -> Self {
+        // the last two steps are exempt from the transition constraints, so the permutation only
+        // covers the steps before them.
+        let num_steps = TRACE_LENGTH - 2;
+        let a: Vec<Felt> = (0..TRACE_LENGTH as u64)
+            .map(|i| Felt::new(start + i))
+            .collect();
+        let mut b: Vec<Felt> = a[..num_steps].iter().rev().copied().collect();
+        b.resize(TRACE_LENGTH, Felt::ZERO);
+
+        Self {
+            layout: TraceLayout::new(2, [2], AuxiliaryAir::aux_segment_rand_elements()),
+            main: Matrix::new(vec![a, b]),
+            meta: Vec::new(),
+        }
+    }
+}
+
+impl Trace for AuxTrace {
+    type BaseField = Felt;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.main.num_rows()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &self.meta
+    }
+
+    fn main_segment(&self) -> &Matrix<Felt> {
+        &self.main
+    }
+
+    fn build_aux_segment<E: FieldElement<BaseField = Felt>>(
+        &mut self,
+        aux_segments: &[Matrix<E>],
+        rand_elements: &[E],
+    ) -> Option<Matrix<E>> {
+        // there is a single auxiliary segment.
+        if !aux_segments.is_empty() {
+            return None;
+        }
+
+        let mut p0 = vec![E::ONE; self.length()];
+        let mut p1 = vec![rand_elements[0]; self.length()];
+        for i in 0..self.length() - 1 {
+            let a = E::from(self.main.get(0, i));
+            let b = E::from(self.main.get(1, i));
+            p0[i + 1] = p0[i] * (a + rand_elements[0]) / (b + rand_elements[0]);
+            p1[i + 1] = p1[i] + rand_elements[1] * (a - b);
+        }
+
+        Some(Matrix::new(vec![p0, p1]))
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Felt>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.main.read_row_into(row_idx, frame.current_mut());
+        self.main.read_row_into(next_row_idx, frame.next_mut());
+    }
+}
+
+// AUX TRACE PROVER
+// ================================================================================================
+
+struct AuxTraceProver {
+    options: ProofOptions,
+}
+
+impl Prover for AuxTraceProver {
+    type BaseField = Felt;
+    type Air = AuxiliaryAir;
+    type Trace = AuxTrace;
+
+    fn get_pub_inputs(&self, trace: &AuxTrace) -> PublicInputs {
+        stack_inputs(trace.main.get(0, 0))
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+fn stack_inputs(start: Felt) -> PublicInputs {
+    let mut stack_inputs = [Felt::ZERO; 16];
+    stack_inputs[0] = start;
+    PublicInputs::new(stack_inputs)
+}
+
+fn build_options() -> ProofOptions {
+    ProofOptions::new(
+        28,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::Quadratic,
+        4,
+        32,
+    )
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn aux_trace_prove_verify() {
+    let prover = AuxTraceProver {
+        options: build_options(),
+    };
+    let proof = prover
+        .prove(AuxTrace::new(3))
+        .expect("Proof generation failed");
+
+    winter_verifier::verify::<AuxiliaryAir>(proof, stack_inputs(Felt::new(3)))
+        .expect("Proof verification failed");
+}
+
+#[test]
+fn aux_trace_verify_wrong_public_inputs() {
+    let prover = AuxTraceProver {
+        options: build_options(),
+    };
+    let proof = prover
+        .prove(AuxTrace::new(3))
+        .expect("Proof generation failed");
+
+    assert!(winter_verifier::verify::<AuxiliaryAir>(proof, stack_inputs(Felt::new(4))).is_err());
+}
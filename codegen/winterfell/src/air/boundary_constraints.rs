@@ -1,5 +1,5 @@
-use super::{AirIR, Codegen, Impl};
-use ir::BoundaryExpr;
+use super::{const_to_string, AirIR, Codegen, Impl};
+use ir::{AssertionRows, AssertionValue, BoundaryExpr};
 
 // HELPERS TO GENERATE THE WINTERFELL BOUNDARY CONSTRAINT METHODS
 // ================================================================================================
@@ -16,31 +16,19 @@ pub(super) fn add_fn_get_assertions(impl_ref: &mut Impl, ir: &AirIR) {
     // declare the result vector to be returned.
     get_assertions.line("let mut result = Vec::new();");
 
-    // add the constraints for the first boundary
-    for (col_idx, constraint) in ir.main_first_boundary_constraints() {
+    // add the constraints, declaring the last step before the first constraint at the last row.
+    let constraints = ir.main_boundary_constraints();
+    for (idx, (col_idx, rows, value)) in constraints.iter().enumerate() {
+        if is_first_at_last_row(&constraints, idx) {
+            get_assertions.line("let last_step = self.last_step();");
+        }
         let assertion = format!(
-            "result.push(Assertion::single({}, 0, {}));",
-            col_idx,
-            constraint.to_string(false)
+            "result.push({});",
+            assertion_to_string(*col_idx, *rows, value, false)
         );
         get_assertions.line(assertion);
     }
 
-    // add the constraints for the last boundary.
-    let last_constraints = ir.main_last_boundary_constraints();
-
-    if !last_constraints.is_empty() {
-        get_assertions.line("let last_step = self.last_step();");
-        for (col_idx, constraint) in last_constraints {
-            let assertion = format!(
-                "result.push(Assertion::single({}, last_step, {}));",
-                col_idx,
-                constraint.to_string(false)
-            );
-            get_assertions.line(assertion);
-        }
-    }
-
     // return the result
     get_assertions.line("result");
 }
@@ -59,33 +47,79 @@ pub(super) fn add_fn_get_aux_assertions(impl_ref: &mut Impl, ir: &AirIR) {
     // declare the result vector to be returned.
     get_aux_assertions.line("let mut result = Vec::new();");
 
-    // add the constraints for the auxiliary columns for the first boundary.
-    for (col_idx, constraint) in ir.aux_first_boundary_constraints() {
+    // add the constraints for the auxiliary columns, declaring the last step before the first
+    // constraint at the last row.
+    let constraints = ir.aux_boundary_constraints();
+    for (idx, (col_idx, rows, value)) in constraints.iter().enumerate() {
+        if is_first_at_last_row(&constraints, idx) {
+            get_aux_assertions.line("let last_step = self.last_step();");
+        }
         let assertion = format!(
-            "result.push(Assertion::single({}, 0, {}));",
-            col_idx,
-            constraint.to_string(true)
+            "result.push({});",
+            assertion_to_string(*col_idx, *rows, value, true)
         );
         get_aux_assertions.line(assertion);
     }
 
-    let last_aux_constraints = ir.aux_last_boundary_constraints();
+    // return the result
+    get_aux_assertions.line("result");
+}
 
-    if !last_aux_constraints.is_empty() {
-        get_aux_assertions.line("let last_step = self.last_step();");
-        // add the constraints for the auxiliary columns for the last boundary.
-        for (col_idx, constraint) in last_aux_constraints {
-            let assertion = format!(
-                "result.push(Assertion::single({}, last_step, {}));",
-                col_idx,
-                constraint.to_string(true)
-            );
-            get_aux_assertions.line(assertion);
+/// Returns true if the constraint at the specified index is the first of the provided constraints
+/// which is applied to the last row.
+fn is_first_at_last_row(
+    constraints: &[(usize, AssertionRows, &AssertionValue)],
+    idx: usize,
+) -> bool {
+    constraints[idx].1 == AssertionRows::Last
+        && (idx == 0 || constraints[idx - 1].1 != AssertionRows::Last)
+}
+
+/// Returns the Winterfell assertion for the boundary constraint with the provided column, rows and
+/// value.
+fn assertion_to_string(
+    col_idx: usize,
+    rows: AssertionRows,
+    value: &AssertionValue,
+    is_aux_constraint: bool,
+) -> String {
+    match (rows, value) {
+        (AssertionRows::Single(row), AssertionValue::Single(expr)) => format!(
+            "Assertion::single({}, {}, {})",
+            col_idx,
+            row,
+            expr.to_string(is_aux_constraint)
+        ),
+        (AssertionRows::Last, AssertionValue::Single(expr)) => format!(
+            "Assertion::single({}, last_step, {})",
+            col_idx,
+            expr.to_string(is_aux_constraint)
+        ),
+        (AssertionRows::Periodic { first_row, stride }, AssertionValue::Single(expr)) => format!(
+            "Assertion::periodic({}, {}, {}, {})",
+            col_idx,
+            first_row,
+            stride,
+            expr.to_string(is_aux_constraint)
+        ),
+        (AssertionRows::Periodic { first_row, stride }, AssertionValue::Sequence(name, _)) => {
+            // public inputs are elements of the base field.
+            let values = if is_aux_constraint {
+                format!(
+                    "self.{}.iter().map(|value| E::from(*value)).collect()",
+                    name
+                )
+            } else {
+                format!("self.{}.to_vec()", name)
+            };
+            format!(
+                "Assertion::sequence({}, {}, {}, {})",
+                col_idx, first_row, stride, values
+            )
         }
+        // the IR only builds sequence constraints for periodic rows.
+        (_, AssertionValue::Sequence(..)) => unreachable!(),
     }
-
-    // return the result
-    get_aux_assertions.line("result");
 }
 
 // RUST STRING GENERATION
@@ -98,14 +132,24 @@ impl Codegen for BoundaryExpr {
         match self {
             Self::Const(value) => {
                 if is_aux_constraint {
-                    format!("E::from({}_u64)", value)
+                    const_to_string(*value)
                 } else {
                     format!("Felt::new({})", value)
                 }
             }
-            Self::PubInput(name, index) => format!("self.{}[{}]", name, index),
-            Self::Rand(index) => {
-                format!("aux_rand_elements.get_segment_elements(0)[{}]", index)
+            Self::PubInput(name, index) => {
+                // public inputs are elements of the base field.
+                if is_aux_constraint {
+                    format!("E::from(self.{}[{}])", name, index)
+                } else {
+                    format!("self.{}[{}]", name, index)
+                }
+            }
+            Self::Rand(segment, index) => {
+                format!(
+                    "aux_rand_elements.get_segment_elements({})[{}]",
+                    segment, index
+                )
             }
             Self::Add(lhs, rhs) => {
                 format!(
@@ -131,6 +175,12 @@ impl Codegen for BoundaryExpr {
             Self::Exp(lhs, rhs) => {
                 format!("({}).exp({})", lhs.to_string(is_aux_constraint), rhs)
             }
+            Self::Inv(expr) => {
+                format!("({}).inv()", expr.to_string(is_aux_constraint))
+            }
+            Self::Neg(expr) => {
+                format!("- ({})", expr.to_string(is_aux_constraint))
+            }
         }
     }
 }
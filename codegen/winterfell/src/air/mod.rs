@@ -1,4 +1,4 @@
-use super::{AirIR, Impl, Scope};
+use super::{AirIR, Function, Impl, Scope};
 use ir::TransitionConstraintDegree;
 
 mod public_inputs;
@@ -53,6 +53,18 @@ fn add_air_struct(scope: &mut Scope, ir: &AirIR, name: &str) {
         .vis("pub")
         .ret("usize")
         .line("self.trace_length() - self.context().num_transition_exemptions()");
+
+    // add a method to get the number of random elements required by each auxiliary trace segment,
+    // which is needed to build the layout of the execution trace.
+    let num_aux_segments = ir.aux_segment_widths().len();
+    let aux_segment_rand_elements: Vec<String> = (0..num_aux_segments)
+        .map(|segment| ir.num_random_values(segment).to_string())
+        .collect();
+    base_impl
+        .new_fn("aux_segment_rand_elements")
+        .vis("pub")
+        .ret(format!("[usize; {}]", num_aux_segments))
+        .line(format!("[{}]", aux_segment_rand_elements.join(", ")));
 }
 
 /// Updates the provided scope with the custom Air struct and an Air trait implementation based on
@@ -108,7 +120,14 @@ fn add_fn_new(impl_ref: &mut Impl, ir: &AirIR) {
     ));
 
     // define the transition constraint degrees of the aux trace `aux_degrees`.
-    new.line("let aux_degrees = Vec::new();");
+    let mut aux_degrees: Vec<String> = Vec::new();
+    for degree in ir.aux_degrees().iter() {
+        aux_degrees.push(degree.to_string(true));
+    }
+    new.line(format!(
+        "let aux_degrees = vec![{}];",
+        aux_degrees.join(", ")
+    ));
 
     // define the number of main trace boundary constraints `num_main_assertions`.
     new.line(format!(
@@ -117,10 +136,17 @@ fn add_fn_new(impl_ref: &mut Impl, ir: &AirIR) {
     ));
 
     // define the number of aux trace boundary constraints `num_aux_assertions`.
-    new.line("let num_aux_assertions = 0;");
+    new.line(format!(
+        "let num_aux_assertions = {};",
+        ir.num_aux_assertions()
+    ));
+
+    // make sure the layout of the provided trace matches the declared trace columns.
+    add_trace_layout_validation(new, ir);
 
     // define the context.
-    let context = "
+    let context = format!(
+        "
 let context = AirContext::new_multi_segment(
     trace_info,
     main_degrees,
@@ -129,7 +155,9 @@ let context = AirContext::new_multi_segment(
     num_aux_assertions,
     options,
 )
-.set_num_transition_exemptions(2);";
+.set_num_transition_exemptions({});",
+        ir.num_transition_exemptions()
+    );
 
     new.line(context);
 
@@ -142,6 +170,37 @@ let context = AirContext::new_multi_segment(
     new.line(format!("Self {{ context, {} }}", pub_inputs.join(", ")));
 }
 
+/// Adds assertions to the referenced function which check that the layout of the execution trace
+/// described by `trace_info` matches the trace columns and random values declared in the AirIR.
+fn add_trace_layout_validation(func: &mut Function, ir: &AirIR) {
+    func.line(format!(
+        "assert_eq!(trace_info.layout().main_trace_width(), {}, \"invalid main trace width\");",
+        ir.main_trace_width()
+    ));
+
+    // each declared segment of the auxiliary trace must have the declared width, and must be
+    // built with at least as many random elements as the constraints reference.
+    let aux_segment_widths = ir.aux_segment_widths();
+    func.line(format!(
+        "assert_eq!(trace_info.layout().num_aux_segments(), {}, \"invalid number of auxiliary trace segments\");",
+        aux_segment_widths.len()
+    ));
+    for (segment, width) in aux_segment_widths.iter().enumerate() {
+        func.line(format!(
+            "assert_eq!(trace_info.layout().get_aux_segment_width({}), {}, \"invalid width of auxiliary trace segment {}\");",
+            segment, width, segment
+        ));
+        // a segment which doesn't use any random values doesn't need to check their number.
+        let num_random_values = ir.num_random_values(segment);
+        if num_random_values > 0 {
+            func.line(format!(
+                "assert!(trace_info.layout().get_aux_segment_rand_elements({}) >= {}, \"not enough random elements for auxiliary trace segment {}\");",
+                segment, num_random_values, segment
+            ));
+        }
+    }
+}
+
 // RUST STRING GENERATION
 // ================================================================================================
 
@@ -150,6 +209,17 @@ pub trait Codegen {
     fn to_string(&self, is_aux_constraint: bool) -> String;
 }
 
+/// Returns the conversion of the provided constant into a field element of type `E`. Constants
+/// which fit into 64 bits are converted from a u64, and larger elements of the 128-bit field from a
+/// u128.
+fn const_to_string(value: u128) -> String {
+    if u64::try_from(value).is_ok() {
+        format!("E::from({}_u64)", value)
+    } else {
+        format!("E::from({}_u128)", value)
+    }
+}
+
 impl Codegen for TransitionConstraintDegree {
     fn to_string(&self, _is_aux_constraint: bool) -> String {
         if self.cycles().is_empty() {
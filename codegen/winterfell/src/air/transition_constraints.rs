@@ -1,8 +1,79 @@
-use super::{AirIR, Impl};
+use super::{const_to_string, AirIR, Impl};
 use ir::{
     transition_constraints::{AlgebraicGraph, Operation},
     NodeIndex,
 };
+use std::collections::{BTreeMap, BTreeSet};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The names of the arguments and local variables of the generated methods and the Rust keywords,
+/// which can't be used as the names of the local variables declared for AirScript variables.
+const RESERVED_NAMES: [&str; 62] = [
+    "frame",
+    "current",
+    "next",
+    "periodic_values",
+    "result",
+    "main_frame",
+    "aux_frame",
+    "main_current",
+    "main_next",
+    "aux_current",
+    "aux_next",
+    "aux_rand_elements",
+    "abstract",
+    "as",
+    "async",
+    "await",
+    "become",
+    "box",
+    "break",
+    "const",
+    "continue",
+    "crate",
+    "do",
+    "dyn",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "final",
+    "fn",
+    "for",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "macro",
+    "match",
+    "mod",
+    "move",
+    "mut",
+    "override",
+    "priv",
+    "pub",
+    "ref",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "super",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "unsafe",
+    "unsized",
+    "use",
+    "virtual",
+    "where",
+    "while",
+    "yield",
+];
 
 // HELPERS TO GENERATE THE WINTERFELL TRANSITION CONSTRAINT METHODS
 // ================================================================================================
@@ -23,13 +94,19 @@ pub(super) fn add_fn_evaluate_transition(impl_ref: &mut Impl, ir: &AirIR) {
     evaluate_transition.line("let current = frame.current();");
     evaluate_transition.line("let next = frame.next();");
 
+    // declare the variables referenced by the constraints.
+    let constraints = ir.main_transition_constraints();
+    let context = Context::new(ir, constraints, false);
+    for line in context.variable_declarations() {
+        evaluate_transition.line(line);
+    }
+
     // output the constraints.
-    let graph = ir.transition_graph();
-    for (idx, constraint) in ir.main_transition_constraints().iter().enumerate() {
+    for (idx, constraint) in constraints.iter().enumerate() {
         evaluate_transition.line(format!(
             "result[{}] = {};",
             idx,
-            constraint.to_string(graph)
+            constraint.to_string(&context)
         ));
     }
 }
@@ -44,83 +121,257 @@ pub(super) fn add_fn_evaluate_aux_transition(impl_ref: &mut Impl, ir: &AirIR) {
         .arg_ref_self()
         .arg("main_frame", "&EvaluationFrame<F>")
         .arg("aux_frame", "&EvaluationFrame<E>")
-        .arg("_periodic_values", "&[F]")
+        .arg("periodic_values", "&[F]")
         .arg("aux_rand_elements", "&AuxTraceRandElements<E>")
         .arg("result", "&mut [E]")
         .bound("F", "FieldElement<BaseField = Felt>")
         .bound("E", "FieldElement<BaseField = Felt> + ExtensionOf<F>");
 
-    // declare current and next trace row arrays.
-    evaluate_aux_transition.line("let current = aux_frame.current();");
-    evaluate_aux_transition.line("let next = aux_frame.next();");
+    // declare current and next trace row arrays of both the main and the auxiliary traces.
+    evaluate_aux_transition.line("let main_current = main_frame.current();");
+    evaluate_aux_transition.line("let main_next = main_frame.next();");
+    evaluate_aux_transition.line("let aux_current = aux_frame.current();");
+    evaluate_aux_transition.line("let aux_next = aux_frame.next();");
+
+    // declare the variables referenced by the constraints.
+    let constraints = ir.aux_transition_constraints();
+    let context = Context::new(ir, constraints, true);
+    for line in context.variable_declarations() {
+        evaluate_aux_transition.line(line);
+    }
 
     // output the constraints.
-    let graph = ir.transition_graph();
-    for (idx, constraint) in ir.aux_transition_constraints().iter().enumerate() {
+    for (idx, constraint) in constraints.iter().enumerate() {
         evaluate_aux_transition.line(format!(
             "result[{}] = {};",
             idx,
-            constraint.to_string(graph)
+            constraint.to_string(&context)
         ));
     }
 }
 
+// RENDERING CONTEXT
+// ================================================================================================
+
+/// The context in which the transition constraints of one of the generated methods are rendered.
+struct Context<'a> {
+    graph: &'a AlgebraicGraph,
+    /// The names of the local variables declared for the AirScript variables and the shared
+    /// subexpressions referenced by the constraints, keyed by the indices of their nodes.
+    variables: BTreeMap<NodeIndex, String>,
+    /// Whether the constraints are against the auxiliary trace.
+    is_aux_constraint: bool,
+}
+
+impl<'a> Context<'a> {
+    /// Returns the context for rendering the specified constraints, declaring a local variable for
+    /// each AirScript variable which is referenced by them.
+    ///
+    /// A local variable `tN` is also declared for each other operation which is referenced more
+    /// than once, so that the subexpressions shared by several constraints, or by several parts of
+    /// the same constraint, are only evaluated once.
+    fn new(ir: &'a AirIR, constraints: &[NodeIndex], is_aux_constraint: bool) -> Self {
+        let graph = ir.transition_graph();
+
+        let variable_nodes: BTreeSet<NodeIndex> = ir
+            .transition_variables()
+            .iter()
+            .map(|(_, index)| *index)
+            .collect();
+        let mut uses = BTreeMap::new();
+        for constraint in constraints {
+            count_uses(graph, constraint, &variable_nodes, &mut uses);
+        }
+
+        // variables with the same value share a node, in which case the first name is used.
+        let mut names = BTreeSet::new();
+        let mut variables = BTreeMap::new();
+        for (name, index) in ir.transition_variables() {
+            if uses.contains_key(index) && !variables.contains_key(index) {
+                variables.insert(*index, unique_name(name, &mut names));
+            }
+        }
+
+        // reading a trace cell, a periodic value or a random value is not worth a local variable.
+        let shared_ops = uses.iter().filter(|&(index, &num_uses)| {
+            num_uses > 1
+                && !variables.contains_key(index)
+                && matches!(
+                    graph.node(index).op(),
+                    Operation::Neg(_)
+                        | Operation::Add(..)
+                        | Operation::Mul(..)
+                        | Operation::Exp(..)
+                )
+        });
+        let shared_ops: Vec<NodeIndex> = shared_ops.map(|(index, _)| *index).collect();
+        for (i, index) in shared_ops.into_iter().enumerate() {
+            variables.insert(index, unique_name(&format!("t{}", i), &mut names));
+        }
+
+        Self {
+            graph,
+            variables,
+            is_aux_constraint,
+        }
+    }
+
+    /// Returns the declarations of the local variables. They are ordered by the indices of their
+    /// nodes, so each variable is declared after the variables its value references.
+    fn variable_declarations(&self) -> Vec<String> {
+        self.variables
+            .iter()
+            .map(|(index, name)| {
+                let value = self.graph.node(index).op().to_string(self);
+                format!("let {} = {};", name, value)
+            })
+            .collect()
+    }
+}
+
+/// Counts the number of times each node of the subgraph with the specified tip is rendered when the
+/// tip is rendered, assuming that each node is rendered once regardless of the number of its
+/// parents. The children of a node are only visited the first time the node is reached.
+///
+/// The negation which is the right operand of an addition is rendered as a subtraction of its
+/// operand, so the operand is counted instead of the negation, unless the negation is the value of
+/// one of the specified variable nodes, which are rendered as their local variables.
+fn count_uses(
+    graph: &AlgebraicGraph,
+    index: &NodeIndex,
+    variable_nodes: &BTreeSet<NodeIndex>,
+    uses: &mut BTreeMap<NodeIndex, usize>,
+) {
+    let num_uses = uses.entry(*index).or_insert(0);
+    *num_uses += 1;
+    if *num_uses > 1 {
+        return;
+    }
+    match graph.node(index).op() {
+        Operation::Neg(idx) | Operation::Exp(idx, _) => {
+            count_uses(graph, idx, variable_nodes, uses)
+        }
+        Operation::Add(l_idx, r_idx) => {
+            count_uses(graph, l_idx, variable_nodes, uses);
+            match graph.node(r_idx).op() {
+                Operation::Neg(n_idx) if !variable_nodes.contains(r_idx) => {
+                    count_uses(graph, n_idx, variable_nodes, uses)
+                }
+                _ => count_uses(graph, r_idx, variable_nodes, uses),
+            }
+        }
+        Operation::Mul(l_idx, r_idx) => {
+            count_uses(graph, l_idx, variable_nodes, uses);
+            count_uses(graph, r_idx, variable_nodes, uses);
+        }
+        _ => {}
+    }
+}
+
+/// Returns a name for the local variable declared for an AirScript variable, which is a valid Rust
+/// identifier in snake case distinct from all reserved and previously used names.
+fn unique_name(name: &str, used_names: &mut BTreeSet<String>) -> String {
+    let name = name.to_lowercase();
+    let mut unique_name = name.clone();
+    let mut suffix = 1;
+    while RESERVED_NAMES.contains(&unique_name.as_str()) || used_names.contains(&unique_name) {
+        unique_name = format!("{}_{}", name, suffix);
+        suffix += 1;
+    }
+    used_names.insert(unique_name.clone());
+    unique_name
+}
+
 // RUST STRING GENERATION
 // ================================================================================================
 
 /// Code generation trait for generating Rust code strings from [AlgebraicGraph] types.
+///
+/// In the constraints against the auxiliary trace, the main trace and the periodic values are
+/// elements of the base field, so they are read from the main frame and converted into elements of
+/// the extension field.
 trait Codegen {
-    fn to_string(&self, graph: &AlgebraicGraph) -> String;
+    fn to_string(&self, context: &Context) -> String;
 }
 
 impl Codegen for NodeIndex {
-    fn to_string(&self, graph: &AlgebraicGraph) -> String {
-        let op = graph.node(self).op();
-        op.to_string(graph)
+    fn to_string(&self, context: &Context) -> String {
+        // nodes representing the values of variables are replaced by their local variables.
+        if let Some(name) = context.variables.get(self) {
+            return name.clone();
+        }
+        let op = context.graph.node(self).op();
+        op.to_string(context)
     }
 }
 
 impl Codegen for Operation {
     // TODO: Only add parentheses in Add and Mul if the expression is an arithmetic operation.
-    fn to_string(&self, graph: &AlgebraicGraph) -> String {
+    fn to_string(&self, context: &Context) -> String {
+        let graph = context.graph;
+        let is_aux_constraint = context.is_aux_constraint;
         match self {
-            Operation::Const(value) => format!("E::from({}_u64)", value),
-            Operation::MainTraceCurrentRow(col_idx) | Operation::AuxTraceCurrentRow(col_idx) => {
-                format!("current[{}]", col_idx)
+            Operation::Const(value) => const_to_string(*value),
+            Operation::MainTraceRow(col_idx, offset) => {
+                if is_aux_constraint {
+                    format!("E::from(main_{}[{}])", frame_row(*offset), col_idx)
+                } else {
+                    format!("{}[{}]", frame_row(*offset), col_idx)
+                }
             }
-            Operation::MainTraceNextRow(col_idx) | Operation::AuxTraceNextRow(col_idx) => {
-                format!("next[{}]", col_idx)
+            Operation::AuxTraceRow(col_idx, offset) => {
+                format!("aux_{}[{}]", frame_row(*offset), col_idx)
             }
             Operation::PeriodicColumn(col_idx, _) => {
-                format!("periodic_values[{}]", col_idx)
+                if is_aux_constraint {
+                    format!("E::from(periodic_values[{}])", col_idx)
+                } else {
+                    format!("periodic_values[{}]", col_idx)
+                }
             }
-            Operation::RandomValue(idx) => {
-                format!("aux_rand_elements.get_segment_elements(0)[{}]", idx)
+            Operation::PublicInput(name, idx) => format!("E::from(self.{}[{}])", name, idx),
+            Operation::RandomValue(segment, idx) => {
+                format!(
+                    "aux_rand_elements.get_segment_elements({})[{}]",
+                    segment, idx
+                )
             }
             Operation::Neg(idx) => {
-                let str = idx.to_string(graph);
+                let str = idx.to_string(context);
                 format!("- ({})", str)
             }
             Operation::Add(l_idx, r_idx) => {
-                let lhs = l_idx.to_string(graph);
+                let lhs = l_idx.to_string(context);
 
-                // output Add followed by Neg as "-"
-                let rhs = if let Operation::Neg(n_idx) = graph.node(r_idx).op() {
-                    format!("- ({})", n_idx.to_string(graph))
-                } else {
-                    format!("+ {}", r_idx.to_string(graph))
+                // output Add followed by Neg as "-", unless the Neg is rendered as a local variable
+                let rhs = match graph.node(r_idx).op() {
+                    Operation::Neg(n_idx) if !context.variables.contains_key(r_idx) => {
+                        format!("- ({})", n_idx.to_string(context))
+                    }
+                    _ => format!("+ {}", r_idx.to_string(context)),
                 };
                 format!("{} {}", lhs, rhs)
             }
             Operation::Mul(l_idx, r_idx) => {
-                let lhs = l_idx.to_string(graph);
-                let rhs = r_idx.to_string(graph);
+                let lhs = l_idx.to_string(context);
+                let rhs = r_idx.to_string(context);
                 format!("({}) * ({})", lhs, rhs)
             }
             Operation::Exp(l_idx, r_idx) => {
-                let lhs = l_idx.to_string(graph);
+                let lhs = l_idx.to_string(context);
                 format!("({}).exp(E::PositiveInteger::from({}_u64))", lhs, r_idx)
             }
         }
     }
 }
+
+/// Returns the name of the row of the evaluation frame at the specified offset from the current
+/// row. Winterfell frames only contain the current and the next row, and wider frames are rejected
+/// when the [CodeGenerator](crate::CodeGenerator) is instantiated.
+fn frame_row(offset: isize) -> &'static str {
+    match offset {
+        0 => "current",
+        1 => "next",
+        _ => unreachable!("row offset {} is outside of the evaluation frame", offset),
+    }
+}
@@ -1,7 +1,8 @@
-use super::Scope;
+use super::{AirIR, Scope};
 
-/// Adds the required imports to the provided scope.
-pub(super) fn add_imports(scope: &mut Scope) {
+/// Adds the required imports to the provided scope. The base field of the generated AIR is the
+/// Winterfell field matching the field declared by the AirIR.
+pub(super) fn add_imports(scope: &mut Scope, ir: &AirIR) {
     // add winterfell imports
     scope.import("winter_air", "Air");
     scope.import("winter_air", "AirContext");
@@ -11,10 +12,13 @@ pub(super) fn add_imports(scope: &mut Scope) {
     scope.import("winter_air", "ProofOptions as WinterProofOptions");
     scope.import("winter_air", "TransitionConstraintDegree");
     scope.import("winter_air", "TraceInfo");
-    scope.import("winter_math", "fields::f64::BaseElement as Felt");
+    scope.import(
+        &format!("winter_math::fields::{}", ir.field().name()),
+        "BaseElement as Felt",
+    );
     scope.import("winter_math", "ExtensionOf");
     scope.import("winter_math", "FieldElement");
-    scope.import("winter_utils", "collections::Vec");
+    scope.import("winter_utils::collections", "Vec");
     scope.import("winter_utils", "ByteWriter");
     scope.import("winter_utils", "Serializable");
 }
@@ -1,5 +1,6 @@
-use codegen::{Impl, Scope};
+use codegen::{Function, Impl, Scope};
 use ir::AirIR;
+use std::fmt::Display;
 
 mod imports;
 use imports::add_imports;
@@ -7,6 +8,17 @@ use imports::add_imports;
 mod air;
 use air::add_air;
 
+// CONSTANTS
+// ================================================================================================
+
+/// The number of rows in the evaluation frames of the targeted version of Winterfell, i.e. the
+/// current and the next row.
+const WINTERFELL_FRAME_WIDTH: usize = 2;
+
+/// The maximum number of auxiliary trace segments supported by the targeted version of
+/// Winterfell.
+const WINTERFELL_MAX_AUX_SEGMENTS: usize = 1;
+
 // GENERATE RUST CODE FOR WINTERFELL AIR
 // ================================================================================================
 
@@ -22,16 +34,40 @@ impl CodeGenerator {
 
     /// Builds a new Rust scope that represents a Winterfell Air trait implementation for the
     /// provided AirIR.
-    pub fn new(ir: &AirIR) -> Self {
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The transition constraints of the AirIR reference rows of the trace which are not
+    ///   available in the evaluation frames of Winterfell.
+    /// - The AirIR declares more auxiliary trace segments than Winterfell supports.
+    /// - The AirIR declares an auxiliary trace without any transition constraints or boundary
+    ///   constraints against it, which Winterfell requires for each auxiliary trace segment.
+    pub fn new(ir: &AirIR) -> Result<Self, CodegenError> {
+        if ir.frame_width() > WINTERFELL_FRAME_WIDTH {
+            return Err(CodegenError::UnsupportedFrameWidth(ir.frame_width()));
+        }
+        let num_aux_segments = ir.aux_segment_widths().len();
+        if num_aux_segments > WINTERFELL_MAX_AUX_SEGMENTS {
+            return Err(CodegenError::UnsupportedAuxSegments(num_aux_segments));
+        }
+        if num_aux_segments > 0 {
+            if ir.aux_transition_constraints().is_empty() {
+                return Err(CodegenError::MissingAuxTransitionConstraints);
+            }
+            if ir.num_aux_assertions() == 0 {
+                return Err(CodegenError::MissingAuxBoundaryConstraints);
+            }
+        }
+
         let mut scope = Scope::new();
 
         // add winterfell imports.
-        add_imports(&mut scope);
+        add_imports(&mut scope, ir);
 
         // add an Air struct and Winterfell Air trait implementation for the provided AirIR.
         add_air(&mut scope, ir);
 
-        Self { scope }
+        Ok(Self { scope })
     }
 
     /// Returns a string of Rust code containing a Winterfell Air implementation for the AirIR with
@@ -40,3 +76,46 @@ impl CodeGenerator {
         self.scope.to_string()
     }
 }
+
+// ERRORS
+// ================================================================================================
+
+/// Errors raised when an AirIR can't be expressed as a Winterfell Air implementation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodegenError {
+    /// The transition constraints are evaluated over a frame of the specified number of rows,
+    /// which is wider than the evaluation frames of Winterfell.
+    UnsupportedFrameWidth(usize),
+    /// The auxiliary trace is split into the specified number of segments, which is more than
+    /// Winterfell supports.
+    UnsupportedAuxSegments(usize),
+    /// An auxiliary trace was declared, but there are no transition constraints against it.
+    MissingAuxTransitionConstraints,
+    /// An auxiliary trace was declared, but there are no boundary constraints against it.
+    MissingAuxBoundaryConstraints,
+}
+
+impl Display for CodegenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedFrameWidth(width) => write!(
+                f,
+                "The transition constraints reference {} consecutive rows of the trace, but Winterfell only supports frames of {} rows (the current and the next row)",
+                width, WINTERFELL_FRAME_WIDTH
+            ),
+            Self::UnsupportedAuxSegments(num_segments) => write!(
+                f,
+                "The auxiliary trace is split into {} segments, but Winterfell only supports {} auxiliary trace segment",
+                num_segments, WINTERFELL_MAX_AUX_SEGMENTS
+            ),
+            Self::MissingAuxTransitionConstraints => write!(
+                f,
+                "An auxiliary trace was declared, but Winterfell requires at least one transition constraint against it"
+            ),
+            Self::MissingAuxBoundaryConstraints => write!(
+                f,
+                "An auxiliary trace was declared, but Winterfell requires at least one boundary constraint against it"
+            ),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use super::SourceSpan;
+use std::fmt::Display;
+
+// DIAGNOSTIC
+// ================================================================================================
+
+/// A message reported by the compiler about a problem in an AirScript source, such as a syntax or
+/// a semantic error.
+///
+/// A diagnostic is made of a short message, an optional error code identifying the kind of
+/// problem, labels pointing to the locations in the source which caused it, and notes with any
+/// additional information which may help to fix it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    severity: Severity,
+    code: Option<String>,
+    message: String,
+    labels: Vec<Label>,
+    notes: Vec<String>,
+}
+
+impl Diagnostic {
+    // --- CONSTRUCTORS ---------------------------------------------------------------------------
+
+    /// Returns a new diagnostic with the specified severity and message.
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code: None,
+            message: message.into(),
+            labels: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    /// Returns a new error diagnostic with the specified message.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    /// Sets the code identifying the kind of the reported problem, e.g. "E0101".
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Adds a label pointing to a location in the source.
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    /// Adds a note with additional information about the reported problem.
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    // --- ACCESSORS ------------------------------------------------------------------------------
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+}
+
+/// Describes how serious the problem reported by a [Diagnostic] is.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+// LABEL
+// ================================================================================================
+
+/// A location in the source referenced by a [Diagnostic], with an optional message describing it.
+///
+/// Primary labels point to the cause of the problem, while secondary labels point to related
+/// locations, such as a previous declaration of a duplicated identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    span: SourceSpan,
+    message: String,
+    is_primary: bool,
+}
+
+impl Label {
+    /// Returns a new label pointing to the cause of the reported problem.
+    pub fn primary(span: SourceSpan) -> Self {
+        Self {
+            span,
+            message: String::new(),
+            is_primary: true,
+        }
+    }
+
+    /// Returns a new label pointing to a location related to the reported problem.
+    pub fn secondary(span: SourceSpan) -> Self {
+        Self {
+            span,
+            message: String::new(),
+            is_primary: false,
+        }
+    }
+
+    /// Sets the message displayed next to the labeled location.
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.is_primary
+    }
+}
+
+// TO DIAGNOSTIC
+// ================================================================================================
+
+/// Implemented by the errors of each compilation stage which can be reported as a [Diagnostic].
+pub trait ToDiagnostic {
+    fn to_diagnostic(&self) -> Diagnostic;
+}
@@ -0,0 +1,310 @@
+use super::{Diagnostic, Label, Severity, SourceFile, SourceFiles, SourceSpan};
+use codespan_reporting::{
+    diagnostic as codespan,
+    files::SimpleFiles,
+    term::{self, termcolor::NoColor},
+};
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+// OUTPUT FORMAT
+// ================================================================================================
+
+/// The formats in which diagnostics can be rendered.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Human-readable text, with a snippet of the source underlining each labeled location.
+    #[default]
+    Plain,
+    /// A JSON array with one object per diagnostic, intended to be consumed by other tools.
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "Invalid diagnostics format `{}`, expected `plain` or `json`",
+                s
+            )),
+        }
+    }
+}
+
+// RENDERING
+// ================================================================================================
+
+/// Renders the provided diagnostics, which were reported for the specified source, in the
+/// requested format.
+///
+/// The file name is only used to identify the source in the rendered output.
+pub fn render(
+    format: OutputFormat,
+    file_name: &str,
+    source: &str,
+    diagnostics: &[Diagnostic],
+) -> String {
+    render_files(format, &SourceFiles::single(file_name, source), diagnostics)
+}
+
+/// Renders the provided diagnostics, which were reported for a program split across the specified
+/// source files, in the requested format. Each label is rendered against the file containing its
+/// span.
+pub fn render_files(
+    format: OutputFormat,
+    files: &SourceFiles,
+    diagnostics: &[Diagnostic],
+) -> String {
+    match format {
+        OutputFormat::Plain => render_plain(files, diagnostics),
+        OutputFormat::Json => render_json(files, diagnostics),
+    }
+}
+
+fn render_plain(files: &SourceFiles, diagnostics: &[Diagnostic]) -> String {
+    let mut codespan_files = SimpleFiles::new();
+    for file in files.files() {
+        codespan_files.add(file.name(), file.source());
+    }
+    let config = term::Config::default();
+    let mut writer = NoColor::new(Vec::new());
+
+    for diagnostic in diagnostics {
+        let diagnostic = to_codespan(diagnostic, files);
+        term::emit(&mut writer, &config, &codespan_files, &diagnostic)
+            .expect("failed to write diagnostic to an in-memory buffer");
+    }
+
+    String::from_utf8(writer.into_inner()).expect("rendered diagnostics are not valid utf8")
+}
+
+fn render_json(files: &SourceFiles, diagnostics: &[Diagnostic]) -> String {
+    let diagnostics: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let labels: Vec<Value> = diagnostic
+                .labels()
+                .iter()
+                .map(|label| {
+                    let span = label.span();
+                    let (_, file) = get_file(files, span.start());
+                    json!({
+                        "primary": label.is_primary(),
+                        "message": label.message(),
+                        "start": location_to_json(file, span.start()),
+                        "end": location_to_json(file, span.end()),
+                    })
+                })
+                .collect();
+
+            // diagnostics are attributed to the file of their primary label, or to the first file
+            // if they don't have one. Diagnostics reported before any file was loaded, which have
+            // no labels, aren't attributed to a file.
+            let offset = diagnostic
+                .labels()
+                .iter()
+                .find(|label| label.is_primary())
+                .map_or(0, |label| label.span().start());
+            let file = files
+                .files()
+                .first()
+                .map(|_| get_file(files, offset).1.name());
+
+            json!({
+                "severity": diagnostic.severity().to_string(),
+                "code": diagnostic.code(),
+                "message": diagnostic.message(),
+                "file": file,
+                "labels": labels,
+                "notes": diagnostic.notes(),
+            })
+        })
+        .collect();
+
+    Value::Array(diagnostics).to_string()
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Converts the diagnostic into the type rendered by `codespan_reporting`, where the id of each
+/// file is its index in the provided set of files.
+fn to_codespan(diagnostic: &Diagnostic, files: &SourceFiles) -> codespan::Diagnostic<usize> {
+    let severity = match diagnostic.severity() {
+        Severity::Error => codespan::Severity::Error,
+        Severity::Warning => codespan::Severity::Warning,
+    };
+    let labels = diagnostic
+        .labels()
+        .iter()
+        .map(|label| to_codespan_label(label, files))
+        .collect();
+
+    let mut result = codespan::Diagnostic::new(severity)
+        .with_message(diagnostic.message())
+        .with_labels(labels)
+        .with_notes(diagnostic.notes().to_vec());
+    if let Some(code) = diagnostic.code() {
+        result = result.with_code(code);
+    }
+    result
+}
+
+fn to_codespan_label(label: &Label, files: &SourceFiles) -> codespan::Label<usize> {
+    let (file_id, file) = get_file(files, label.span().start());
+    let start = clamp_offset(file, label.span().start());
+    let end = clamp_offset(file, label.span().end()).max(start);
+    let result = if label.is_primary() {
+        codespan::Label::primary(file_id, start..end)
+    } else {
+        codespan::Label::secondary(file_id, start..end)
+    };
+    result.with_message(label.message())
+}
+
+/// Returns the index of the file containing the specified offset along with the file itself, or
+/// the first file if the offset is not within any of the files.
+///
+/// # Panics
+/// Panics if the set of files is empty.
+fn get_file(files: &SourceFiles, offset: usize) -> (usize, &SourceFile) {
+    files
+        .files()
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, file)| file.offset() <= offset)
+        .or_else(|| files.files().first().map(|file| (0, file)))
+        .expect("diagnostics can't be rendered without a source file")
+}
+
+/// Returns the closest valid offset within the file which is not greater than the specified one,
+/// relative to the start of the file, so that spans recorded for a different version of the
+/// source can't cause rendering to fail.
+fn clamp_offset(file: &SourceFile, offset: usize) -> usize {
+    let source = file.source();
+    let mut offset = offset.saturating_sub(file.offset()).min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+fn location_to_json(file: &SourceFile, offset: usize) -> Value {
+    let offset = offset.saturating_sub(file.offset());
+    let location = SourceSpan::new(offset, offset).start_location(file.source());
+    json!({
+        "offset": offset,
+        "line": location.line(),
+        "column": location.column(),
+    })
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "trace_columns:
+    main: [clk]
+transition_constraints:
+    enf clk' = ctx + 1";
+
+    fn undeclared_ctx() -> Diagnostic {
+        let start = SOURCE.find("ctx").unwrap();
+        Diagnostic::error("Identifier ctx was not declared")
+            .with_code("E0100")
+            .with_label(
+                Label::primary(SourceSpan::new(start, start + 3)).with_message("undeclared"),
+            )
+            .with_note("declare it in the trace_columns section")
+    }
+
+    #[test]
+    fn plain_output() {
+        let rendered = render(OutputFormat::Plain, "test.air", SOURCE, &[undeclared_ctx()]);
+
+        let expected = "error[E0100]: Identifier ctx was not declared
+  ┌─ test.air:4:16
+  │
+4 │     enf clk' = ctx + 1
+  │                ^^^ undeclared
+  │
+  = declare it in the trace_columns section
+
+";
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn json_output() {
+        let rendered = render(OutputFormat::Json, "test.air", SOURCE, &[undeclared_ctx()]);
+        let rendered: Value = serde_json::from_str(&rendered).unwrap();
+
+        let expected = json!([{
+            "severity": "error",
+            "code": "E0100",
+            "message": "Identifier ctx was not declared",
+            "file": "test.air",
+            "labels": [{
+                "primary": true,
+                "message": "undeclared",
+                "start": { "offset": 70, "line": 4, "column": 16 },
+                "end": { "offset": 73, "line": 4, "column": 19 },
+            }],
+            "notes": ["declare it in the trace_columns section"],
+        }]);
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn multiple_files() {
+        let library = "mod bitwise\n\nev is_binary([a]):\n    enf a^2 = b";
+        let mut files = SourceFiles::single("test.air", SOURCE);
+        let offset = files.add("bitwise.air", library);
+        let start = offset + library.rfind('b').unwrap();
+        let diagnostics = [Diagnostic::error("Identifier b was not declared")
+            .with_label(Label::primary(SourceSpan::new(start, start + 1)))];
+
+        let rendered = render_files(OutputFormat::Plain, &files, &diagnostics);
+        assert!(rendered.contains("bitwise.air:4:15"));
+
+        let rendered = render_files(OutputFormat::Json, &files, &diagnostics);
+        let rendered: Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(rendered[0]["file"], "bitwise.air");
+        assert_eq!(
+            rendered[0]["labels"][0]["start"],
+            json!({ "offset": 46, "line": 4, "column": 15 })
+        );
+    }
+
+    #[test]
+    fn json_output_without_files() {
+        let diagnostics = [Diagnostic::error("No input file specified")];
+        let rendered = render_files(OutputFormat::Json, &SourceFiles::default(), &diagnostics);
+        let rendered: Value = serde_json::from_str(&rendered).unwrap();
+
+        let expected = json!([{
+            "severity": "error",
+            "code": null,
+            "message": "No input file specified",
+            "file": null,
+            "labels": [],
+            "notes": [],
+        }]);
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn output_format_from_str() {
+        assert_eq!("plain".parse(), Ok(OutputFormat::Plain));
+        assert_eq!("json".parse(), Ok(OutputFormat::Json));
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+}
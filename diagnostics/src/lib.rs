@@ -0,0 +1,11 @@
+mod span;
+pub use span::{SourceLocation, SourceSpan};
+
+mod source;
+pub use source::{SourceFile, SourceFiles};
+
+mod diagnostic;
+pub use diagnostic::{Diagnostic, Label, Severity, ToDiagnostic};
+
+mod emitter;
+pub use emitter::{render, render_files, OutputFormat};
@@ -0,0 +1,73 @@
+// SOURCE FILES
+// ================================================================================================
+
+/// The source files of an AirScript program which is split across several files.
+///
+/// Every file is assigned a distinct range of byte offsets, so that a [SourceSpan](super::SourceSpan)
+/// recorded by the parser identifies both the file in which it was found and the location within
+/// that file. The first file added starts at offset zero, so the spans of a program made of a
+/// single file are the same as the byte offsets within that file.
+#[derive(Debug, Default, Clone)]
+pub struct SourceFiles {
+    files: Vec<SourceFile>,
+}
+
+impl SourceFiles {
+    // --- CONSTRUCTOR ----------------------------------------------------------------------------
+
+    /// Returns a set of source files containing only the specified file.
+    pub fn single(name: &str, source: &str) -> Self {
+        let mut files = Self::default();
+        files.add(name, source);
+        files
+    }
+
+    // --- MUTATORS -------------------------------------------------------------------------------
+
+    /// Adds a file to the set and returns the offset at which the spans within the file start.
+    pub fn add(&mut self, name: &str, source: &str) -> usize {
+        // leave a gap of one byte between files, so that a span at the end of a file can't be
+        // mistaken for a span at the start of the following file.
+        let offset = self
+            .files
+            .last()
+            .map_or(0, |file| file.offset + file.source.len() + 1);
+        self.files.push(SourceFile {
+            name: name.to_string(),
+            source: source.to_string(),
+            offset,
+        });
+        offset
+    }
+
+    // --- ACCESSORS ------------------------------------------------------------------------------
+
+    /// Returns all of the files in the order in which they were added.
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+}
+
+/// A single source file, along with the offset at which its spans start.
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    name: String,
+    source: String,
+    offset: usize,
+}
+
+impl SourceFile {
+    /// Returns the name which identifies the file in rendered diagnostics.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the offset at which the spans within the file start.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
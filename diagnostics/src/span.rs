@@ -0,0 +1,90 @@
+use std::fmt::Display;
+
+// SOURCE SPAN
+// ================================================================================================
+
+/// A byte range within the AirScript source string from which an AST node was parsed. The range
+/// starts at the first byte of the node and ends at the byte immediately following it.
+///
+/// Spans are recorded by the parser for every AST node which can be referenced by an error. They
+/// are not considered when comparing AST nodes for equality.
+#[derive(Debug, Default, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash)]
+pub struct SourceSpan {
+    start: usize,
+    end: usize,
+}
+
+impl SourceSpan {
+    pub fn new(start: usize, end: usize) -> Self {
+        debug_assert!(
+            start <= end,
+            "span start {} is after span end {}",
+            start,
+            end
+        );
+        Self { start, end }
+    }
+
+    /// Returns the byte offset of the start of the span.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the byte offset immediately following the end of the span.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the line and column of the start of the span within the provided source.
+    pub fn start_location(&self, source: &str) -> SourceLocation {
+        SourceLocation::from_offset(source, self.start)
+    }
+
+    /// Returns the line and column of the end of the span within the provided source.
+    pub fn end_location(&self, source: &str) -> SourceLocation {
+        SourceLocation::from_offset(source, self.end)
+    }
+}
+
+// SOURCE LOCATION
+// ================================================================================================
+
+/// A human-readable position within an AirScript source string. Both the line and the column are
+/// 1-based, and columns are counted in characters rather than bytes.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct SourceLocation {
+    line: usize,
+    column: usize,
+}
+
+impl SourceLocation {
+    /// Returns the location of the specified byte offset within the provided source. Offsets past
+    /// the end of the source are clamped to the end of the source.
+    pub fn from_offset(source: &str, offset: usize) -> Self {
+        let mut offset = offset.min(source.len());
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let preceding = &source[..offset];
+        let line_start = preceding.rfind('\n').map_or(0, |idx| idx + 1);
+
+        Self {
+            line: preceding.matches('\n').count() + 1,
+            column: preceding[line_start..].chars().count() + 1,
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
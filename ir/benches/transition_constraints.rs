@@ -0,0 +1,53 @@
+use air_ir::AirIR;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use parser::parse;
+
+/// The number of main trace columns referenced by the synthetic constraints.
+const NUM_COLUMNS: usize = 64;
+
+/// Returns the source of an AIR with the specified number of distinct transition constraints, each
+/// of which is made of several nodes and shares some of its subexpressions with other constraints.
+fn build_source(num_constraints: usize) -> String {
+    let mut source = format!(
+        "def BenchAir
+
+trace_columns:
+    main: [c[{NUM_COLUMNS}]]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf c[0].first = 0
+
+transition_constraints:
+"
+    );
+    for i in 0..num_constraints {
+        let a = i % NUM_COLUMNS;
+        let b = (i + 1) % NUM_COLUMNS;
+        let c = (i + 2) % NUM_COLUMNS;
+        source.push_str(&format!(
+            "    enf c[{a}]' = c[{b}] * c[{c}] + c[{a}] * {i} + {}\n",
+            i + 1
+        ));
+    }
+    source
+}
+
+fn transition_constraints(c: &mut Criterion) {
+    let mut group = c.benchmark_group("transition_constraints");
+    for num_constraints in [250, 500, 1000, 2000] {
+        let ast = parse(&build_source(num_constraints)).expect("Parsing failed");
+        group.throughput(Throughput::Elements(num_constraints as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(num_constraints),
+            &ast,
+            |bench, ast| bench.iter(|| AirIR::from_source(ast).expect("IR generation failed")),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, transition_constraints);
+criterion_main!(benches);
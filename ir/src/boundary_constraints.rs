@@ -1,27 +1,26 @@
-use super::{BTreeMap, BoundaryExpr, Identifier, IdentifierType, SemanticError, SymbolTable};
+use super::{BTreeMap, Field, IdentifierType, SemanticError, SourceSpan, SymbolTable};
 use parser::ast;
+use std::fmt::Display;
 
 // BOUNDARY CONSTRAINTS
 // ================================================================================================
 
-/// A struct containing all of the boundary constraints to be applied at each of the 2 allowed
-/// boundaries (first row and last row). For ease of code generation and evaluation, constraints are
-/// sorted into maps by the boundary. This also simplifies ensuring that there are no conflicting
-/// constraints sharing a boundary and column index.
+/// The minimum stride of the rows of a periodic boundary constraint.
+pub const MIN_ASSERTION_STRIDE: usize = 2;
+
+/// A struct containing all of the boundary constraints to be applied to the main and the aux
+/// traces. For ease of code generation, the constraints of each trace are sorted into a map keyed
+/// by the rows and the column to which they are applied, so constraints at a single row come
+/// first, followed by the constraints at the last row and the periodic constraints. This also
+/// simplifies ensuring that there are no conflicting constraints sharing a row and column index.
 #[derive(Default, Debug)]
 pub(crate) struct BoundaryConstraints {
-    /// The boundary constraints to be applied at the first row of the main trace, with the trace
-    /// column index as the key, and the expression as the value.
-    main_first: BTreeMap<usize, BoundaryExpr>,
-    /// The boundary constraints to be applied at the last row of the main trace, with the trace
-    /// column index as the key, and the expression as the value.
-    main_last: BTreeMap<usize, BoundaryExpr>,
-    /// The boundary constraints to be applied at the first row of the aux trace, with the trace
-    /// column index as the key, and the expression as the value.
-    aux_first: BTreeMap<usize, BoundaryExpr>,
-    /// The boundary constraints to be applied at the last row of the aux trace, with the trace
-    /// column index as the key, and the expression as the value.
-    aux_last: BTreeMap<usize, BoundaryExpr>,
+    /// The boundary constraints to be applied to the main trace, keyed by their rows and the index
+    /// of their trace column.
+    main: BTreeMap<(AssertionRows, usize), AssertionValue>,
+    /// The boundary constraints to be applied to the aux trace, keyed by their rows and the index
+    /// of their trace column.
+    aux: BTreeMap<(AssertionRows, usize), AssertionValue>,
 }
 
 impl BoundaryConstraints {
@@ -29,105 +28,550 @@ impl BoundaryConstraints {
 
     /// Returns the total number of boundary constraints for the main trace.
     pub fn main_len(&self) -> usize {
-        self.main_first.len() + self.main_last.len()
-    }
-
-    /// Returns all of the boundary constraints for the first row of the main trace.
-    pub fn main_first(&self) -> Vec<(usize, &BoundaryExpr)> {
-        self.main_first.iter().map(|(k, v)| (*k, v)).collect()
+        self.main.len()
     }
 
-    /// Returns all of the boundary constraints for the final row of the main trace.
-    pub fn main_last(&self) -> Vec<(usize, &BoundaryExpr)> {
-        self.main_last.iter().map(|(k, v)| (*k, v)).collect()
+    /// Returns all of the boundary constraints for the main trace, as the index of the trace
+    /// column, the rows and the value of each constraint.
+    pub fn main(&self) -> Vec<(usize, AssertionRows, &AssertionValue)> {
+        self.main
+            .iter()
+            .map(|((rows, col_idx), value)| (*col_idx, *rows, value))
+            .collect()
     }
 
     /// Returns the total number of boundary constraints for the aux trace.
     pub fn aux_len(&self) -> usize {
-        self.aux_first.len() + self.aux_last.len()
+        self.aux.len()
     }
 
-    /// Returns all of the boundary constraints for the first row of the aux trace.
-    pub fn aux_first(&self) -> Vec<(usize, &BoundaryExpr)> {
-        self.aux_first.iter().map(|(k, v)| (*k, v)).collect()
+    /// Returns all of the boundary constraints for the aux trace, as the index of the trace
+    /// column, the rows and the value of each constraint.
+    pub fn aux(&self) -> Vec<(usize, AssertionRows, &AssertionValue)> {
+        self.aux
+            .iter()
+            .map(|((rows, col_idx), value)| (*col_idx, *rows, value))
+            .collect()
     }
 
-    /// Returns all of the boundary constraints for the final row of the aux trace.
-    pub fn aux_last(&self) -> Vec<(usize, &BoundaryExpr)> {
-        self.aux_last.iter().map(|(k, v)| (*k, v)).collect()
+    /// Returns true if any boundary constraints are applied to the last row of the main or the aux
+    /// trace.
+    pub fn has_last(&self) -> bool {
+        self.main
+            .keys()
+            .chain(self.aux.keys())
+            .any(|(rows, _)| *rows == AssertionRows::Last)
+    }
+
+    /// Returns the number of random values of the specified auxiliary trace segment required by
+    /// the boundary constraints, i.e. one more than the largest index of a random value of the
+    /// segment they reference, or zero if none are referenced.
+    pub fn num_random_values(&self, segment: usize) -> usize {
+        self.main
+            .values()
+            .chain(self.aux.values())
+            .map(|value| match value {
+                AssertionValue::Single(expr) => num_random_values(expr, segment),
+                AssertionValue::Sequence(..) => 0,
+            })
+            .max()
+            .unwrap_or(0)
     }
 
     // --- MUTATORS -------------------------------------------------------------------------------
 
-    /// Add a boundary constraint from the AST to the list of constraints for its specified
-    /// boundary.
+    /// Add a boundary constraint from the AST to the constraints of the trace of its column.
+    ///
+    /// A periodic constraint whose value is a public input is a sequence constraint, which asserts
+    /// the elements of the public input at its successive rows.
     ///
     /// # Errors
     /// Returns an error if:
     /// - The identifier specified for the boundary constraint column has not been declared or has
     ///   been declared with the wrong type.
-    /// - The constraint expression is contains invalid public input references.
-    /// - A boundary constraint has already been declared for the specified column and boundary.
+    /// - The constraint expression contains invalid references to public inputs or constants, or
+    ///   constants which are not elements of the field of the AIR.
+    /// - The constraint is applied to a column of the main trace and its value references random
+    ///   values, which are only available to the constraints of the auxiliary trace.
+    /// - The stride of a periodic constraint is not a power of two of at least 2, or its first row
+    ///   is not smaller than its stride.
+    /// - The length of the public input of a sequence constraint is not a power of two.
+    /// - A boundary constraint has already been declared for any of the rows of the constraint in
+    ///   the specified column.
     pub(super) fn insert(
         &mut self,
         symbol_table: &SymbolTable,
         constraint: &ast::BoundaryConstraint,
     ) -> Result<(), SemanticError> {
-        // validate the expression
-        let expr = constraint.value();
-        validate_expression(symbol_table, &expr)?;
-
-        // add the constraint to the specified boundary for the specified trace
-        let col_type = symbol_table.get_type(constraint.column())?;
-        let result = match col_type {
-            IdentifierType::MainTraceColumn(col_idx) => match constraint.boundary() {
-                ast::Boundary::First => self.main_first.insert(col_idx, expr),
-                ast::Boundary::Last => self.main_last.insert(col_idx, expr),
-            },
-            IdentifierType::AuxTraceColumn(col_idx) => match constraint.boundary() {
-                ast::Boundary::First => self.aux_first.insert(col_idx, expr),
-                ast::Boundary::Last => self.aux_last.insert(col_idx, expr),
-            },
-            _ => {
-                return Err(SemanticError::InvalidUsage(format!(
+        let rows = build_rows(constraint)?;
+        symbol_table
+            .field()
+            .validate_boundary_expr(&constraint.value(), constraint.span())?;
+
+        let col_type = match constraint.column() {
+            ast::BoundaryColumn::Named(ident) => symbol_table.get_type(ident)?.clone(),
+            ast::BoundaryColumn::Indexed(access) => {
+                symbol_table.get_trace_column_group_element(access)?
+            }
+        };
+        match col_type {
+            IdentifierType::MainTraceColumn(col_idx) | IdentifierType::AuxTraceColumn(col_idx) => {
+                // validate the value and resolve the identifiers it references
+                let value = build_value(symbol_table, constraint, rows)?;
+                let column = constraint.column().to_string();
+                if let (IdentifierType::MainTraceColumn(_), AssertionValue::Single(expr)) =
+                    (&col_type, &value)
+                {
+                    if has_random_values(expr) {
+                        return Err(SemanticError::InvalidUsage(
+                            format!(
+                                "Random values cannot be used in the boundary constraint of main trace column {}, since they are only available to the constraints of the auxiliary trace",
+                                column
+                            ),
+                            constraint.span(),
+                        ));
+                    }
+                }
+                self.insert_value(constraint, &col_type, &column, col_idx, rows, value)
+            }
+            IdentifierType::MainTraceColumnGroup(ref range)
+            | IdentifierType::AuxTraceColumnGroup(ref range) => {
+                // the elements of the vector value are applied to the columns of the group in order.
+                let values = build_vector_values(symbol_table, constraint, range.len())?;
+                let elem_type = match col_type {
+                    IdentifierType::MainTraceColumnGroup(_) => IdentifierType::MainTraceColumn,
+                    _ => IdentifierType::AuxTraceColumn,
+                };
+                for (idx, (col_idx, value)) in range.clone().zip(values).enumerate() {
+                    let column = format!("{}[{}]", constraint.column(), idx);
+                    self.insert_value(
+                        constraint,
+                        &elem_type(col_idx),
+                        &column,
+                        col_idx,
+                        rows,
+                        value,
+                    )?;
+                }
+                Ok(())
+            }
+            _ => Err(SemanticError::InvalidUsage(
+                format!(
                     "Identifier {} was declared as a {}, not as a trace column",
                     constraint.column(),
                     col_type
-                )));
-            }
+                ),
+                constraint.column().span(),
+            )),
+        }
+    }
+
+    /// Adds the value of a boundary constraint at the specified rows of the trace column with the
+    /// specified type, name and index.
+    ///
+    /// # Errors
+    /// Returns an error if a boundary constraint has already been declared for any of the rows in
+    /// the specified column.
+    fn insert_value(
+        &mut self,
+        constraint: &ast::BoundaryConstraint,
+        col_type: &IdentifierType,
+        column: &str,
+        col_idx: usize,
+        rows: AssertionRows,
+        value: AssertionValue,
+    ) -> Result<(), SemanticError> {
+        let constraints = match col_type {
+            IdentifierType::AuxTraceColumn(_) => &mut self.aux,
+            _ => &mut self.main,
         };
 
-        // raise an error if multiple constraints were applied to the same boundary
-        if result.is_some() {
-            return Err(SemanticError::TooManyConstraints(format!(
-                "A boundary constraint was already defined for {} '{}' at the {}",
-                col_type,
-                constraint.column(),
-                constraint.boundary()
-            )));
+        // raise an error if multiple constraints were applied to the same cell of the trace
+        if let Some((other_rows, _)) = constraints
+            .keys()
+            .find(|(other_rows, other_idx)| *other_idx == col_idx && rows.overlaps(other_rows))
+        {
+            return Err(SemanticError::TooManyConstraints(
+                format!(
+                    "A boundary constraint was already defined for {} '{}' at {}",
+                    col_type, column, other_rows
+                ),
+                constraint.span(),
+            ));
         }
+        constraints.insert((rows, col_idx), value);
 
         Ok(())
     }
 }
 
-/// Recursively validates the BoundaryExpression.
+/// Returns the rows of the trace to which the provided boundary constraint is applied.
+///
+/// # Errors
+/// Returns an error if the constraint is periodic and its stride is not a power of two of at
+/// least 2, or its first row is not smaller than its stride.
+fn build_rows(constraint: &ast::BoundaryConstraint) -> Result<AssertionRows, SemanticError> {
+    match constraint.boundary() {
+        ast::Boundary::First => Ok(AssertionRows::Single(0)),
+        ast::Boundary::Last => Ok(AssertionRows::Last),
+        ast::Boundary::Row(row) => Ok(AssertionRows::Single(row)),
+        ast::Boundary::Periodic { first_row, stride } => {
+            if !stride.is_power_of_two() || stride < MIN_ASSERTION_STRIDE {
+                return Err(SemanticError::InvalidAssertion(
+                    format!(
+                        "The stride of a periodic boundary constraint must be a power of two of at least {}, but was {}",
+                        MIN_ASSERTION_STRIDE, stride
+                    ),
+                    constraint.span(),
+                ));
+            }
+            if first_row >= stride {
+                return Err(SemanticError::InvalidAssertion(
+                    format!(
+                        "The first row of a periodic boundary constraint must be smaller than its stride ({}), but was {}",
+                        stride, first_row
+                    ),
+                    constraint.span(),
+                ));
+            }
+            Ok(AssertionRows::Periodic { first_row, stride })
+        }
+    }
+}
+
+/// Builds the value of the provided boundary constraint, which is applied to the specified rows.
 ///
 /// # Errors
-/// Returns an error if the expression includes a reference to a public input that hasn't been
-/// declared or to an invalid index in an existing public input.
-fn validate_expression(
+/// Returns an error if the value is invalid, or if it is a public input whose length is not a
+/// power of two.
+fn build_value(
     symbol_table: &SymbolTable,
-    expr: &ast::BoundaryExpr,
+    constraint: &ast::BoundaryConstraint,
+    rows: AssertionRows,
+) -> Result<AssertionValue, SemanticError> {
+    let value = constraint.value();
+    if let (AssertionRows::Periodic { .. }, ast::BoundaryExpr::Var(ident)) = (rows, &value) {
+        if let IdentifierType::PublicInput(size) = symbol_table.get_type(ident)? {
+            if !size.is_power_of_two() {
+                return Err(SemanticError::InvalidAssertion(
+                    format!(
+                        "The length of public input {} asserted by a sequence boundary constraint must be a power of two, but was {}",
+                        ident, size
+                    ),
+                    ident.span(),
+                ));
+            }
+            return Ok(AssertionValue::Sequence(ident.name().to_string(), *size));
+        }
+    }
+
+    Ok(AssertionValue::Single(build_expression(
+        symbol_table,
+        &value,
+        constraint.span(),
+    )?))
+}
+
+/// Builds the values of a boundary constraint applied to a group of trace columns with the
+/// specified length, which must be a public input or a vector constant with the same length as the
+/// group. The values are the elements of the vector, in order.
+///
+/// # Errors
+/// Returns an error if the value of the constraint is not a public input or a vector constant, or
+/// if its length is different from the length of the group.
+fn build_vector_values(
+    symbol_table: &SymbolTable,
+    constraint: &ast::BoundaryConstraint,
+    len: usize,
+) -> Result<Vec<AssertionValue>, SemanticError> {
+    let invalid_value = || {
+        SemanticError::InvalidUsage(
+            format!(
+                "The value of a boundary constraint on trace column group {} must be a public input or a vector constant",
+                constraint.column()
+            ),
+            constraint.span(),
+        )
+    };
+    let ast::BoundaryExpr::Var(ident) = constraint.value() else {
+        return Err(invalid_value());
+    };
+
+    let values: Vec<BoundaryExpr> = match symbol_table.get_type(&ident)? {
+        IdentifierType::PublicInput(size) => (0..*size)
+            .map(|idx| BoundaryExpr::PubInput(ident.name().to_string(), idx))
+            .collect(),
+        IdentifierType::Constant(ast::ConstantType::Vector(values)) => values
+            .iter()
+            .map(|value| BoundaryExpr::Const(*value))
+            .collect(),
+        _ => return Err(invalid_value()),
+    };
+    if values.len() != len {
+        return Err(SemanticError::InvalidAssertion(
+            format!(
+                "Vector {} of length {} cannot be bound to trace column group {} of length {}",
+                ident,
+                values.len(),
+                constraint.column(),
+                len
+            ),
+            constraint.span(),
+        ));
+    }
+
+    Ok(values.into_iter().map(AssertionValue::Single).collect())
+}
+
+/// Builds the value of the provided parsed variable and declares the variable in the symbol table,
+/// so that it can be referenced by the following statements of its section.
+///
+/// # Errors
+/// Returns an error if the value of the variable is invalid or if its name was already declared.
+pub(super) fn insert_variable(
+    symbol_table: &mut SymbolTable,
+    variable: &ast::BoundaryVariable,
 ) -> Result<(), SemanticError> {
+    symbol_table
+        .field()
+        .validate_boundary_expr(variable.value(), variable.span())?;
+    let value = build_expression(symbol_table, variable.value(), variable.span())?;
+    symbol_table.insert_boundary_variable(variable.ident(), value)
+}
+
+/// Recursively builds the IR representation of the boundary constraint expression, resolving the
+/// references to public inputs and random values and the values of constants. A division is built
+/// as the product of the numerator and the inverse of the denominator.
+///
+/// # Errors
+/// Returns an error located at the provided span, which is the span of the statement of the
+/// expression, if the expression includes:
+/// - A reference to a public input, a random value or a constant that hasn't been declared, or to
+///   an invalid index in an existing public input, set of declared random values or constant.
+/// - A division by a constant equal to zero or the inverse of a constant equal to zero.
+fn build_expression(
+    symbol_table: &SymbolTable,
+    expr: &ast::BoundaryExpr,
+    span: SourceSpan,
+) -> Result<BoundaryExpr, SemanticError> {
+    match expr {
+        ast::BoundaryExpr::Const(value) => Ok(BoundaryExpr::Const(*value)),
+        ast::BoundaryExpr::Var(ident) => match symbol_table.get_type(ident)? {
+            // the values of variables are inlined into the expression.
+            IdentifierType::BoundaryVariable(value) => Ok(value.clone()),
+            IdentifierType::RandomValue(segment, index) => Ok(BoundaryExpr::Rand(*segment, *index)),
+            _ => Ok(BoundaryExpr::Const(
+                symbol_table.get_scalar_constant(ident)?,
+            )),
+        },
+        ast::BoundaryExpr::VectorAccess(access) => match symbol_table.get_type(access.ident())? {
+            IdentifierType::PublicInput(_) => {
+                symbol_table.validate_public_input(access.ident(), access.idx())?;
+                Ok(BoundaryExpr::PubInput(
+                    access.name().to_string(),
+                    access.idx(),
+                ))
+            }
+            _ => Ok(BoundaryExpr::Const(
+                symbol_table.get_vector_constant_element(access)?,
+            )),
+        },
+        ast::BoundaryExpr::MatrixAccess(access) => Ok(BoundaryExpr::Const(
+            symbol_table.get_matrix_constant_element(access)?,
+        )),
+        ast::BoundaryExpr::Rand(access) => {
+            let (segment, index) = symbol_table.get_random_value(access)?;
+            Ok(BoundaryExpr::Rand(segment, index))
+        }
+        ast::BoundaryExpr::Add(lhs, rhs) => Ok(BoundaryExpr::Add(
+            Box::new(build_expression(symbol_table, lhs, span)?),
+            Box::new(build_expression(symbol_table, rhs, span)?),
+        )),
+        ast::BoundaryExpr::Sub(lhs, rhs) => Ok(BoundaryExpr::Sub(
+            Box::new(build_expression(symbol_table, lhs, span)?),
+            Box::new(build_expression(symbol_table, rhs, span)?),
+        )),
+        ast::BoundaryExpr::Mul(lhs, rhs) => Ok(BoundaryExpr::Mul(
+            Box::new(build_expression(symbol_table, lhs, span)?),
+            Box::new(build_expression(symbol_table, rhs, span)?),
+        )),
+        ast::BoundaryExpr::Exp(lhs, exp) => Ok(BoundaryExpr::Exp(
+            Box::new(build_expression(symbol_table, lhs, span)?),
+            *exp,
+        )),
+        ast::BoundaryExpr::Div(lhs, rhs) => {
+            let rhs = build_expression(symbol_table, rhs, span)?;
+            if evaluate_constant(symbol_table.field(), &rhs) == Some(0) {
+                return Err(SemanticError::DivisionByZero(
+                    "Cannot divide by a denominator which is a constant equal to zero".to_string(),
+                    span,
+                ));
+            }
+            Ok(BoundaryExpr::Mul(
+                Box::new(build_expression(symbol_table, lhs, span)?),
+                Box::new(BoundaryExpr::Inv(Box::new(rhs))),
+            ))
+        }
+        ast::BoundaryExpr::Inv(expr) => {
+            let expr = build_expression(symbol_table, expr, span)?;
+            if evaluate_constant(symbol_table.field(), &expr) == Some(0) {
+                return Err(SemanticError::DivisionByZero(
+                    "Cannot compute the inverse of a constant equal to zero".to_string(),
+                    span,
+                ));
+            }
+            Ok(BoundaryExpr::Inv(Box::new(expr)))
+        }
+        ast::BoundaryExpr::Neg(expr) => Ok(BoundaryExpr::Neg(Box::new(build_expression(
+            symbol_table,
+            expr,
+            span,
+        )?))),
+    }
+}
+
+/// Returns the value of the provided expression in the specified field if it only depends on
+/// constants, or None if it references a public input or a random value.
+fn evaluate_constant(field: Field, expr: &BoundaryExpr) -> Option<u128> {
+    match expr {
+        BoundaryExpr::Const(value) => Some(*value),
+        BoundaryExpr::PubInput(..) | BoundaryExpr::Rand(..) => None,
+        BoundaryExpr::Add(lhs, rhs) => Some(field.add(
+            evaluate_constant(field, lhs)?,
+            evaluate_constant(field, rhs)?,
+        )),
+        BoundaryExpr::Sub(lhs, rhs) => Some(field.add(
+            evaluate_constant(field, lhs)?,
+            field.neg(evaluate_constant(field, rhs)?),
+        )),
+        BoundaryExpr::Mul(lhs, rhs) => Some(field.mul(
+            evaluate_constant(field, lhs)?,
+            evaluate_constant(field, rhs)?,
+        )),
+        BoundaryExpr::Exp(base, power) => {
+            Some(field.exp(evaluate_constant(field, base)?, *power as usize))
+        }
+        BoundaryExpr::Inv(value) => Some(field.inv(evaluate_constant(field, value)?)),
+        BoundaryExpr::Neg(value) => Some(field.neg(evaluate_constant(field, value)?)),
+    }
+}
+
+/// Recursively computes the number of random values of the specified auxiliary trace segment
+/// required by the expression.
+fn num_random_values(expr: &BoundaryExpr, segment: usize) -> usize {
     match expr {
-        BoundaryExpr::PubInput(Identifier(name), index) => {
-            symbol_table.validate_public_input(name, *index)
+        BoundaryExpr::Rand(s, index) if *s == segment => index + 1,
+        BoundaryExpr::Add(lhs, rhs) | BoundaryExpr::Sub(lhs, rhs) | BoundaryExpr::Mul(lhs, rhs) => {
+            num_random_values(lhs, segment).max(num_random_values(rhs, segment))
         }
-        BoundaryExpr::Add(lhs, rhs) | BoundaryExpr::Sub(lhs, rhs) => {
-            validate_expression(symbol_table, lhs)?;
-            validate_expression(symbol_table, rhs)
+        BoundaryExpr::Exp(lhs, _) | BoundaryExpr::Inv(lhs) | BoundaryExpr::Neg(lhs) => {
+            num_random_values(lhs, segment)
         }
-        _ => Ok(()),
+        BoundaryExpr::Rand(..) | BoundaryExpr::Const(_) | BoundaryExpr::PubInput(..) => 0,
     }
 }
+
+/// Returns true if the expression references any random values.
+fn has_random_values(expr: &BoundaryExpr) -> bool {
+    match expr {
+        BoundaryExpr::Rand(..) => true,
+        BoundaryExpr::Add(lhs, rhs) | BoundaryExpr::Sub(lhs, rhs) | BoundaryExpr::Mul(lhs, rhs) => {
+            has_random_values(lhs) || has_random_values(rhs)
+        }
+        BoundaryExpr::Exp(lhs, _) | BoundaryExpr::Inv(lhs) | BoundaryExpr::Neg(lhs) => {
+            has_random_values(lhs)
+        }
+        BoundaryExpr::Const(_) | BoundaryExpr::PubInput(..) => false,
+    }
+}
+
+// ASSERTIONS
+// ================================================================================================
+
+/// The rows of the trace to which a boundary constraint is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AssertionRows {
+    /// The row at the specified index. The first row of the trace is at index 0.
+    Single(usize),
+    /// The last row reached by the transition constraints, whose index depends on the length of
+    /// the trace.
+    Last,
+    /// Every `stride`-th row, starting from the row at index `first_row`.
+    Periodic { first_row: usize, stride: usize },
+}
+
+impl AssertionRows {
+    /// Returns true if any row belongs to both sets of rows for every trace length. The last row
+    /// depends on the length of the trace, so it is only considered to overlap with itself.
+    fn overlaps(&self, other: &AssertionRows) -> bool {
+        match (*self, *other) {
+            (Self::Last, Self::Last) => true,
+            (Self::Last, _) | (_, Self::Last) => false,
+            (Self::Single(row), Self::Single(other_row)) => row == other_row,
+            (Self::Single(row), Self::Periodic { first_row, stride })
+            | (Self::Periodic { first_row, stride }, Self::Single(row)) => {
+                row % stride == first_row
+            }
+            (
+                Self::Periodic { first_row, stride },
+                Self::Periodic {
+                    first_row: other_first_row,
+                    stride: other_stride,
+                },
+            ) => {
+                // the strides are powers of two, so the rows of the longer stride are a subset of
+                // the rows of the shorter stride which start from the same row.
+                let stride = stride.min(other_stride);
+                first_row % stride == other_first_row % stride
+            }
+        }
+    }
+}
+
+impl Display for AssertionRows {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Single(0) => write!(f, "the first row"),
+            Self::Single(row) => write!(f, "row {}", row),
+            Self::Last => write!(f, "the last row"),
+            Self::Periodic { first_row, stride } => {
+                write!(f, "every {} rows from row {}", stride, first_row)
+            }
+        }
+    }
+}
+
+/// The value asserted by a boundary constraint.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AssertionValue {
+    /// A single value, asserted at each of the rows of the constraint.
+    Single(BoundaryExpr),
+    /// The elements of a public input, identified by its name and length, which are asserted at
+    /// the successive rows of a periodic constraint.
+    Sequence(String, usize),
+}
+
+// BOUNDARY EXPRESSIONS
+// ================================================================================================
+
+/// Arithmetic expressions for evaluation of boundary constraints, in which the values of constants
+/// have been resolved.
+#[derive(Debug, PartialEq, Clone)]
+pub enum BoundaryExpr {
+    Const(u128),
+    /// Reference to a public input element, identified by the name of a public input array and the
+    /// index of the cell.
+    PubInput(String, usize),
+    /// Represents a random value provided by the verifier, identified by the index of the
+    /// auxiliary trace segment it was drawn for and its index in the random values of that
+    /// segment.
+    Rand(usize, usize),
+    Add(Box<BoundaryExpr>, Box<BoundaryExpr>),
+    Sub(Box<BoundaryExpr>, Box<BoundaryExpr>),
+    Mul(Box<BoundaryExpr>, Box<BoundaryExpr>),
+    Exp(Box<BoundaryExpr>, u64),
+    /// Multiplicative inverse of an expression. A division is represented as the product of the
+    /// numerator and the inverse of the denominator.
+    Inv(Box<BoundaryExpr>),
+    Neg(Box<BoundaryExpr>),
+}
@@ -1,10 +1,160 @@
+use super::{boundary_constraints::MIN_ASSERTION_STRIDE, Field, MIN_CYCLE_LENGTH};
+use diagnostics::{Diagnostic, Label, ToDiagnostic};
+use parser::ast::SourceSpan;
+use std::fmt::Display;
+
+/// Errors found while validating the AST and building the IR. Errors caused by a specific part of
+/// the source carry the [SourceSpan] of the offending token or declaration.
 #[derive(Debug)]
 pub enum SemanticError {
-    InvalidIdentifier(String),
-    DuplicateIdentifier(String),
-    InvalidUsage(String),
-    IndexOutOfRange(String),
-    TooManyConstraints(String),
-    InvalidPeriodicColumn(String),
+    InvalidIdentifier(String, SourceSpan),
+    DuplicateIdentifier(String, SourceSpan),
+    InvalidUsage(String, SourceSpan),
+    IndexOutOfRange(String, SourceSpan),
+    TooManyConstraints(String, SourceSpan),
+    InvalidPeriodicColumn(String, SourceSpan),
+    InvalidTransitionExemptions(String, SourceSpan),
+    InvalidConstant(String, SourceSpan),
+    InvalidListComprehension(String, SourceSpan),
+    InvalidEvaluatorCall(String, SourceSpan),
+    InvalidImport(String, SourceSpan),
+    InvalidAssertion(String, SourceSpan),
+    InvalidField(String, SourceSpan),
+    DivisionByZero(String, SourceSpan),
+    InvalidConstraint(String, SourceSpan),
     MissingDeclaration(String),
 }
+
+impl SemanticError {
+    /// Returns the description of the error.
+    pub fn message(&self) -> &str {
+        match self {
+            Self::InvalidIdentifier(msg, _)
+            | Self::DuplicateIdentifier(msg, _)
+            | Self::InvalidUsage(msg, _)
+            | Self::IndexOutOfRange(msg, _)
+            | Self::TooManyConstraints(msg, _)
+            | Self::InvalidPeriodicColumn(msg, _)
+            | Self::InvalidTransitionExemptions(msg, _)
+            | Self::InvalidConstant(msg, _)
+            | Self::InvalidListComprehension(msg, _)
+            | Self::InvalidEvaluatorCall(msg, _)
+            | Self::InvalidImport(msg, _)
+            | Self::InvalidAssertion(msg, _)
+            | Self::InvalidField(msg, _)
+            | Self::DivisionByZero(msg, _)
+            | Self::InvalidConstraint(msg, _)
+            | Self::MissingDeclaration(msg) => msg,
+        }
+    }
+
+    /// Returns the location in the source which caused the error, or None if the error does not
+    /// refer to a specific location (e.g. a missing section).
+    pub fn span(&self) -> Option<SourceSpan> {
+        match self {
+            Self::InvalidIdentifier(_, span)
+            | Self::DuplicateIdentifier(_, span)
+            | Self::InvalidUsage(_, span)
+            | Self::IndexOutOfRange(_, span)
+            | Self::TooManyConstraints(_, span)
+            | Self::InvalidPeriodicColumn(_, span)
+            | Self::InvalidTransitionExemptions(_, span)
+            | Self::InvalidConstant(_, span)
+            | Self::InvalidListComprehension(_, span)
+            | Self::InvalidEvaluatorCall(_, span)
+            | Self::InvalidImport(_, span)
+            | Self::InvalidAssertion(_, span)
+            | Self::InvalidField(_, span)
+            | Self::DivisionByZero(_, span)
+            | Self::InvalidConstraint(_, span) => Some(*span),
+            Self::MissingDeclaration(_) => None,
+        }
+    }
+}
+
+impl Display for SemanticError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl ToDiagnostic for SemanticError {
+    fn to_diagnostic(&self) -> Diagnostic {
+        let (code, label) = match self {
+            Self::InvalidIdentifier(..) => ("E0100", "not declared"),
+            Self::DuplicateIdentifier(..) => ("E0101", "declared more than once"),
+            Self::InvalidUsage(..) => ("E0102", "invalid usage"),
+            Self::IndexOutOfRange(..) => ("E0103", "index out of range"),
+            Self::TooManyConstraints(..) => ("E0104", "conflicting constraint"),
+            Self::InvalidPeriodicColumn(..) => ("E0105", "invalid periodic column"),
+            Self::MissingDeclaration(..) => ("E0106", ""),
+            Self::InvalidTransitionExemptions(..) => ("E0107", "invalid transition exemptions"),
+            Self::InvalidConstant(..) => ("E0108", "invalid constant"),
+            Self::InvalidListComprehension(..) => ("E0109", "invalid list comprehension"),
+            Self::InvalidEvaluatorCall(..) => ("E0110", "invalid evaluator call"),
+            Self::InvalidImport(..) => ("E0111", "invalid import"),
+            Self::InvalidAssertion(..) => ("E0112", "invalid boundary constraint"),
+            Self::InvalidField(..) => ("E0113", "invalid field"),
+            Self::DivisionByZero(..) => ("E0114", "division by zero"),
+            Self::InvalidConstraint(..) => ("E0115", "invalid constraint"),
+        };
+
+        let mut diagnostic = Diagnostic::error(self.message()).with_code(code);
+        if let Some(span) = self.span() {
+            diagnostic = diagnostic.with_label(Label::primary(span).with_message(label));
+        }
+
+        match self {
+            Self::TooManyConstraints(..) => diagnostic
+                .with_note("only one boundary constraint can be applied to each row of a column"),
+            Self::InvalidPeriodicColumn(..) => diagnostic.with_note(format!(
+                "the length of a periodic column must be a power of two and at least {}",
+                MIN_CYCLE_LENGTH
+            )),
+            Self::InvalidTransitionExemptions(..) => diagnostic.with_note(
+                "the transition constraints are not enforced against the last rows of the trace, \
+                and boundary constraints at the last row are applied to the row at index \
+                trace_length - transition_exemptions",
+            ),
+            Self::InvalidConstant(..) => diagnostic.with_note(
+                "a matrix constant must have the same number of columns in every row, and every \
+                constant must be smaller than the modulus of the field of the AIR",
+            ),
+            Self::InvalidListComprehension(..) => diagnostic
+                .with_note("all of the iterables of a comprehension must have the same length"),
+            Self::InvalidEvaluatorCall(..) => diagnostic.with_note(
+                "the arguments of an evaluator must provide one column of the matching trace for \
+                each column of its parameters",
+            ),
+            Self::InvalidImport(..) => diagnostic.with_note(
+                "only the constants and evaluator functions declared in a library module can be \
+                imported from it",
+            ),
+            Self::InvalidAssertion(..) => diagnostic.with_note(format!(
+                "a periodic boundary constraint is applied to every stride-th row from its first \
+                row, where the stride must be a power of two of at least {}, a public input \
+                asserted by a sequence constraint must have a length which is a power of two, and \
+                a vector bound to a group of trace columns must have the length of the group",
+                MIN_ASSERTION_STRIDE
+            )),
+            Self::InvalidField(..) => diagnostic.with_note(format!(
+                "the field of an AIR can be declared once, as one of {}",
+                Field::ALL.map(|field| field.name()).join(", ")
+            )),
+            Self::DivisionByZero(..) => diagnostic.with_note(
+                "zero has no multiplicative inverse, so the denominator of a division and the \
+                argument of inv must not be equal to zero",
+            ),
+            Self::InvalidConstraint(..) => diagnostic.with_note(
+                "a transition constraint which is equal to a non-zero constant can never be \
+                satisfied, and at least one transition constraint of the main trace must not be \
+                always satisfied",
+            ),
+            Self::MissingDeclaration(..) => diagnostic.with_note(
+                "an AIR requires the trace_columns, public_inputs, boundary_constraints and \
+                transition_constraints sections",
+            ),
+            _ => diagnostic,
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use super::SemanticError;
+use parser::ast::{self, BoundaryExpr, Identifier, Literal, SourceSpan, TransitionExpr};
+use std::fmt::Display;
+
+// FIELD
+// ================================================================================================
+
+/// The prime field over which the constraints of an AIR are defined, which is declared with the
+/// `field` section of the AIR. The 64-bit field is used when no field is declared.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Field {
+    /// The 62-bit field with modulus 2^62 - 111 * 2^39 + 1.
+    F62,
+    /// The 64-bit field with modulus 2^64 - 2^32 + 1.
+    #[default]
+    F64,
+    /// The 128-bit field with modulus 2^128 - 45 * 2^40 + 1.
+    F128,
+}
+
+impl Field {
+    /// The fields which can be declared, in the order of their moduli.
+    pub const ALL: [Field; 3] = [Field::F62, Field::F64, Field::F128];
+
+    /// Returns the field declared by the provided identifier.
+    ///
+    /// # Errors
+    /// Returns an error if the identifier is not the name of a supported field.
+    pub(super) fn from_ident(ident: &Identifier) -> Result<Self, SemanticError> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.name() == ident.name())
+            .ok_or_else(|| {
+                SemanticError::InvalidField(
+                    format!("Field {} is not supported", ident),
+                    ident.span(),
+                )
+            })
+    }
+
+    /// Returns the name under which the field is declared, e.g. `f64`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::F62 => "f62",
+            Self::F64 => "f64",
+            Self::F128 => "f128",
+        }
+    }
+
+    /// Returns the modulus of the field.
+    pub fn modulus(&self) -> u128 {
+        match self {
+            Self::F62 => 4611624995532046337,
+            Self::F64 => 0xFFFF_FFFF_0000_0001,
+            Self::F128 => 340282366920938463463374557953744961537,
+        }
+    }
+
+    // --- ARITHMETIC -----------------------------------------------------------------------------
+
+    /// Returns the sum of the provided field elements.
+    pub(crate) fn add(&self, lhs: u128, rhs: u128) -> u128 {
+        let (sum, overflow) = lhs.overflowing_add(rhs);
+        if overflow || sum >= self.modulus() {
+            sum.wrapping_sub(self.modulus())
+        } else {
+            sum
+        }
+    }
+
+    /// Returns the product of the provided field elements. The product is accumulated by doubling
+    /// and adding, since the product of two elements of the 128-bit field doesn't fit into a u128.
+    pub(crate) fn mul(&self, lhs: u128, rhs: u128) -> u128 {
+        let mut result = 0;
+        for bit in (0..u128::BITS).rev() {
+            result = self.add(result, result);
+            if (rhs >> bit) & 1 == 1 {
+                result = self.add(result, lhs);
+            }
+        }
+        result
+    }
+
+    /// Returns the additive inverse of the provided field element.
+    pub(crate) fn neg(&self, value: u128) -> u128 {
+        if value == 0 {
+            0
+        } else {
+            self.modulus() - value
+        }
+    }
+
+    /// Returns the provided field element raised to the specified power.
+    pub(crate) fn exp(&self, base: u128, power: usize) -> u128 {
+        self.pow(base, power as u128)
+    }
+
+    /// Returns the multiplicative inverse of the provided field element, which is computed as
+    /// value^(p - 2) by Fermat's little theorem. The inverse of zero is zero.
+    pub(crate) fn inv(&self, value: u128) -> u128 {
+        self.pow(value, self.modulus() - 2)
+    }
+
+    /// Returns the provided field element raised to the specified power, which can be larger than
+    /// the largest usize when it is derived from the modulus.
+    fn pow(&self, base: u128, power: u128) -> u128 {
+        let mut result = 1;
+        let mut base = base;
+        let mut power = power;
+        while power > 0 {
+            if power & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            power >>= 1;
+        }
+        result
+    }
+
+    // --- VALIDATION -----------------------------------------------------------------------------
+
+    /// Checks that the provided value is an element of the field.
+    ///
+    /// # Errors
+    /// Returns an error located at the provided span if the value is not smaller than the modulus.
+    pub(crate) fn validate_value(
+        &self,
+        value: u128,
+        span: SourceSpan,
+    ) -> Result<(), SemanticError> {
+        if value >= self.modulus() {
+            return Err(SemanticError::InvalidConstant(
+                format!(
+                    "Value {} is not an element of field {}, whose modulus is {}",
+                    value,
+                    self,
+                    self.modulus()
+                ),
+                span,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value of the provided literal as an element of the field. A literal relative to
+    /// the modulus is reduced modulo the field, so `p - 0` is zero.
+    ///
+    /// # Errors
+    /// Returns an error located at the provided span if the value is not an element of the field.
+    pub(crate) fn resolve_literal(
+        &self,
+        literal: Literal,
+        span: SourceSpan,
+    ) -> Result<u128, SemanticError> {
+        match literal {
+            Literal::Int(value) => {
+                self.validate_value(value, span)?;
+                Ok(value)
+            }
+            Literal::ModulusMinus(offset) => {
+                if offset > self.modulus() {
+                    return Err(SemanticError::InvalidConstant(
+                        format!(
+                            "Value {} is not an element of field {}, whose modulus is {}",
+                            literal,
+                            self,
+                            self.modulus()
+                        ),
+                        span,
+                    ));
+                }
+                Ok((self.modulus() - offset) % self.modulus())
+            }
+        }
+    }
+
+    /// Checks that all of the constants of the provided transition expression are elements of the
+    /// field. The exponents are not field elements, so they are not checked.
+    ///
+    /// # Errors
+    /// Returns an error located at the provided span, which is the span of the statement of the
+    /// expression, if any of its constants is not smaller than the modulus.
+    pub(crate) fn validate_transition_expr(
+        &self,
+        expr: &TransitionExpr,
+        span: SourceSpan,
+    ) -> Result<(), SemanticError> {
+        match expr {
+            TransitionExpr::Const(value) => self.validate_value(*value, span),
+            TransitionExpr::Add(lhs, rhs)
+            | TransitionExpr::Sub(lhs, rhs)
+            | TransitionExpr::Mul(lhs, rhs) => {
+                self.validate_transition_expr(lhs, span)?;
+                self.validate_transition_expr(rhs, span)
+            }
+            TransitionExpr::Exp(base, _) | TransitionExpr::Neg(base) => {
+                self.validate_transition_expr(base, span)
+            }
+            TransitionExpr::ListFolding(
+                ast::ListFolding::Sum(list) | ast::ListFolding::Prod(list),
+            ) => match list {
+                ast::ListValue::Var(_) => Ok(()),
+                ast::ListValue::Vector(exprs) => exprs
+                    .iter()
+                    .try_for_each(|expr| self.validate_transition_expr(expr, span)),
+                ast::ListValue::Comprehension(comprehension) => {
+                    self.validate_transition_expr(comprehension.expr(), span)
+                }
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that all of the constants of the provided boundary expression are elements of the
+    /// field. The exponents are not field elements, so they are not checked.
+    ///
+    /// # Errors
+    /// Returns an error located at the provided span, which is the span of the statement of the
+    /// expression, if any of its constants is not smaller than the modulus.
+    pub(crate) fn validate_boundary_expr(
+        &self,
+        expr: &BoundaryExpr,
+        span: SourceSpan,
+    ) -> Result<(), SemanticError> {
+        match expr {
+            BoundaryExpr::Const(value) => self.validate_value(*value, span),
+            BoundaryExpr::Add(lhs, rhs)
+            | BoundaryExpr::Sub(lhs, rhs)
+            | BoundaryExpr::Mul(lhs, rhs)
+            | BoundaryExpr::Div(lhs, rhs) => {
+                self.validate_boundary_expr(lhs, span)?;
+                self.validate_boundary_expr(rhs, span)
+            }
+            BoundaryExpr::Exp(base, _) | BoundaryExpr::Inv(base) | BoundaryExpr::Neg(base) => {
+                self.validate_boundary_expr(base, span)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Returns the field declared by the `field` section of the provided root module, or the default
+/// field if the section is omitted.
+///
+/// # Errors
+/// Returns an error if the declared field is not supported or if the field is declared more than
+/// once.
+pub(super) fn get_field(source: &[ast::SourceSection]) -> Result<Field, SemanticError> {
+    let mut declarations = source.iter().filter_map(|section| match section {
+        ast::SourceSection::Field(ident) => Some(ident),
+        _ => None,
+    });
+    let Some(ident) = declarations.next() else {
+        return Ok(Field::default());
+    };
+    if let Some(duplicate) = declarations.next() {
+        return Err(SemanticError::InvalidField(
+            "The field was declared more than once".to_string(),
+            duplicate.span(),
+        ));
+    }
+
+    Field::from_ident(ident)
+}
@@ -29,33 +29,27 @@ impl SourceValidator {
         }
     }
 
-    /// Returns a SemanticError if any of the required declarations are missing.
-    pub fn check(&self) -> Result<(), SemanticError> {
-        // make sure trace_columns are declared.
-        if !self.trace_columns_exists {
-            return Err(SemanticError::MissingDeclaration(
-                "trace_columns section is missing".to_string(),
-            ));
-        }
-        // make sure public_inputs are declared.
-        if !self.public_inputs_exists {
-            return Err(SemanticError::MissingDeclaration(
-                "public_inputs section is missing".to_string(),
-            ));
-        }
-        // make sure boundary_constraints are declared.
-        if !self.boundary_constraints_exists {
-            return Err(SemanticError::MissingDeclaration(
-                "boundary_constraints section is missing".to_string(),
-            ));
-        }
-        // make sure transition_constraints are declared.
-        if !self.transition_constraints_exists {
-            return Err(SemanticError::MissingDeclaration(
-                "transition_constraints section is missing".to_string(),
-            ));
-        }
+    /// Returns a SemanticError for each of the required declarations which are missing.
+    pub fn check(&self) -> Result<(), Vec<SemanticError>> {
+        let required_sections = [
+            ("trace_columns", self.trace_columns_exists),
+            ("public_inputs", self.public_inputs_exists),
+            ("boundary_constraints", self.boundary_constraints_exists),
+            ("transition_constraints", self.transition_constraints_exists),
+        ];
+
+        let errors: Vec<SemanticError> = required_sections
+            .iter()
+            .filter(|(_, exists)| !exists)
+            .map(|(section, _)| {
+                SemanticError::MissingDeclaration(format!("{} section is missing", section))
+            })
+            .collect();
 
-        Ok(())
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
 }
@@ -1,5 +1,5 @@
-use parser::ast;
-pub use parser::ast::{boundary_constraints::BoundaryExpr, Identifier, PublicInput};
+pub use parser::ast::{Identifier, PublicInput, SourceSpan};
+use parser::{ast, Program};
 use std::collections::BTreeMap;
 
 mod symbol_table;
@@ -7,19 +7,25 @@ use symbol_table::{IdentifierType, SymbolTable};
 
 pub mod boundary_constraints;
 use boundary_constraints::BoundaryConstraints;
+pub use boundary_constraints::{AssertionRows, AssertionValue, BoundaryExpr};
 
 pub mod transition_constraints;
 use transition_constraints::{AlgebraicGraph, TransitionConstraints, MIN_CYCLE_LENGTH};
 pub use transition_constraints::{NodeIndex, TransitionConstraintDegree};
 
 mod error;
-use error::SemanticError;
+pub use error::SemanticError;
+
+mod field;
+pub use field::Field;
+
+mod modules;
 
 mod helpers;
 use helpers::SourceValidator;
 
 pub type PublicInputs = Vec<(String, usize)>;
-pub type PeriodicColumns = Vec<Vec<u64>>;
+pub type PeriodicColumns = Vec<Vec<u128>>;
 
 /// Internal representation of an AIR.
 ///
@@ -27,86 +33,222 @@ pub type PeriodicColumns = Vec<Vec<u64>>;
 #[derive(Default, Debug)]
 pub struct AirIR {
     air_name: String,
+    field: Field,
+    main_trace_width: usize,
+    aux_segment_widths: Vec<usize>,
+    random_values: Option<BTreeMap<usize, usize>>,
     public_inputs: PublicInputs,
     periodic_columns: PeriodicColumns,
     boundary_constraints: BoundaryConstraints,
     transition_constraints: TransitionConstraints,
+    num_transition_exemptions: usize,
 }
 
 impl AirIR {
     // --- CONSTRUCTOR ----------------------------------------------------------------------------
 
     /// Consumes the provided source and generates a matching AirIR.
-    pub fn from_source(source: &ast::Source) -> Result<Self, SemanticError> {
+    ///
+    /// # Errors
+    /// Returns all of the [SemanticError]s found in the source. Invalid declarations and
+    /// constraints are skipped, so that the errors in the rest of the source are still reported.
+    pub fn from_source(source: &ast::Source) -> Result<Self, Vec<SemanticError>> {
+        Self::from_modules(source, &BTreeMap::new())
+    }
+
+    /// Consumes the provided program, whose root module can import items from its library
+    /// modules, and generates a matching AirIR.
+    ///
+    /// # Errors
+    /// Returns all of the [SemanticError]s found in the modules of the program.
+    pub fn from_program(program: &Program) -> Result<Self, Vec<SemanticError>> {
+        Self::from_modules(program.root(), program.libraries())
+    }
+
+    /// Generates the AirIR of the provided root module, which can import items from the provided
+    /// library modules.
+    fn from_modules(
+        source: &ast::Source,
+        libraries: &BTreeMap<String, ast::Library>,
+    ) -> Result<Self, Vec<SemanticError>> {
         let ast::Source(source) = source;
 
         // set a default name.
         let mut air_name = "CustomAir";
 
         let mut validator = SourceValidator::new();
+        let mut errors = Vec::new();
+        let mut exemptions: Option<&ast::TransitionExemptions> = None;
+
+        // the field is resolved first, since the values of the constants are validated against
+        // its modulus.
+        let field = field::get_field(source).unwrap_or_else(|err| {
+            errors.push(err);
+            Field::default()
+        });
 
         // process the declarations of identifiers first, using a single symbol table to enforce
         // uniqueness.
-        let mut symbol_table = SymbolTable::default();
+        let mut symbol_table = SymbolTable::new(field);
 
         for section in source {
             match section {
-                ast::SourceSection::AirDef(Identifier(air_def)) => {
+                ast::SourceSection::AirDef(air_def) => {
                     // update the name of the air.
-                    air_name = air_def;
+                    air_name = air_def.name();
+                }
+                ast::SourceSection::Import(import) => {
+                    // process & validate the imported module and add the imported items
+                    if let Err(errs) = modules::insert_import(&mut symbol_table, libraries, import)
+                    {
+                        errors.extend(errs);
+                    }
+                }
+                ast::SourceSection::Constants(constants) => {
+                    // process & validate the constants
+                    if let Err(errs) = symbol_table.insert_constants(constants) {
+                        errors.extend(errs);
+                    }
                 }
                 ast::SourceSection::TraceCols(columns) => {
                     // process & validate the main trace columns
-                    symbol_table.insert_main_trace_columns(&columns.main_cols)?;
+                    if let Err(errs) = symbol_table.insert_main_trace_columns(&columns.main_cols) {
+                        errors.extend(errs);
+                    }
                     // process & validate the auxiliary trace columns
-                    symbol_table.insert_aux_trace_columns(&columns.aux_cols)?;
+                    if let Err(errs) = symbol_table.insert_aux_trace_columns(&columns.aux_segments)
+                    {
+                        errors.extend(errs);
+                    }
                     validator.exists("trace_columns");
                 }
                 ast::SourceSection::PublicInputs(inputs) => {
                     // process & validate the public inputs
-                    symbol_table.insert_public_inputs(inputs)?;
+                    if let Err(errs) = symbol_table.insert_public_inputs(inputs) {
+                        errors.extend(errs);
+                    }
                     validator.exists("public_inputs");
                 }
                 ast::SourceSection::PeriodicColumns(columns) => {
                     // process & validate the periodic columns
-                    symbol_table.insert_periodic_columns(columns)?;
+                    if let Err(errs) = symbol_table.insert_periodic_columns(columns) {
+                        errors.extend(errs);
+                    }
+                }
+                ast::SourceSection::EvaluatorFunction(evaluator) => {
+                    // the body of the evaluator is processed when it is called.
+                    if let Err(err) = symbol_table.insert_evaluator(evaluator) {
+                        errors.push(err);
+                    }
+                }
+                ast::SourceSection::TransitionExemptions(declaration) => {
+                    // the number of exemptions is validated once the constraints are processed.
+                    if exemptions.is_some() {
+                        errors.push(SemanticError::InvalidTransitionExemptions(
+                            "The number of transition exemptions was declared more than once"
+                                .to_string(),
+                            declaration.span(),
+                        ));
+                    } else {
+                        exemptions = Some(declaration);
+                    }
                 }
                 _ => {}
             }
         }
 
+        // the random values are declared for the auxiliary trace segments, so they are processed
+        // once all of the segments have been declared.
+        for section in source {
+            if let ast::SourceSection::RandomValues(random_values) = section {
+                if let Err(errs) = symbol_table.insert_random_values(random_values) {
+                    errors.extend(errs);
+                }
+            }
+        }
+
         // then process the constraints & validate them against the symbol table.
         let mut boundary_constraints = BoundaryConstraints::default();
         let mut transition_constraints = TransitionConstraints::default();
         for section in source {
             match section {
                 ast::SourceSection::BoundaryConstraints(constraints) => {
-                    for constraint in constraints.boundary_constraints.iter() {
-                        boundary_constraints.insert(&symbol_table, constraint)?;
+                    for stmt in constraints.stmts.iter() {
+                        let result = match stmt {
+                            ast::BoundaryStmt::Constraint(constraint) => {
+                                boundary_constraints.insert(&symbol_table, constraint)
+                            }
+                            ast::BoundaryStmt::Variable(variable) => {
+                                boundary_constraints::insert_variable(&mut symbol_table, variable)
+                            }
+                        };
+                        if let Err(err) = result {
+                            errors.push(err);
+                        }
                     }
+                    // variables are scoped to the section in which they are declared.
+                    symbol_table.clear_variables();
                     validator.exists("boundary_constraints");
                 }
                 ast::SourceSection::TransitionConstraints(constraints) => {
-                    for constraint in constraints.transition_constraints.iter() {
-                        transition_constraints.insert(&symbol_table, constraint)?;
+                    for stmt in constraints.stmts.iter() {
+                        if let Err(err) =
+                            transition_constraints.insert_stmt(&mut symbol_table, stmt)
+                        {
+                            errors.push(err);
+                        }
                     }
+                    // variables are scoped to the section in which they are declared.
+                    symbol_table.clear_variables();
                     validator.exists("transition_constraints");
                 }
                 _ => {}
             }
         }
 
+        // simplify the transition constraints once all of them have been added, unless some of
+        // them were invalid and could not be added.
+        if errors.is_empty() {
+            if let Err(errs) = transition_constraints.optimize(field) {
+                errors.extend(errs);
+            }
+        }
+
+        // use the declared number of transition exemptions, or the minimum number required by the
+        // transition constraints if it wasn't declared.
+        let num_transition_exemptions = match exemptions {
+            Some(exemptions) => transition_constraints
+                .validate_exemptions(exemptions, boundary_constraints.has_last())
+                .unwrap_or_else(|err| {
+                    errors.push(err);
+                    0
+                }),
+            None => transition_constraints.min_num_exemptions(),
+        };
+
+        let (main_trace_width, aux_segment_widths) = symbol_table.trace_widths();
+        let random_values = symbol_table.random_values();
         let (public_inputs, periodic_columns) = symbol_table.into_declarations();
 
         // validate sections
-        validator.check()?;
+        if let Err(errs) = validator.check() {
+            errors.extend(errs);
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
 
         Ok(Self {
             air_name: air_name.to_string(),
+            field,
+            main_trace_width,
+            aux_segment_widths,
+            random_values,
             public_inputs,
             periodic_columns,
             boundary_constraints,
             transition_constraints,
+            num_transition_exemptions,
         })
     }
 
@@ -116,6 +258,41 @@ impl AirIR {
         &self.air_name
     }
 
+    /// Returns the field over which the AIR is defined.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    pub fn main_trace_width(&self) -> usize {
+        self.main_trace_width
+    }
+
+    /// Returns the total number of columns in all segments of the auxiliary trace.
+    pub fn aux_trace_width(&self) -> usize {
+        self.aux_segment_widths.iter().sum()
+    }
+
+    /// Returns the number of columns in each segment of the auxiliary trace, in the order in which
+    /// the segments were declared.
+    pub fn aux_segment_widths(&self) -> &[usize] {
+        &self.aux_segment_widths
+    }
+
+    /// Returns the number of random values which must be provided by the verifier for the
+    /// specified segment of the auxiliary trace, i.e. the number of random values declared for the
+    /// segment in the `random_values` section or, if the random values were not declared, one more
+    /// than the largest index of a random value of the segment referenced by the constraints.
+    pub fn num_random_values(&self, segment: usize) -> usize {
+        match &self.random_values {
+            Some(random_values) => random_values.get(&segment).copied().unwrap_or(0),
+            None => self.boundary_constraints.num_random_values(segment).max(
+                self.transition_constraints
+                    .graph()
+                    .num_random_values(segment),
+            ),
+        }
+    }
+
     pub fn public_inputs(&self) -> &PublicInputs {
         &self.public_inputs
     }
@@ -130,24 +307,21 @@ impl AirIR {
         self.boundary_constraints.main_len()
     }
 
-    pub fn main_first_boundary_constraints(&self) -> Vec<(usize, &BoundaryExpr)> {
-        self.boundary_constraints.main_first()
-    }
-
-    pub fn main_last_boundary_constraints(&self) -> Vec<(usize, &BoundaryExpr)> {
-        self.boundary_constraints.main_last()
+    /// Returns the boundary constraints of the main trace, as the index of the trace column, the
+    /// rows and the value of each constraint. Constraints at a single row are returned first,
+    /// followed by the constraints at the last row and the periodic constraints.
+    pub fn main_boundary_constraints(&self) -> Vec<(usize, AssertionRows, &AssertionValue)> {
+        self.boundary_constraints.main()
     }
 
     pub fn num_aux_assertions(&self) -> usize {
         self.boundary_constraints.aux_len()
     }
 
-    pub fn aux_first_boundary_constraints(&self) -> Vec<(usize, &BoundaryExpr)> {
-        self.boundary_constraints.aux_first()
-    }
-
-    pub fn aux_last_boundary_constraints(&self) -> Vec<(usize, &BoundaryExpr)> {
-        self.boundary_constraints.aux_last()
+    /// Returns the boundary constraints of the aux trace, in the same order as the constraints of
+    /// the main trace.
+    pub fn aux_boundary_constraints(&self) -> Vec<(usize, AssertionRows, &AssertionValue)> {
+        self.boundary_constraints.aux()
     }
 
     // --- PUBLIC ACCESSORS FOR TRANSITION CONSTRAINTS --------------------------------------------
@@ -168,6 +342,27 @@ impl AirIR {
         self.transition_constraints.aux_constraints()
     }
 
+    /// Returns the number of consecutive rows of the trace over which the transition constraints
+    /// are evaluated, starting from the current row. The frame always contains at least the
+    /// current and the next row, and is wider if the constraints reference rows at larger offsets.
+    pub fn frame_width(&self) -> usize {
+        self.transition_constraints.graph().max_row_offset().max(1) + 1
+    }
+
+    /// Returns the number of rows at the end of the trace against which the transition
+    /// constraints are not enforced. Boundary constraints at the last row are applied to the row
+    /// at index `trace_length - num_transition_exemptions`.
+    pub fn num_transition_exemptions(&self) -> usize {
+        self.num_transition_exemptions
+    }
+
+    /// Returns the names of the variables declared in the transition constraints and the indices
+    /// of the nodes representing their values in the transition graph, in the order of
+    /// declaration.
+    pub fn transition_variables(&self) -> &[(String, NodeIndex)] {
+        self.transition_constraints.variables()
+    }
+
     pub fn transition_graph(&self) -> &AlgebraicGraph {
         self.transition_constraints.graph()
     }
@@ -177,7 +372,9 @@ impl AirIR {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use parser::parse;
+    use diagnostics::ToDiagnostic;
+    use parser::{parse, parse_library};
+    use transition_constraints::Operation;
 
     #[test]
     fn boundary_constraints() {
@@ -282,25 +479,198 @@ mod tests {
     }
 
     #[test]
-    fn transition_constraints() {
+    fn boundary_constraints_at_rows() {
         let source = "
         trace_columns:
-            main: [clk]
+            main: [clk, a, b]
+        public_inputs:
+            stack_inputs: [4]
+        transition_constraints:
+            enf clk' = clk + 1
+        boundary_constraints:
+            enf b.every(2) = stack_inputs
+            enf a.every(8, 3) = 1
+            enf clk.last = 7
+            enf a.row(5) = 2
+            enf clk.first = 0";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+
+        // the constraints are sorted by rows, and then by column.
+        let one = AssertionValue::Single(BoundaryExpr::Const(1));
+        let two = AssertionValue::Single(BoundaryExpr::Const(2));
+        let zero = AssertionValue::Single(BoundaryExpr::Const(0));
+        let seven = AssertionValue::Single(BoundaryExpr::Const(7));
+        let sequence = AssertionValue::Sequence("stack_inputs".to_string(), 4);
+        assert_eq!(
+            ir.main_boundary_constraints(),
+            vec![
+                (0, AssertionRows::Single(0), &zero),
+                (1, AssertionRows::Single(5), &two),
+                (0, AssertionRows::Last, &seven),
+                (
+                    2,
+                    AssertionRows::Periodic {
+                        first_row: 0,
+                        stride: 2
+                    },
+                    &sequence
+                ),
+                (
+                    1,
+                    AssertionRows::Periodic {
+                        first_row: 3,
+                        stride: 8
+                    },
+                    &one
+                ),
+            ]
+        );
+        assert_eq!(ir.num_main_assertions(), 5);
+    }
+
+    #[test]
+    fn vector_boundary_constraints() {
+        let source = "
+        constants:
+            INIT: [1, 2]
+        trace_columns:
+            main: [clk, state[3], b[2]]
+            aux: [p[2]]
+        public_inputs:
+            stack_inputs: [3]
+        transition_constraints:
+            enf clk' = clk + 1
+        boundary_constraints:
+            enf state.first = stack_inputs
+            enf b.last = INIT
+            enf p.every(4, 1) = INIT";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+
+        // one constraint is applied to each column of the group, with the matching element.
+        let stack_inputs: Vec<AssertionValue> = (0..3)
+            .map(|idx| {
+                AssertionValue::Single(BoundaryExpr::PubInput("stack_inputs".to_string(), idx))
+            })
+            .collect();
+        let one = AssertionValue::Single(BoundaryExpr::Const(1));
+        let two = AssertionValue::Single(BoundaryExpr::Const(2));
+        assert_eq!(
+            ir.main_boundary_constraints(),
+            vec![
+                (1, AssertionRows::Single(0), &stack_inputs[0]),
+                (2, AssertionRows::Single(0), &stack_inputs[1]),
+                (3, AssertionRows::Single(0), &stack_inputs[2]),
+                (4, AssertionRows::Last, &one),
+                (5, AssertionRows::Last, &two),
+            ]
+        );
+        let rows = AssertionRows::Periodic {
+            first_row: 1,
+            stride: 4,
+        };
+        assert_eq!(
+            ir.aux_boundary_constraints(),
+            vec![(0, rows, &one), (1, rows, &two)]
+        );
+    }
+
+    #[test]
+    fn err_vector_boundary_constraints() {
+        let source = "
+        constants:
+            INIT: [1, 2]
+        trace_columns:
+            main: [clk, state[3], b[2]]
+        public_inputs:
+            stack_inputs: [16]
+        transition_constraints:
+            enf clk' = clk + 1
+        boundary_constraints:
+            enf state.first = stack_inputs
+            enf state.last = INIT
+            enf b.first = INIT
+            enf b[1].first = 0
+            enf b.last = stack_inputs[0]";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid constraints were accepted");
+        assert_eq!(errors.len(), 4);
+        assert!(matches!(errors[0], SemanticError::InvalidAssertion(..)));
+        assert!(matches!(errors[1], SemanticError::InvalidAssertion(..)));
+        assert!(matches!(errors[2], SemanticError::TooManyConstraints(..)));
+        assert_eq!(
+            errors[2].message(),
+            "A boundary constraint was already defined for MainTraceColumn 'b[1]' at the first row"
+        );
+        assert!(matches!(errors[3], SemanticError::InvalidUsage(..)));
+    }
+
+    #[test]
+    fn err_bc_overlapping_rows() {
+        let source = "
+        trace_columns:
+            main: [clk, a]
         public_inputs:
             stack_inputs: [16]
+        transition_constraints:
+            enf clk' = clk + 1
         boundary_constraints:
             enf clk.first = 0
+            enf clk.row(0) = 0
+            enf clk.every(4, 1) = 1
+            enf clk.row(9) = 1
+            enf clk.every(2, 1) = 1
+            enf a.every(4, 1) = 1
+            enf a.every(8, 3) = 1
+            enf a.last = 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        // the constraints on column a don't overlap, since rows 1 + 4k and 3 + 8k are disjoint,
+        // and the last row depends on the length of the trace.
+        let errors =
+            AirIR::from_source(&parsed).expect_err("Overlapping constraints were accepted");
+        assert_eq!(errors.len(), 3);
+        assert!(errors
+            .iter()
+            .all(|err| matches!(err, SemanticError::TooManyConstraints(..))));
+        assert_eq!(
+            errors[2].message(),
+            "A boundary constraint was already defined for MainTraceColumn 'clk' at every 4 rows from row 1"
+        );
+    }
+
+    #[test]
+    fn err_bc_invalid_periodic_rows() {
+        let source = "
+        trace_columns:
+            main: [clk, a, b, c]
+        public_inputs:
+            stack_inputs: [6]
         transition_constraints:
-            enf clk' = clk + 1";
+            enf clk' = clk + 1
+        boundary_constraints:
+            enf clk.every(1) = 0
+            enf a.every(6) = 0
+            enf b.every(4, 4) = 0
+            enf c.every(2) = stack_inputs";
 
         let parsed = parse(source).expect("Parsing failed");
 
-        let result = AirIR::from_source(&parsed);
-        assert!(result.is_ok());
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid constraints were accepted");
+        assert_eq!(errors.len(), 4);
+        assert!(errors
+            .iter()
+            .all(|err| matches!(err, SemanticError::InvalidAssertion(..))));
     }
 
     #[test]
-    fn transition_constraints_using_parens() {
+    fn transition_constraints() {
         let source = "
         trace_columns:
             main: [clk]
@@ -309,7 +679,7 @@ mod tests {
         boundary_constraints:
             enf clk.first = 0
         transition_constraints:
-            enf clk' = (clk + 1)";
+            enf clk' = clk + 1";
 
         let parsed = parse(source).expect("Parsing failed");
 
@@ -318,101 +688,314 @@ mod tests {
     }
 
     #[test]
-    fn err_tc_empty_or_omitted() {
-        // if transition constraints are empty, an error should be returned at parser level.
+    fn trace_layout() {
         let source = "
         trace_columns:
-            main: [clk]
+            main: [a, b, c]
+            aux: [p]
         public_inputs:
             stack_inputs: [16]
+        boundary_constraints:
+            enf p.first = $rand[2]
         transition_constraints:
+            enf p' = p * (a + $rand[0])";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        assert_eq!(ir.main_trace_width(), 3);
+        assert_eq!(ir.aux_trace_width(), 1);
+        assert_eq!(ir.aux_segment_widths(), &[1]);
+        assert_eq!(ir.num_random_values(0), 3);
+        assert_eq!(ir.aux_degrees()[0].base(), 2);
+        assert_eq!(ir.num_aux_assertions(), 1);
+    }
+
+    #[test]
+    fn aux_trace_segments() {
+        let source = "
+        trace_columns:
+            main: [a, b]
+            aux: [p, q[2]]
+            aux ranges: [r]
+        public_inputs:
+            stack_inputs: [16]
         boundary_constraints:
-            enf clk.first = 0";
+            enf p.first = $rand[1]
+            enf r.last = $rand.ranges[3]
+        transition_constraints:
+            enf q[1]' = q[0] * (a + $rand[0])
+            enf r' = r * ($rand.ranges[0] - b)";
 
-        assert!(parse(source).is_err());
+        let parsed = parse(source).expect("Parsing failed");
 
-        // if transition constraints are omitted, an error should be returned at IR level.
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        assert_eq!(ir.aux_trace_width(), 4);
+        assert_eq!(ir.aux_segment_widths(), &[3, 1]);
+        assert_eq!(ir.num_random_values(0), 2);
+        assert_eq!(ir.num_random_values(1), 4);
+
+        // the columns of all segments are indexed together in the auxiliary trace.
+        let columns: Vec<usize> = ir
+            .aux_boundary_constraints()
+            .iter()
+            .map(|(column, ..)| *column)
+            .collect();
+        assert_eq!(columns, vec![0, 3]);
+        let graph = ir.transition_graph();
+        let ops: Vec<&Operation> = graph.nodes().map(|(_, node)| node.op()).collect();
+        assert!(ops.contains(&&Operation::AuxTraceRow(3, 1)));
+        assert!(ops.contains(&&Operation::RandomValue(1, 0)));
+    }
+
+    #[test]
+    fn random_values() {
         let source = "
         trace_columns:
-            main: [clk]
+            main: [a]
+            aux: [p]
+            aux bus: [q]
         public_inputs:
             stack_inputs: [16]
+        random_values:
+            $rand: [4]
+            $rand.bus: [alpha, beta]
         boundary_constraints:
-            enf clk.first = 0";
+            enf q.first = beta
+        transition_constraints:
+            enf p' = p * (a + $rand[1])
+            enf q' = q * (a + alpha)";
 
         let parsed = parse(source).expect("Parsing failed");
-        let result = AirIR::from_source(&parsed);
-        assert!(result.is_err());
+
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        // the declared number of random values is used, even if fewer are referenced.
+        assert_eq!(ir.num_random_values(0), 4);
+        assert_eq!(ir.num_random_values(1), 2);
+
+        let (_, _, value) = ir.aux_boundary_constraints()[0];
+        assert_eq!(value, &AssertionValue::Single(BoundaryExpr::Rand(1, 1)));
+        let graph = ir.transition_graph();
+        let ops: Vec<&Operation> = graph.nodes().map(|(_, node)| node.op()).collect();
+        assert!(ops.contains(&&Operation::RandomValue(0, 1)));
+        assert!(ops.contains(&&Operation::RandomValue(1, 0)));
     }
 
     #[test]
-    fn err_tc_column_undeclared() {
+    fn err_random_values() {
         let source = "
         trace_columns:
-            main: [ctx]
+            main: [a]
+            aux: [p]
+            aux bus: [q]
         public_inputs:
             stack_inputs: [16]
+        random_values:
+            $rand: [2]
+            $rand: [alpha]
+            $rand.ranges: [4]
+            $rand.bus: [0]
         boundary_constraints:
-            enf ctx.first = 0
+            enf p.first = $rand[2]
         transition_constraints:
-            enf clk' = clk + 1";
+            enf p' = p * (a + $rand[1])
+            enf q' = q * (a + $rand.bus[0])";
 
         let parsed = parse(source).expect("Parsing failed");
 
-        let result = AirIR::from_source(&parsed);
-        assert!(result.is_err());
+        let errors = AirIR::from_source(&parsed).expect_err("IR generation should have failed");
+        let messages: Vec<&str> = errors.iter().map(|err| err.message()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Random values $rand were declared more than once",
+                "Identifier ranges was not declared",
+                "Random values $rand.bus must contain at least one value",
+                "Out-of-range index 2 in random values $rand of length 2",
+                "Out-of-range index 0 in random values $rand.bus of length 0",
+            ]
+        );
     }
 
     #[test]
-    fn err_public_inputs_empty_or_omitted() {
-        // if public inputs are empty, an error should be returned at parser level.
+    fn err_random_values_without_aux_trace() {
         let source = "
         trace_columns:
-            main: [clk]
+            main: [a]
         public_inputs:
+            stack_inputs: [16]
+        random_values:
+            $rand: [2]
+        boundary_constraints:
+            enf a.first = 0
         transition_constraints:
-            enf clk' = clk + 1
+            enf a' = a + 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("IR generation should have failed");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message(),
+            "Random values $rand were declared without an auxiliary trace segment"
+        );
+    }
+
+    #[test]
+    fn err_random_values_used_without_aux_trace() {
+        let source = "
+        trace_columns:
+            main: [a]
+        public_inputs:
+            stack_inputs: [16]
         boundary_constraints:
-            enf clk.first = 0";
+            enf a.first = 0
+        transition_constraints:
+            enf a' = a * $rand[0]";
 
-        assert!(parse(source).is_err());
+        let parsed = parse(source).expect("Parsing failed");
 
-        // if public inputs are omitted, an error should be returned at IR level.
+        let errors = AirIR::from_source(&parsed).expect_err("IR generation should have failed");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message(),
+            "Random value $rand[0] was used without an auxiliary trace segment"
+        );
+    }
+
+    #[test]
+    fn err_invalid_aux_trace_segment() {
         let source = "
         trace_columns:
-            main: [clk]
+            main: [a]
+            aux bus: [p]
+            aux a: [q]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf p.first = $rand.ranges[0]
         transition_constraints:
-            enf clk' = clk + 1
+            enf p' = p * $rand.a[0]";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("IR generation should have failed");
+        let messages: Vec<&str> = errors.iter().map(|err| err.message()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Cannot declare a as a AuxTraceSegment, since it was already defined as a MainTraceColumn",
+                "Identifier ranges was not declared",
+                "Identifier a was declared as a MainTraceColumn not as an auxiliary trace segment",
+            ]
+        );
+    }
+
+    #[test]
+    fn err_random_values_in_main_boundary_constraints() {
+        let source = "
+        trace_columns:
+            main: [a, b]
+            aux: [p]
+        public_inputs:
+            stack_inputs: [16]
         boundary_constraints:
-            enf clk.first = 0";
+            let x = $rand[0] * 2
+            enf a.first = $rand[1]
+            enf b.last = x + 1
+            enf p.first = $rand[0]
+        transition_constraints:
+            enf a' = a + 1
+            enf p' = p * (a + $rand[0])";
 
         let parsed = parse(source).expect("Parsing failed");
-        let result = AirIR::from_source(&parsed);
-        assert!(result.is_err());
+
+        let errors = AirIR::from_source(&parsed).expect_err("IR generation should have failed");
+        let messages: Vec<&str> = errors.iter().map(|err| err.message()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Random values cannot be used in the boundary constraint of main trace column a, since they are only available to the constraints of the auxiliary trace",
+                "Random values cannot be used in the boundary constraint of main trace column b, since they are only available to the constraints of the auxiliary trace",
+            ]
+        );
     }
 
     #[test]
-    fn err_trace_cols_omitted() {
-        // returns an error if trace columns declaration is missing
+    fn transition_exemptions() {
+        // if the number of exemptions is not declared, it is inferred from the constraints.
         let source = "
+        trace_columns:
+            main: [clk]
         public_inputs:
             stack_inputs: [16]
+        boundary_constraints:
+            enf clk.last = 0
         transition_constraints:
-            enf clk' = clk + 1
+            enf clk' = clk + 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        assert_eq!(ir.num_transition_exemptions(), 1);
+
+        let source = format!("{}\n        transition_exemptions: 3", source);
+        let parsed = parse(&source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        assert_eq!(ir.num_transition_exemptions(), 3);
+    }
+
+    #[test]
+    fn err_transition_exemptions_too_few() {
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
         boundary_constraints:
-            enf clk.first = 0";
+            enf clk.first = 0
+        transition_exemptions: 0
+        transition_constraints:
+            enf clk' = clk + 1";
 
         let parsed = parse(source).expect("Parsing failed");
 
-        let result = AirIR::from_source(&parsed);
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid exemptions were accepted");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            SemanticError::InvalidTransitionExemptions(..)
+        ));
+    }
 
-        // this fails before the check for missing trace columns declaration since the clk column
-        // used in constraints is not declared.
-        assert!(result.is_err());
+    #[test]
+    fn err_transition_exemptions_too_many_with_last_boundary() {
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.last = 0
+        transition_exemptions: 6
+        transition_constraints:
+            enf clk' = clk + 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid exemptions were accepted");
+        assert!(matches!(
+            errors[0],
+            SemanticError::InvalidTransitionExemptions(..)
+        ));
+
+        // without boundary constraints at the last row, the last step is not used.
+        let source = source.replace("clk.last", "clk.first");
+        let parsed = parse(&source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        assert_eq!(ir.num_transition_exemptions(), 6);
     }
 
     #[test]
-    fn op_mul() {
+    fn err_transition_exemptions_declared_twice() {
         let source = "
         trace_columns:
             main: [clk]
@@ -420,16 +1003,56 @@ mod tests {
             stack_inputs: [16]
         boundary_constraints:
             enf clk.first = 0
+        transition_exemptions: 2
+        transition_exemptions: 2
         transition_constraints:
-            enf clk' * clk = 1";
-        let parsed = parse(source).expect("Parsing failed");
+            enf clk' = clk + 1";
 
-        let result = AirIR::from_source(&parsed);
-        assert!(result.is_ok());
+        let parsed = parse(source).expect("Parsing failed");
+        let errors = AirIR::from_source(&parsed).expect_err("Duplicate exemptions were accepted");
+        assert!(matches!(
+            errors[0],
+            SemanticError::InvalidTransitionExemptions(..)
+        ));
     }
 
     #[test]
-    fn op_exp() {
+    fn row_offsets() {
+        let source = "
+        trace_columns:
+            main: [clk, b[2]]
+            aux: [p]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk'' = clk[+1] + 1
+            enf sum([x[+3] for x in b]) = b[0]''
+            enf p[+2] = p * clk";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+
+        let graph = ir.transition_graph();
+        let ops: Vec<&Operation> = graph.nodes().map(|(_, node)| node.op()).collect();
+        for op in [
+            Operation::MainTraceRow(0, 2),
+            Operation::MainTraceRow(0, 1),
+            Operation::MainTraceRow(1, 3),
+            Operation::MainTraceRow(2, 3),
+            Operation::MainTraceRow(1, 2),
+            Operation::AuxTraceRow(0, 2),
+            Operation::AuxTraceRow(0, 0),
+        ] {
+            assert!(ops.contains(&&op), "missing operation {:?}", op);
+        }
+        assert_eq!(graph.max_row_offset(), 3);
+        assert_eq!(ir.frame_width(), 4);
+        // the constraints reference rows up to 3 rows after the current row.
+        assert_eq!(ir.num_transition_exemptions(), 3);
+
+        // the frame contains the next row even if the constraints don't reference it.
         let source = "
         trace_columns:
             main: [clk]
@@ -438,10 +1061,1574 @@ mod tests {
         boundary_constraints:
             enf clk.first = 0
         transition_constraints:
-            enf clk'^2 - clk = 1";
+            enf clk^2 = clk";
         let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        assert_eq!(ir.frame_width(), 2);
+    }
 
-        let result = AirIR::from_source(&parsed);
-        assert!(result.is_ok());
+    #[test]
+    fn previous_row_offsets() {
+        let source = "
+        trace_columns:
+            main: [a, b]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf a.first = 0
+        transition_constraints:
+            let x = b[-1]
+            enf a = a[-1] + 1
+            enf b' = x * a[-1]";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+
+        // each constraint is shifted forward by its earliest row, so a = a[-1] + 1 becomes
+        // a' = a + 1 and b' = b[-1] * a[-1] becomes b[+2] = b * a'.
+        let graph = ir.transition_graph();
+        let ops: Vec<&Operation> = graph.nodes().map(|(_, node)| node.op()).collect();
+        for op in [
+            Operation::MainTraceRow(0, 1),
+            Operation::MainTraceRow(0, 0),
+            Operation::MainTraceRow(1, 2),
+            Operation::MainTraceRow(1, 0),
+        ] {
+            assert!(ops.contains(&&op), "missing operation {:?}", op);
+        }
+        assert!(!ops
+            .iter()
+            .any(|op| matches!(op, Operation::MainTraceRow(_, offset) if *offset < 0)));
+        assert_eq!(ir.frame_width(), 3);
+        // the shifted constraints reference rows up to 2 rows after the current row.
+        assert_eq!(ir.num_transition_exemptions(), 2);
+    }
+
+    #[test]
+    fn err_row_offset_of_constant() {
+        let source = "
+        constants:
+            A: 1
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' = clk + A[+2]";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let errors =
+            AirIR::from_source(&parsed).expect_err("Row offset of a constant was accepted");
+        let messages: Vec<&str> = errors.iter().map(|e| e.message()).collect();
+        assert_eq!(
+            messages,
+            vec!["Identifier A was declared as a ScalarConstant not as a trace column"]
+        );
+    }
+
+    #[test]
+    fn transition_constraints_using_parens() {
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' = (clk + 1)";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let result = AirIR::from_source(&parsed);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn err_tc_empty_or_omitted() {
+        // if transition constraints are empty, an error should be returned at parser level.
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        transition_constraints:
+        boundary_constraints:
+            enf clk.first = 0";
+
+        assert!(parse(source).is_err());
+
+        // if transition constraints are omitted, an error should be returned at IR level.
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let result = AirIR::from_source(&parsed);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn err_tc_column_undeclared() {
+        let source = "
+        trace_columns:
+            main: [ctx]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf ctx.first = 0
+        transition_constraints:
+            enf clk' = clk + 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let result = AirIR::from_source(&parsed);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn constants() {
+        let source = "
+        constants:
+            A: 2
+            B: [3, 4]
+            C: [[5, 6], [7, 8]]
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = A + B[1] * C[0][1]
+        transition_constraints:
+            enf clk' = clk + A * B[0] + C[1][0]";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+
+        // the values of the constants are inlined into the boundary constraint expressions.
+        let expected = BoundaryExpr::Add(
+            Box::new(BoundaryExpr::Const(2)),
+            Box::new(BoundaryExpr::Mul(
+                Box::new(BoundaryExpr::Const(4)),
+                Box::new(BoundaryExpr::Const(6)),
+            )),
+        );
+        assert_eq!(
+            ir.main_boundary_constraints(),
+            vec![(
+                0,
+                AssertionRows::Single(0),
+                &AssertionValue::Single(expected)
+            )]
+        );
+        assert_eq!(ir.main_degrees()[0].base(), 1);
+    }
+
+    #[test]
+    fn err_constant_index_out_of_range() {
+        let source = "
+        constants:
+            B: [3, 4]
+            C: [[5, 6], [7, 8]]
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = B[2]
+        transition_constraints:
+            enf clk' = clk + C[0][2]";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid indices were accepted");
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .all(|err| matches!(err, SemanticError::IndexOutOfRange(..))));
+    }
+
+    #[test]
+    fn err_constant_invalid_usage() {
+        // vectors and matrices can't be used without an index and scalars can't be indexed.
+        let source = "
+        constants:
+            A: 2
+            B: [3, 4]
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = B
+        transition_constraints:
+            enf clk' = clk + A[0]";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid usages were accepted");
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .all(|err| matches!(err, SemanticError::InvalidUsage(..))));
+    }
+
+    #[test]
+    fn err_constant_matrix_with_uneven_rows() {
+        let source = "
+        constants:
+            C: [[5, 6], [7]]
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' = clk + 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid matrix was accepted");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], SemanticError::InvalidConstant(..)));
+    }
+
+    #[test]
+    fn field_declaration() {
+        let source = "
+        field: f128
+        trace_columns:
+            main: [a, b]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf a.first = 0x1_0000_0000_0000_0000_0000_0000
+        transition_constraints:
+            enf a' = a * 2^100
+            enf b' = b * (0 - 1)";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        assert_eq!(ir.field(), Field::F128);
+
+        // constants which don't fit into 64 bits are elements of the 128-bit field.
+        let (_, _, value) = ir.main_boundary_constraints()[0];
+        assert_eq!(value, &AssertionValue::Single(BoundaryExpr::Const(1 << 96)));
+        let graph = ir.transition_graph();
+        assert!(graph
+            .nodes()
+            .any(|(_, node)| node.op() == &Operation::Const(1 << 100)));
+        // -1 is folded into the negation of its additive inverse.
+        let Operation::Add(_, rhs) = graph.node(&ir.main_transition_constraints()[1]).op() else {
+            panic!("expected the constraint to be an addition");
+        };
+        assert_eq!(graph.node(rhs).op(), &Operation::MainTraceRow(1, 0));
+    }
+
+    #[test]
+    fn field_arithmetic() {
+        let field = Field::F128;
+        let p = field.modulus();
+        assert_eq!(field.mul(p - 1, p - 1), 1);
+        // 2^128 = 45 * 2^40 - 1 modulo the 128-bit field.
+        assert_eq!(field.mul(1 << 64, 1 << 64), 45 * (1 << 40) - 1);
+        assert_eq!(field.exp(2, 128), 45 * (1 << 40) - 1);
+        assert_eq!(field.add(p - 1, 2), 1);
+        assert_eq!(field.neg(1), p - 1);
+
+        let field = Field::F64;
+        assert_eq!(field.exp(2, 64), u32::MAX as u128);
+    }
+
+    #[test]
+    fn default_field() {
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' = clk + 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        assert_eq!(ir.field(), Field::F64);
+    }
+
+    #[test]
+    fn err_constants_not_in_field() {
+        let source = "
+        field: f62
+        constants:
+            A: 4611624995532046337
+            B: [1, 4611624995532046338]
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        periodic_columns:
+            k: [1, 4611624995532046337]
+        boundary_constraints:
+            let x = 4611624995532046337
+            enf clk.first = 4611624995532046337
+        transition_constraints:
+            enf clk' = clk + 4611624995532046337
+            enf clk' = sum([clk, 4611624995532046337])";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid constants were accepted");
+        assert_eq!(errors.len(), 7);
+        assert!(matches!(errors[0], SemanticError::InvalidConstant(..)));
+        assert_eq!(
+            errors[0].message(),
+            "Value 4611624995532046337 is not an element of field f62, whose modulus is 4611624995532046337"
+        );
+        assert!(errors
+            .iter()
+            .all(|err| matches!(err, SemanticError::InvalidConstant(..))));
+    }
+
+    #[test]
+    fn err_constant_not_in_default_field() {
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' = clk + 18446744069414584321";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid constant was accepted");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], SemanticError::InvalidConstant(..)));
+    }
+
+    #[test]
+    fn modulus_literals() {
+        let source = "
+        field: f62
+        constants:
+            A: p - 1
+            B: [p - 0, 0xff_ff]
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        periodic_columns:
+            k: [p - 0b10, 1]
+        boundary_constraints:
+            enf clk.first = A + B[0] * B[1]
+        transition_constraints:
+            enf clk' = clk + k";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+
+        // the literals are resolved against the modulus of the declared field, so p - 0 is zero.
+        let p = Field::F62.modulus();
+        let expected = BoundaryExpr::Add(
+            Box::new(BoundaryExpr::Const(p - 1)),
+            Box::new(BoundaryExpr::Mul(
+                Box::new(BoundaryExpr::Const(0)),
+                Box::new(BoundaryExpr::Const(0xffff)),
+            )),
+        );
+        assert_eq!(
+            ir.main_boundary_constraints(),
+            vec![(
+                0,
+                AssertionRows::Single(0),
+                &AssertionValue::Single(expected)
+            )]
+        );
+        assert_eq!(ir.periodic_columns(), &vec![vec![p - 2, 1]]);
+
+        // the literals relative to the modulus of the 128-bit field don't fit into 64 bits.
+        let source = "
+        field: f128
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        periodic_columns:
+            k: [p - 1, 1]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' = clk + k";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        let p = Field::F128.modulus();
+        assert_eq!(ir.periodic_columns(), &vec![vec![p - 1, 1]]);
+    }
+
+    #[test]
+    fn err_modulus_literals() {
+        let source = "
+        field: f62
+        constants:
+            A: p - 0xffff_ffff_ffff_ffff
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = A
+        transition_constraints:
+            enf clk' = clk + 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid constant was accepted");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message(),
+            "Value p - 18446744073709551615 is not an element of field f62, whose modulus is 4611624995532046337"
+        );
+    }
+
+    #[test]
+    fn err_invalid_field() {
+        let source = "
+        field: f32
+        field: f64
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' = clk + 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid field was accepted");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], SemanticError::InvalidField(..)));
+        assert_eq!(errors[0].message(), "The field was declared more than once");
+
+        let source = source.replace("field: f64", "");
+        let parsed = parse(&source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid field was accepted");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message(), "Field f32 is not supported");
+    }
+
+    #[test]
+    fn variables() {
+        let source = "
+        trace_columns:
+            main: [a, b]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            let x = stack_inputs[0] + 1
+            enf a.first = x * x
+        transition_constraints:
+            let total = a + b
+            enf a' = total * total
+            enf b' = total";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+
+        // the values of boundary variables are inlined into the constraint expressions.
+        let x = BoundaryExpr::Add(
+            Box::new(BoundaryExpr::PubInput("stack_inputs".to_string(), 0)),
+            Box::new(BoundaryExpr::Const(1)),
+        );
+        let expected = BoundaryExpr::Mul(Box::new(x.clone()), Box::new(x));
+        assert_eq!(
+            ir.main_boundary_constraints(),
+            vec![(
+                0,
+                AssertionRows::Single(0),
+                &AssertionValue::Single(expected)
+            )]
+        );
+
+        // transition variables are represented by a single node shared by all constraints.
+        let (name, sum) = &ir.transition_variables()[0];
+        assert_eq!(name, "total");
+        let graph = ir.transition_graph();
+        let Operation::Add(_, lhs) = graph.node(&ir.main_transition_constraints()[0]).op() else {
+            panic!("expected the constraint to be a subtraction");
+        };
+        let Operation::Neg(product) = graph.node(lhs).op() else {
+            panic!("expected the right-hand side to be negated");
+        };
+        assert_eq!(graph.node(product).op(), &Operation::Mul(*sum, *sum));
+        assert_eq!(ir.main_degrees()[0].base(), 2);
+    }
+
+    #[test]
+    fn err_variable_out_of_scope() {
+        // variables can only be referenced in the section in which they were declared, and only
+        // after their declaration.
+        let source = "
+        trace_columns:
+            main: [a]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            let x = 1
+            enf a.first = x
+        transition_constraints:
+            enf a' = a + y
+            let y = 1
+            enf a' = a + x";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Out-of-scope variables were accepted");
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .all(|err| matches!(err, SemanticError::InvalidIdentifier(..))));
+    }
+
+    #[test]
+    fn err_variable_duplicate_identifier() {
+        let source = "
+        trace_columns:
+            main: [a]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf a.first = 0
+        transition_constraints:
+            let x = a + 1
+            let x = a + 2
+            let a = 1
+            enf a' = x";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Duplicate variables were accepted");
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .all(|err| matches!(err, SemanticError::DuplicateIdentifier(..))));
+    }
+
+    #[test]
+    fn trace_column_groups() {
+        let source = "
+        trace_columns:
+            main: [clk, state[3]]
+            aux: [p[2], q]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf state[2].first = 0
+            enf p[1].last = 1
+        transition_constraints:
+            enf state[1]' = state[0] + clk
+            enf q' = p[1] * state[2]";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+
+        // the columns of a group follow the columns declared before it in the same trace.
+        assert_eq!(ir.main_trace_width(), 4);
+        assert_eq!(ir.aux_trace_width(), 3);
+        assert_eq!(
+            ir.main_boundary_constraints(),
+            vec![(
+                3,
+                AssertionRows::Single(0),
+                &AssertionValue::Single(BoundaryExpr::Const(0))
+            )]
+        );
+        assert_eq!(
+            ir.aux_boundary_constraints(),
+            vec![(
+                1,
+                AssertionRows::Last,
+                &AssertionValue::Single(BoundaryExpr::Const(1))
+            )]
+        );
+
+        let graph = ir.transition_graph();
+        let Operation::Add(next, _) = graph.node(&ir.main_transition_constraints()[0]).op() else {
+            panic!("expected the constraint to be a subtraction");
+        };
+        assert_eq!(graph.node(next).op(), &Operation::MainTraceRow(2, 1));
+        let Operation::Add(next, _) = graph.node(&ir.aux_transition_constraints()[0]).op() else {
+            panic!("expected the constraint to be a subtraction");
+        };
+        assert_eq!(graph.node(next).op(), &Operation::AuxTraceRow(2, 1));
+    }
+
+    #[test]
+    fn err_trace_column_group_index_out_of_range() {
+        let source = "
+        trace_columns:
+            main: [clk, state[3]]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf state[3].first = 0
+        transition_constraints:
+            enf state[0]' = state[5] + clk";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid indices were accepted");
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .all(|err| matches!(err, SemanticError::IndexOutOfRange(..))));
+    }
+
+    #[test]
+    fn err_trace_column_group_invalid_usage() {
+        // groups can only be bound to vectors without an index and single columns can't be
+        // indexed.
+        let source = "
+        trace_columns:
+            main: [clk, state[3]]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf state.first = 0
+        transition_constraints:
+            enf clk[0]' = state + 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid usages were accepted");
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .all(|err| matches!(err, SemanticError::InvalidUsage(..))));
+    }
+
+    #[test]
+    fn list_folding() {
+        let source = "
+        constants:
+            A: [2, 3]
+        trace_columns:
+            main: [clk, a[2], b[2]]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' = sum([x * y for (x, y) in (a, A)])
+            enf clk' = prod(b)";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        let graph = ir.transition_graph();
+
+        // the folded lists are expanded into the same nodes as the equivalent expressions.
+        let Operation::Add(_, sum) = graph.node(&ir.main_transition_constraints()[0]).op() else {
+            panic!("expected the constraint to be a subtraction");
+        };
+        let Operation::Neg(sum) = graph.node(sum).op() else {
+            panic!("expected the right-hand side to be negated");
+        };
+        let Operation::Add(lhs, rhs) = graph.node(sum).op() else {
+            panic!("expected the right-hand side to be a sum");
+        };
+        let Operation::Mul(a0, two) = graph.node(lhs).op() else {
+            panic!("expected the first element to be a product");
+        };
+        assert_eq!(graph.node(a0).op(), &Operation::MainTraceRow(1, 0));
+        assert_eq!(graph.node(two).op(), &Operation::Const(2));
+        let Operation::Mul(a1, three) = graph.node(rhs).op() else {
+            panic!("expected the second element to be a product");
+        };
+        assert_eq!(graph.node(a1).op(), &Operation::MainTraceRow(2, 0));
+        assert_eq!(graph.node(three).op(), &Operation::Const(3));
+
+        assert_eq!(ir.main_degrees()[0].base(), 1);
+        assert_eq!(ir.main_degrees()[1].base(), 2);
+    }
+
+    #[test]
+    fn constraint_comprehension() {
+        let source = "
+        trace_columns:
+            main: [clk, state[3]]
+            aux: [p[3]]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf x' = x + clk for x in state
+            enf y' = y * x for (x, y) in (state, p)";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        let graph = ir.transition_graph();
+
+        // one constraint is enforced for each element of the iterables, in order.
+        assert_eq!(ir.main_transition_constraints().len(), 3);
+        assert_eq!(ir.aux_transition_constraints().len(), 3);
+        for (idx, constraint) in ir.aux_transition_constraints().iter().enumerate() {
+            let Operation::Add(next, _) = graph.node(constraint).op() else {
+                panic!("expected the constraint to be a subtraction");
+            };
+            assert_eq!(graph.node(next).op(), &Operation::AuxTraceRow(idx, 1));
+        }
+    }
+
+    #[test]
+    fn err_list_comprehension_iterables_of_different_lengths() {
+        let source = "
+        trace_columns:
+            main: [clk, a[2], b[3]]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' = sum([x * y for (x, y) in (a, b)])
+            enf x' = y for (x, y) in (b, a)";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid iterables were accepted");
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .all(|err| matches!(err, SemanticError::InvalidListComprehension(..))));
+    }
+
+    #[test]
+    fn err_list_comprehension_invalid_bindings() {
+        // bindings can't shadow declared identifiers and can only be bound to trace column groups
+        // and vector constants.
+        let source = "
+        trace_columns:
+            main: [clk, a[2]]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' = sum([clk for clk in a])
+            enf x' = x for x in clk";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid bindings were accepted");
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], SemanticError::DuplicateIdentifier(..)));
+        assert!(matches!(errors[1], SemanticError::InvalidUsage(..)));
+    }
+
+    #[test]
+    fn evaluator_functions() {
+        let source = "
+        ev is_binary([x]):
+            enf x^2 = x
+        ev running_product([a[2]], [p]):
+            let r = a[0] + a[1] + $rand[0]
+            enf p' = p * r
+        trace_columns:
+            main: [s, b[2]]
+            aux: [q]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf s.first = 0
+        transition_constraints:
+            enf is_binary([s])
+            enf is_binary([b[1]])
+            enf running_product([b], [q])";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        let graph = ir.transition_graph();
+
+        // the constraints of the evaluators are inlined with their parameters bound to the
+        // arguments of each call.
+        assert_eq!(ir.main_transition_constraints().len(), 2);
+        for (constraint, column) in ir.main_transition_constraints().iter().zip([0, 2]) {
+            let Operation::Add(square, _) = graph.node(constraint).op() else {
+                panic!("expected the constraint to be a subtraction");
+            };
+            let Operation::Exp(base, 2) = graph.node(square).op() else {
+                panic!("expected the left-hand side to be a square");
+            };
+            assert_eq!(graph.node(base).op(), &Operation::MainTraceRow(column, 0));
+        }
+
+        assert_eq!(ir.aux_transition_constraints().len(), 1);
+        let Operation::Add(next, _) = graph.node(&ir.aux_transition_constraints()[0]).op() else {
+            panic!("expected the constraint to be a subtraction");
+        };
+        assert_eq!(graph.node(next).op(), &Operation::AuxTraceRow(0, 1));
+        assert_eq!(ir.aux_degrees()[0].base(), 2);
+    }
+
+    #[test]
+    fn err_evaluator_invalid_arguments() {
+        let source = "
+        ev is_binary([x]):
+            enf x^2 = x
+        ev is_pair([y[2]]):
+            enf y[0] = y[1]
+        trace_columns:
+            main: [s, t, b[2]]
+            aux: [q]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf s.first = 0
+        transition_constraints:
+            enf is_binary([s, t])
+            enf is_binary([q])
+            enf is_pair([t, b[1]])";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid arguments were accepted");
+        assert_eq!(errors.len(), 3);
+        assert!(errors
+            .iter()
+            .all(|err| matches!(err, SemanticError::InvalidEvaluatorCall(..))));
+    }
+
+    #[test]
+    fn err_evaluator_scope() {
+        // the trace columns of the AIR can't be referenced in the body of an evaluator, and
+        // evaluators can't be called recursively.
+        let source = "
+        ev uses_column([x]):
+            enf x' = s
+        ev recursive([x]):
+            enf recursive([x])
+        trace_columns:
+            main: [s]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf s.first = 0
+        transition_constraints:
+            enf uses_column([s])
+            enf recursive([s])";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid evaluators were accepted");
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], SemanticError::InvalidIdentifier(..)));
+        assert!(matches!(errors[1], SemanticError::InvalidEvaluatorCall(..)));
+    }
+
+    #[test]
+    fn selectors() {
+        let source = "
+        trace_columns:
+            main: [clk, s]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' = clk + 1 when s";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        let graph = ir.transition_graph();
+
+        // the constraint is multiplied by its selector, which increases its degree.
+        let Operation::Mul(selector, _) = graph.node(&ir.main_transition_constraints()[0]).op()
+        else {
+            panic!("expected the constraint to be multiplied by the selector");
+        };
+        assert_eq!(graph.node(selector).op(), &Operation::MainTraceRow(1, 0));
+        assert_eq!(ir.main_degrees()[0].base(), 2);
+    }
+
+    #[test]
+    fn match_constraints() {
+        let source = "
+        trace_columns:
+            main: [clk, s[3]]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf match {
+                case s[0]: clk' = clk + 1,
+                case s[1] * s[2]: clk' = clk
+            }
+            enf match exclusive {
+                case s[0]: clk' = 0,
+                case s[1]: clk' = 1,
+                case s[2]: clk' = clk
+            }";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        let graph = ir.transition_graph();
+
+        // the exclusive match adds a binary constraint for each of its 3 selectors and a
+        // mutual exclusion constraint for each of the 3 pairs of selectors.
+        let degrees: Vec<usize> = ir.main_degrees().iter().map(|d| d.base()).collect();
+        assert_eq!(degrees, vec![2, 3, 2, 2, 2, 2, 2, 2, 2, 2, 2]);
+
+        let Operation::Mul(s0, s1) = graph.node(&ir.main_transition_constraints()[6]).op() else {
+            panic!("expected the selectors to be multiplied");
+        };
+        assert_eq!(graph.node(s0).op(), &Operation::MainTraceRow(1, 0));
+        assert_eq!(graph.node(s1).op(), &Operation::MainTraceRow(2, 0));
+    }
+
+    /// Evaluates the subgraph with the specified tip over the provided current and next rows of
+    /// the main trace, modulo the field.
+    fn evaluate(graph: &AlgebraicGraph, index: &NodeIndex, current: &[u64], next: &[u64]) -> u64 {
+        let p = Field::F64.modulus();
+        let value = match graph.node(index).op() {
+            Operation::Const(value) => *value % p,
+            Operation::MainTraceRow(col, 0) => current[*col] as u128,
+            Operation::MainTraceRow(col, 1) => next[*col] as u128,
+            Operation::Neg(value) => p - evaluate(graph, value, current, next) as u128,
+            Operation::Add(lhs, rhs) => {
+                evaluate(graph, lhs, current, next) as u128
+                    + evaluate(graph, rhs, current, next) as u128
+            }
+            Operation::Mul(lhs, rhs) => {
+                evaluate(graph, lhs, current, next) as u128
+                    * evaluate(graph, rhs, current, next) as u128
+            }
+            Operation::Exp(base, power) => {
+                let base = evaluate(graph, base, current, next) as u128;
+                (0..*power).fold(1, |acc, _| acc * base % p)
+            }
+            op => panic!("unexpected operation {:?}", op),
+        };
+        (value % p) as u64
+    }
+
+    #[test]
+    fn optimizer_applies_algebraic_identities() {
+        let source = "
+        trace_columns:
+            main: [a, b, c]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf a.first = 0
+        transition_constraints:
+            enf a' = a * 1 + 0
+            enf b' = 0 - (0 - b^1)
+            enf c' = c * 2^3
+            enf a * 0 = 0";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        let graph = ir.transition_graph();
+
+        // the constraint which is always satisfied is removed.
+        let constraints = ir.main_transition_constraints();
+        assert_eq!(constraints.len(), 3);
+        // a' - a, b' - b and c' - c * 8 are represented with 4, 4 and 6 nodes, and nodes which were
+        // replaced, such as the constants 0 and 1, are removed.
+        assert_eq!(graph.num_nodes(), 14);
+        assert!(graph
+            .nodes()
+            .all(|(_, node)| !matches!(node.op(), Operation::Const(0) | Operation::Const(1))));
+
+        let current = [3, 5, 7];
+        let next = [11, 13, 17];
+        let expected = [11 - 3, 13 - 5, Field::F64.modulus() as u64 - (7 * 8 - 17)];
+        for (constraint, expected) in constraints.iter().zip(expected) {
+            assert_eq!(evaluate(graph, constraint, &current, &next), expected);
+        }
+        assert_eq!(ir.main_degrees()[2].base(), 1);
+    }
+
+    #[test]
+    fn optimizer_folds_constants() {
+        let source = "
+        trace_columns:
+            main: [a, b, c]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf a.first = 0
+        transition_constraints:
+            let minus_one = 0 - 1
+            enf a' = a * minus_one
+            enf b' = b + (18446744069414584320 + 1)
+            enf c' = c * minus_one * minus_one + 2^64";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+        let graph = ir.transition_graph();
+
+        // a' + a, b' - b and c' - (c + 2^32 - 1) are represented with 3, 4 and 6 nodes.
+        assert_eq!(graph.num_nodes(), 13);
+        // the constant folded from 2^64 is reduced modulo the field.
+        assert!(graph
+            .nodes()
+            .any(|(_, node)| node.op() == &Operation::Const(u32::MAX as u128)));
+        // the variable is no longer referenced by the constraints, so it is removed.
+        assert!(ir.transition_variables().is_empty());
+
+        let current = [3, 5, 7];
+        let next = [11, 13, 17];
+        let expected = [
+            11 + 3,
+            13 - 5,
+            Field::F64.modulus() as u64 - (7 + u32::MAX as u64 - 17),
+        ];
+        for (constraint, expected) in ir.main_transition_constraints().iter().zip(expected) {
+            assert_eq!(evaluate(graph, constraint, &current, &next), expected);
+        }
+    }
+
+    #[test]
+    fn err_optimizer_constant_constraints() {
+        // a constraint which is equal to a non-zero constant can never be satisfied.
+        let source = "
+        trace_columns:
+            main: [a]
+            aux: [p]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf a.first = 0
+        transition_constraints:
+            enf a' = a + 1
+            enf a * 0 = 1
+            enf p * 0 = 0 - 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let errors =
+            AirIR::from_source(&parsed).expect_err("Unsatisfiable constraints were accepted");
+        let messages: Vec<&str> = errors.iter().map(|e| e.message()).collect();
+        let p = Field::F64.modulus();
+        assert_eq!(
+            messages,
+            vec![
+                format!(
+                    "The transition constraint is never satisfied, since it is equal to the non-zero constant {}",
+                    p - 1
+                ),
+                "The transition constraint is never satisfied, since it is equal to the non-zero constant 1"
+                    .to_string(),
+            ]
+        );
+
+        // at least one constraint of the main trace must remain once the constraints which are
+        // always satisfied are removed.
+        let source = "
+        trace_columns:
+            main: [a]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf a.first = 0
+        transition_constraints:
+            enf a - a = 0
+            enf a * 0 = 0";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let errors = AirIR::from_source(&parsed).expect_err("Empty constraints were accepted");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], SemanticError::InvalidConstraint(..)));
+        assert_eq!(
+            errors[0].message(),
+            "All of the transition constraints of the main trace are always satisfied, but at least one transition constraint of the main trace is required"
+        );
+
+        // the same applies to the constraints of the auxiliary trace.
+        let source = "
+        trace_columns:
+            main: [a]
+            aux: [p]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf a.first = 0
+            enf p.first = 1
+        transition_constraints:
+            enf a' = a + 1
+            enf p - p = 0
+            enf p' + (-p') = 0 * $rand[0]";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let errors = AirIR::from_source(&parsed).expect_err("Empty constraints were accepted");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], SemanticError::InvalidConstraint(..)));
+        assert_eq!(
+            errors[0].message(),
+            "All of the transition constraints of the auxiliary trace are always satisfied, but at least one transition constraint of the auxiliary trace is required"
+        );
+    }
+
+    #[test]
+    fn commutative_operations_share_nodes() {
+        let source = "
+        trace_columns:
+            main: [a, b, c]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf a.first = 0
+        transition_constraints:
+            enf a' = a + b
+            enf b' = b + a
+            enf c' = a * b + c
+            enf c' = c + b * a";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+
+        // a', a, b, a + b, -(a + b), a' - (a + b), b', b' - (a + b), c', c, a * b, a * b + c,
+        // -(a * b + c) and c' - (a * b + c): the last constraint is the same as the third one.
+        assert_eq!(ir.transition_graph().num_nodes(), 14);
+        let constraints = ir.main_transition_constraints();
+        assert_eq!(constraints[2], constraints[3]);
+    }
+
+    /// Returns a program with the provided root module and library modules.
+    fn build_program(source: &str, libraries: &[&str]) -> Program {
+        let root = parse(source).expect("Parsing failed");
+        let libraries = libraries
+            .iter()
+            .map(|library| {
+                let library = parse_library(library).expect("Parsing failed");
+                (library.name.name().to_string(), library)
+            })
+            .collect();
+        Program::new(root, libraries)
+    }
+
+    #[test]
+    fn module_imports() {
+        let bitwise = "
+        mod bitwise
+        constants:
+            ONE: 1
+        ev is_binary([x]):
+            enf x^2 = x";
+        // the evaluator of the counter module references a constant it imports, which is not
+        // imported by the root module.
+        let counter = "
+        mod counter
+        use bitwise::ONE
+        ev increment([x]):
+            enf x' = x + ONE";
+        let source = "
+        use bitwise::is_binary
+        use counter::*
+        trace_columns:
+            main: [s, t]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf s.first = 0
+        transition_constraints:
+            enf is_binary([s])
+            enf increment([t])";
+
+        let program = build_program(source, &[bitwise, counter]);
+        let ir = AirIR::from_program(&program).expect("IR generation failed");
+        assert_eq!(ir.main_transition_constraints().len(), 2);
+    }
+
+    #[test]
+    fn err_module_invalid_imports() {
+        let bitwise = "
+        mod bitwise
+        constants:
+            ONE: 1";
+        let source = "
+        use bitwise::TWO
+        use bitwise::*
+        use undeclared::*
+        constants:
+            ONE: 2
+        trace_columns:
+            main: [s]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf s.first = 0
+        transition_constraints:
+            enf s' = s + ONE";
+
+        let program = build_program(source, &[bitwise]);
+        let errors = AirIR::from_program(&program).expect_err("Invalid imports were accepted");
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(errors[0], SemanticError::InvalidImport(..)));
+        assert!(matches!(errors[1], SemanticError::InvalidImport(..)));
+        assert!(matches!(errors[2], SemanticError::DuplicateIdentifier(..)));
+    }
+
+    #[test]
+    fn err_module_evaluator_scope() {
+        // the body of an evaluator declared in a library module can't reference the declarations
+        // of the root module.
+        let library = "
+        mod library
+        ev uses_constant([x]):
+            enf x' = x + ONE";
+        let source = "
+        use library::*
+        constants:
+            ONE: 1
+        trace_columns:
+            main: [s]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf s.first = 0
+        transition_constraints:
+            enf uses_constant([s])";
+
+        let program = build_program(source, &[library]);
+        let errors = AirIR::from_program(&program).expect_err("Invalid evaluator was accepted");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], SemanticError::InvalidIdentifier(..)));
+    }
+
+    #[test]
+    fn err_span_points_to_identifier() {
+        let source = "
+        trace_columns:
+            main: [ctx]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf ctx.first = 0
+        transition_constraints:
+            enf ctx' = clk + 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Undeclared identifier was accepted");
+        assert_eq!(errors.len(), 1);
+        let err = &errors[0];
+        assert!(matches!(err, SemanticError::InvalidIdentifier(..)));
+        let span = err.span().expect("Error has no span");
+        assert_eq!(&source[span.start()..span.end()], "clk");
+        let location = span.start_location(source);
+        assert_eq!((location.line(), location.column()), (9, 24));
+    }
+
+    #[test]
+    fn err_diagnostic_has_code_and_label() {
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        transition_constraints:
+            enf clk' = clk + 1
+        boundary_constraints:
+            enf clk.first = 0
+            enf clk.first = 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let diagnostic = AirIR::from_source(&parsed)
+            .expect_err("Duplicate boundary constraint was accepted")[0]
+            .to_diagnostic();
+        assert_eq!(diagnostic.code(), Some("E0104"));
+        let span = diagnostic.labels()[0].span();
+        assert_eq!(&source[span.start()..span.end()], "enf clk.first = 1");
+        assert_eq!(diagnostic.notes().len(), 1);
+    }
+
+    #[test]
+    fn err_all_semantic_errors_reported() {
+        let source = "
+        trace_columns:
+            main: [clk, clk]
+        public_inputs:
+            stack_inputs: [16]
+        periodic_columns:
+            k0: [1, 0, 1]
+        boundary_constraints:
+            enf clk.first = stack_inputs[16]
+        transition_constraints:
+            enf clk' = clk + k0
+            enf ctx' = ctx + 1";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid source was accepted");
+        assert_eq!(errors.len(), 4);
+        assert!(matches!(errors[0], SemanticError::DuplicateIdentifier(..)));
+        assert!(matches!(
+            errors[1],
+            SemanticError::InvalidPeriodicColumn(..)
+        ));
+        assert!(matches!(errors[2], SemanticError::IndexOutOfRange(..)));
+        assert!(matches!(errors[3], SemanticError::InvalidIdentifier(..)));
+    }
+
+    #[test]
+    fn public_inputs_in_transition_constraints() {
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            program_hash: [4]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            let h = program_hash[3]
+            enf clk' = clk * h + program_hash[3]^2";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+
+        // public inputs are constant over the trace, so they don't increase the degree.
+        assert_eq!(ir.main_degrees()[0].base(), 1);
+
+        // both references to the public input share the same node.
+        let graph = ir.transition_graph();
+        let public_inputs: Vec<&Operation> = graph
+            .nodes()
+            .map(|(_, node)| node.op())
+            .filter(|op| matches!(op, Operation::PublicInput(..)))
+            .collect();
+        assert_eq!(
+            public_inputs,
+            vec![&Operation::PublicInput("program_hash".to_string(), 3)]
+        );
+    }
+
+    #[test]
+    fn err_public_inputs_in_transition_constraints() {
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            program_hash: [4]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' = clk + program_hash[4]
+            enf clk' = clk + program_hash";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let errors = AirIR::from_source(&parsed).expect_err("Invalid public inputs were accepted");
+        let messages: Vec<&str> = errors.iter().map(|e| e.message()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Out-of-range index 4 in public input program_hash of length 4",
+                "Identifier program_hash was declared as a PublicInput not as a trace column",
+            ]
+        );
+    }
+
+    #[test]
+    fn err_public_inputs_empty_or_omitted() {
+        // if public inputs are empty, an error should be returned at parser level.
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+        transition_constraints:
+            enf clk' = clk + 1
+        boundary_constraints:
+            enf clk.first = 0";
+
+        assert!(parse(source).is_err());
+
+        // if public inputs are omitted, an error should be returned at IR level.
+        let source = "
+        trace_columns:
+            main: [clk]
+        transition_constraints:
+            enf clk' = clk + 1
+        boundary_constraints:
+            enf clk.first = 0";
+
+        let parsed = parse(source).expect("Parsing failed");
+        let result = AirIR::from_source(&parsed);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn err_trace_cols_omitted() {
+        // returns an error if trace columns declaration is missing
+        let source = "
+        public_inputs:
+            stack_inputs: [16]
+        transition_constraints:
+            enf clk' = clk + 1
+        boundary_constraints:
+            enf clk.first = 0";
+
+        let parsed = parse(source).expect("Parsing failed");
+
+        let result = AirIR::from_source(&parsed);
+
+        // this fails before the check for missing trace columns declaration since the clk column
+        // used in constraints is not declared.
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn op_mul() {
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk' * clk = 1";
+        let parsed = parse(source).expect("Parsing failed");
+
+        let result = AirIR::from_source(&parsed);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn op_exp() {
+        let source = "
+        trace_columns:
+            main: [clk]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf clk.first = 0
+        transition_constraints:
+            enf clk'^2 - clk = 1";
+        let parsed = parse(source).expect("Parsing failed");
+
+        let result = AirIR::from_source(&parsed);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn op_neg() {
+        let source = "
+        trace_columns:
+            main: [a, b]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf a.first = -1
+        transition_constraints:
+            enf a' = -a
+            enf b' = -b^2 * -a";
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+
+        let expected = BoundaryExpr::Neg(Box::new(BoundaryExpr::Const(1)));
+        assert_eq!(
+            ir.main_boundary_constraints(),
+            vec![(
+                0,
+                AssertionRows::Single(0),
+                &AssertionValue::Single(expected)
+            )]
+        );
+
+        // the negation of b^2 is multiplied by the negation of a, so the second constraint is
+        // b' - b^2 * a.
+        let current = [3, 5];
+        let next = [11, 13];
+        let expected = [11 + 3, Field::F64.modulus() as u64 - (25 * 3 - 13)];
+        let graph = ir.transition_graph();
+        for (constraint, expected) in ir.main_transition_constraints().iter().zip(expected) {
+            assert_eq!(evaluate(graph, constraint, &current, &next), expected);
+        }
+        assert_eq!(ir.main_degrees()[1].base(), 3);
+    }
+
+    #[test]
+    fn boundary_division() {
+        let source = "
+        constants:
+            A: 2
+        trace_columns:
+            main: [a, b]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            enf a.first = stack_inputs[0] / A
+            enf b.first = inv(stack_inputs[1] + 1)
+        transition_constraints:
+            enf a' = a + b";
+        let parsed = parse(source).expect("Parsing failed");
+        let ir = AirIR::from_source(&parsed).expect("IR generation failed");
+
+        // a division is built as the product of the numerator and the inverse of the denominator.
+        let a = BoundaryExpr::Mul(
+            Box::new(BoundaryExpr::PubInput("stack_inputs".to_string(), 0)),
+            Box::new(BoundaryExpr::Inv(Box::new(BoundaryExpr::Const(2)))),
+        );
+        let b = BoundaryExpr::Inv(Box::new(BoundaryExpr::Add(
+            Box::new(BoundaryExpr::PubInput("stack_inputs".to_string(), 1)),
+            Box::new(BoundaryExpr::Const(1)),
+        )));
+        assert_eq!(
+            ir.main_boundary_constraints(),
+            vec![
+                (0, AssertionRows::Single(0), &AssertionValue::Single(a)),
+                (1, AssertionRows::Single(0), &AssertionValue::Single(b)),
+            ]
+        );
+    }
+
+    #[test]
+    fn err_division_by_zero() {
+        let source = "
+        constants:
+            A: 1
+        trace_columns:
+            main: [a, b, c, d]
+        public_inputs:
+            stack_inputs: [16]
+        boundary_constraints:
+            let x = A - 1
+            enf a.first = stack_inputs[0] / x
+            enf b.first = inv(2^3 - 8)
+            enf c.first = 1 / (18446744069414584320 + 1)
+            enf d.first = 1 / stack_inputs[0]
+        transition_constraints:
+            enf a' = a + b";
+        let parsed = parse(source).expect("Parsing failed");
+
+        // the constant denominators are evaluated in the field of the AIR, so (p - 1) + 1 is zero,
+        // while the value of a public input is unknown.
+        let errors = AirIR::from_source(&parsed).expect_err("IR generation should have failed");
+        let messages: Vec<&str> = errors.iter().map(|err| err.message()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Cannot divide by a denominator which is a constant equal to zero",
+                "Cannot compute the inverse of a constant equal to zero",
+                "Cannot divide by a denominator which is a constant equal to zero",
+            ]
+        );
+        assert!(matches!(errors[0], SemanticError::DivisionByZero(..)));
     }
 }
@@ -0,0 +1,136 @@
+use super::{BTreeMap, SemanticError, SymbolTable};
+use parser::ast::{self, Identifier};
+use std::collections::BTreeSet;
+
+// IMPORTS
+// ================================================================================================
+
+/// Adds the items imported by the provided declaration to the module which is being processed.
+/// The declarations of the imported library module are processed the first time it is imported,
+/// and its items are then kept in its own namespace in the symbol table.
+///
+/// # Errors
+/// Returns an error if:
+/// - The imported module was not loaded, or it imports itself.
+/// - An imported item is not a constant or an evaluator declared in the imported module.
+/// - The name of an imported item was already declared in the module which is being processed.
+/// - The declarations of the imported module are invalid.
+pub(super) fn insert_import(
+    symbol_table: &mut SymbolTable,
+    libraries: &BTreeMap<String, ast::Library>,
+    import: &ast::Import,
+) -> Result<(), Vec<SemanticError>> {
+    let module = import.module();
+    let library = libraries.get(module.name()).ok_or_else(|| {
+        vec![SemanticError::InvalidImport(
+            format!("Module {} was not loaded", module),
+            module.span(),
+        )]
+    })?;
+    if symbol_table.is_processing_module(module.name()) {
+        return Err(vec![SemanticError::InvalidImport(
+            format!("Module {} cannot be imported into itself", module),
+            module.span(),
+        )]);
+    }
+
+    let mut errors = Vec::new();
+    if !symbol_table.has_module(module.name()) {
+        if let Err(errs) = insert_library(symbol_table, libraries, library) {
+            errors.extend(errs);
+        }
+    }
+
+    // only the items declared in the module are imported, and not the items it imports itself.
+    let declared_items = get_declared_items(library);
+    let items = match import.items() {
+        ast::ImportItems::All => declared_items
+            .into_iter()
+            .map(|item| Identifier::new(item.name().to_string(), import.span()))
+            .collect(),
+        ast::ImportItems::Single(item) => {
+            if declared_items.contains(&item) {
+                vec![item.clone()]
+            } else {
+                errors.push(SemanticError::InvalidImport(
+                    format!(
+                        "Module {} does not declare a constant or an evaluator named {}",
+                        module, item
+                    ),
+                    item.span(),
+                ));
+                vec![]
+            }
+        }
+    };
+    for item in items {
+        if let Err(err) = symbol_table.import_item(module, &item) {
+            errors.push(err);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Processes the declarations of the provided library module, including its own imports, into a
+/// new namespace of the symbol table.
+///
+/// # Errors
+/// Returns the errors for all invalid declarations and imports of the module.
+fn insert_library(
+    symbol_table: &mut SymbolTable,
+    libraries: &BTreeMap<String, ast::Library>,
+    library: &ast::Library,
+) -> Result<(), Vec<SemanticError>> {
+    let mut errors = Vec::new();
+
+    symbol_table.enter_module(library.name.name());
+    for section in library.sections.iter() {
+        match section {
+            ast::LibrarySection::Import(import) => {
+                if let Err(errs) = insert_import(symbol_table, libraries, import) {
+                    errors.extend(errs);
+                }
+            }
+            ast::LibrarySection::Constants(constants) => {
+                if let Err(errs) = symbol_table.insert_constants(constants) {
+                    errors.extend(errs);
+                }
+            }
+            ast::LibrarySection::EvaluatorFunction(evaluator) => {
+                // the body of the evaluator is processed when it is called.
+                if let Err(err) = symbol_table.insert_evaluator(evaluator) {
+                    errors.push(err);
+                }
+            }
+        }
+    }
+    symbol_table.exit_module();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Returns the identifiers of the constants and the evaluators declared in the library module.
+/// Names which were declared more than once are only returned once, since the duplicates are
+/// reported when the declarations of the module are processed.
+fn get_declared_items(library: &ast::Library) -> BTreeSet<&Identifier> {
+    library
+        .sections
+        .iter()
+        .flat_map(|section| match section {
+            ast::LibrarySection::Import(_) => vec![],
+            ast::LibrarySection::Constants(constants) => {
+                constants.iter().map(|constant| constant.ident()).collect()
+            }
+            ast::LibrarySection::EvaluatorFunction(evaluator) => vec![evaluator.ident()],
+        })
+        .collect()
+}
@@ -1,36 +1,81 @@
-use super::{BTreeMap, PeriodicColumns, PublicInputs, SemanticError, MIN_CYCLE_LENGTH};
-use parser::ast::{Identifier, PeriodicColumn, PublicInput};
-use std::fmt::Display;
+use super::{
+    transition_constraints::ConstraintType, BTreeMap, BoundaryExpr, Field, NodeIndex,
+    PeriodicColumns, PublicInputs, SemanticError, MIN_CYCLE_LENGTH,
+};
+use parser::ast::{
+    AuxSegment, Constant, ConstantType, EvaluatorArg, EvaluatorCall, EvaluatorFunction, Identifier,
+    Literal, MatrixAccess, PeriodicColumn, PublicInput, RandomValueAccess, RandomValues,
+    SourceSpan, TraceColumn, VectorAccess,
+};
+use std::{fmt::Display, ops::Range};
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub(super) enum IdentifierType {
+    /// an identifier for a constant, containing its value resolved to elements of the field
+    Constant(ConstantType<u128>),
     /// an identifier for a main trace column, containing its index in the main trace
     MainTraceColumn(usize),
     /// an identifier for a auxiliary trace column, containing its index in the auxiliary trace
     AuxTraceColumn(usize),
+    /// an identifier for a segment of the auxiliary trace, containing its index among the
+    /// auxiliary trace segments
+    AuxTraceSegment(usize),
+    /// an identifier for a group of main trace columns, containing the range of indices of its
+    /// columns in the main trace
+    MainTraceColumnGroup(Range<usize>),
+    /// an identifier for a group of auxiliary trace columns, containing the range of indices of its
+    /// columns in the auxiliary trace
+    AuxTraceColumnGroup(Range<usize>),
     /// an identifier for a public input, containing the size of the public input array
     PublicInput(usize),
+    /// an identifier bound to a random value, containing the index of its auxiliary trace segment
+    /// and its index in the random values of that segment.
+    RandomValue(usize, usize),
     /// an identifier for a periodic column, containing its index out of all periodic columns and
     /// its cycle length in that order.
     PeriodicColumn(usize, usize),
+    /// an identifier for a variable declared in a transition constraints section, containing the
+    /// type of the trace it is applied to and the index of the node representing its value in the
+    /// graph of transition constraints.
+    TransitionVariable(ConstraintType, NodeIndex),
+    /// an identifier for a variable declared in a boundary constraints section, containing its
+    /// value.
+    BoundaryVariable(BoundaryExpr),
+    /// an identifier for an evaluator function, containing the key of its declaration, which is
+    /// qualified by the name of the library module declaring it, e.g. `bitwise::is_binary`, for
+    /// evaluators which are not declared in the root module.
+    Evaluator(String),
 }
 
 impl Display for IdentifierType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::Constant(ConstantType::Scalar(_)) => write!(f, "ScalarConstant"),
+            Self::Constant(ConstantType::Vector(_)) => write!(f, "VectorConstant"),
+            Self::Constant(ConstantType::Matrix(_)) => write!(f, "MatrixConstant"),
             Self::PublicInput(_) => write!(f, "PublicInput"),
             Self::PeriodicColumn(_, _) => write!(f, "PeriodicColumn"),
+            Self::RandomValue(..) => write!(f, "RandomValue"),
             Self::MainTraceColumn(_) => write!(f, "MainTraceColumn"),
             Self::AuxTraceColumn(_) => write!(f, "AuxTraceColumn"),
+            Self::MainTraceColumnGroup(_) => write!(f, "MainTraceColumnGroup"),
+            Self::AuxTraceColumnGroup(_) => write!(f, "AuxTraceColumnGroup"),
+            Self::AuxTraceSegment(_) => write!(f, "AuxTraceSegment"),
+            Self::TransitionVariable(..) | Self::BoundaryVariable(_) => write!(f, "Variable"),
+            Self::Evaluator(_) => write!(f, "Evaluator"),
         }
     }
 }
 
 /// SymbolTable for identifiers to track their types and information and enforce uniqueness of
 /// identifiers.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub(super) struct SymbolTable {
-    /// A map of all declared identifiers from their name (the key) to their type.
+    /// The field over which the AIR is defined, which bounds the values of the constants.
+    field: Field,
+
+    /// A map of all identifiers declared in or imported into the module which is being processed
+    /// from their name (the key) to their type.
     identifiers: BTreeMap<String, IdentifierType>,
 
     /// A map of the Air's periodic columns using the index of the column within the declared
@@ -40,108 +85,541 @@ pub(super) struct SymbolTable {
     /// A vector of public inputs with each value as a tuple of input identifier and it's array
     /// size.
     public_inputs: PublicInputs,
+
+    /// The number of declared columns in the main execution trace.
+    main_trace_width: usize,
+
+    /// The number of declared columns in each segment of the auxiliary execution trace.
+    aux_segment_widths: Vec<usize>,
+
+    /// A map of the number of declared random values from the index of their auxiliary trace
+    /// segment, or None if the random values were not declared, in which case the random values
+    /// which are referenced are not checked.
+    random_values: Option<BTreeMap<usize, usize>>,
+
+    /// A map of the declared evaluator functions from their key to the name of the library module
+    /// declaring them, or None for the root module, and their declaration.
+    evaluators: BTreeMap<String, (Option<String>, EvaluatorFunction)>,
+
+    /// The keys of the evaluators whose bodies are being processed with this table, in the order
+    /// in which they were called. This is empty outside of evaluator bodies.
+    evaluator_calls: Vec<String>,
+
+    /// The name of the library module whose declarations are being processed, or None for the
+    /// root module.
+    module: Option<String>,
+
+    /// A map of the library modules whose declarations have been processed from their name (the
+    /// key) to the identifiers declared in or imported into them.
+    namespaces: BTreeMap<String, BTreeMap<String, IdentifierType>>,
+
+    /// The names and the identifiers of the modules which are importing the module being
+    /// processed, from the outermost to the innermost.
+    enclosing_modules: Vec<(Option<String>, BTreeMap<String, IdentifierType>)>,
 }
 
 impl SymbolTable {
+    // --- CONSTRUCTOR ----------------------------------------------------------------------------
+
+    /// Returns an empty symbol table for an AIR defined over the specified field.
+    pub(super) fn new(field: Field) -> Self {
+        Self {
+            field,
+            ..Default::default()
+        }
+    }
+
     /// Adds a declared identifier to the symbol table using the identifier as the key and the
     /// type the identifier represents as the value.
     ///
     /// # Errors
-    /// It returns an error if the identifier already existed in the table.
+    /// It returns an error if the identifier already existed in the table, in which case the
+    /// previous declaration is kept.
     fn insert_symbol(
         &mut self,
-        ident_name: &str,
+        ident: &Identifier,
         ident_type: IdentifierType,
     ) -> Result<(), SemanticError> {
-        let result = self.identifiers.insert(ident_name.to_owned(), ident_type);
-        match result {
-            Some(prev_type) => Err(SemanticError::DuplicateIdentifier(format!(
-                "Cannot declare {} as a {}, since it was already defined as a {}",
-                ident_name, ident_type, prev_type
-            ))),
-            None => Ok(()),
+        if let Some(prev_type) = self.identifiers.get(ident.name()) {
+            return Err(SemanticError::DuplicateIdentifier(
+                format!(
+                    "Cannot declare {} as a {}, since it was already defined as a {}",
+                    ident, ident_type, prev_type
+                ),
+                ident.span(),
+            ));
         }
+
+        self.identifiers.insert(ident.name().to_owned(), ident_type);
+        Ok(())
     }
 
     // --- MUTATORS -------------------------------------------------------------------------------
 
+    /// Adds all constants by their identifier names and values.
+    ///
+    /// # Errors
+    /// Returns the errors for all invalid declarations. Matrices with rows of different lengths
+    /// and constants whose values are not elements of the field are still added to the table, so
+    /// that they aren't reported again as undeclared when they are used.
+    pub(super) fn insert_constants(
+        &mut self,
+        constants: &[Constant],
+    ) -> Result<(), Vec<SemanticError>> {
+        let mut errors = Vec::new();
+        for constant in constants.iter() {
+            if let Err(err) = validate_constant(constant) {
+                errors.push(err);
+            }
+            let mut invalid_value = None;
+            let value = constant.value().map(|literal| {
+                resolve_literal(self.field, literal, constant.span(), &mut invalid_value)
+            });
+            errors.extend(invalid_value);
+            if let Err(err) = self.insert_symbol(constant.ident(), IdentifierType::Constant(value))
+            {
+                errors.push(err);
+            }
+        }
+
+        into_result(errors)
+    }
+
     /// Add all main trace columns by their identifiers and indices in the main execution trace.
+    /// Each group of columns is added by its identifier and the range of indices of its columns,
+    /// which follow the indices of the columns declared before it.
+    ///
+    /// # Errors
+    /// Returns the errors for all invalid declarations. Valid declarations are added to the table
+    /// even if other declarations are invalid.
     pub(super) fn insert_main_trace_columns(
         &mut self,
-        columns: &[Identifier],
-    ) -> Result<(), SemanticError> {
-        for (idx, Identifier(name)) in columns.iter().enumerate() {
-            self.insert_symbol(name, IdentifierType::MainTraceColumn(idx))?;
-        }
+        columns: &[TraceColumn],
+    ) -> Result<(), Vec<SemanticError>> {
+        let (width, errors) = self.insert_trace_columns(
+            columns,
+            0,
+            IdentifierType::MainTraceColumn,
+            IdentifierType::MainTraceColumnGroup,
+        );
+        self.main_trace_width = width;
 
-        Ok(())
+        into_result(errors)
     }
 
-    /// Adds all auxiliary trace columns by their identifier names and indices in the auxiliary
-    /// execution trace.
+    /// Adds the named segments of the auxiliary trace by their identifier names and indices, and
+    /// all of their columns by their identifier names and indices in the auxiliary execution
+    /// trace. The columns of all segments are indexed together, so the indices of the columns of a
+    /// segment follow the indices of the columns of the previous segments. Each group of columns
+    /// is added by its identifier and the range of indices of its columns.
+    ///
+    /// # Errors
+    /// Returns the errors for all invalid declarations. Valid declarations are added to the table
+    /// even if other declarations are invalid.
     pub(super) fn insert_aux_trace_columns(
         &mut self,
-        columns: &[Identifier],
-    ) -> Result<(), SemanticError> {
-        for (idx, Identifier(name)) in columns.iter().enumerate() {
-            self.insert_symbol(name, IdentifierType::AuxTraceColumn(idx))?;
+        segments: &[AuxSegment],
+    ) -> Result<(), Vec<SemanticError>> {
+        let mut offset = 0;
+        let mut errors = Vec::new();
+        for (index, segment) in segments.iter().enumerate() {
+            if let Some(ident) = segment.ident() {
+                if let Err(err) = self.insert_symbol(ident, IdentifierType::AuxTraceSegment(index))
+                {
+                    errors.push(err);
+                }
+            }
+            let (width, segment_errors) = self.insert_trace_columns(
+                segment.cols(),
+                offset,
+                IdentifierType::AuxTraceColumn,
+                IdentifierType::AuxTraceColumnGroup,
+            );
+            errors.extend(segment_errors);
+            self.aux_segment_widths.push(width);
+            offset += width;
         }
 
-        Ok(())
+        into_result(errors)
+    }
+
+    /// Adds the columns of a single execution trace or trace segment, whose first column is at the
+    /// specified offset in its trace, using the provided constructors to build the types of single
+    /// columns and of column groups. Returns the number of added columns along with the errors for
+    /// all invalid declarations.
+    fn insert_trace_columns(
+        &mut self,
+        columns: &[TraceColumn],
+        start: usize,
+        single_type: fn(usize) -> IdentifierType,
+        group_type: fn(Range<usize>) -> IdentifierType,
+    ) -> (usize, Vec<SemanticError>) {
+        let mut offset = start;
+        let mut errors = Vec::new();
+        for column in columns.iter() {
+            let ident_type = match column {
+                TraceColumn::Single(_) => single_type(offset),
+                TraceColumn::Group(_, size) => group_type(offset..offset + size),
+            };
+            if let Err(err) = self.insert_symbol(column.ident(), ident_type) {
+                errors.push(err);
+            }
+            offset += column.size();
+        }
+
+        (offset - start, errors)
     }
 
     /// Adds all public inputs by their identifier names and array length.
+    ///
+    /// # Errors
+    /// Returns the errors for all invalid declarations. Valid declarations are added to the table
+    /// even if other declarations are invalid.
     pub(super) fn insert_public_inputs(
         &mut self,
         public_inputs: &[PublicInput],
-    ) -> Result<(), SemanticError> {
+    ) -> Result<(), Vec<SemanticError>> {
+        let mut errors = Vec::new();
         for input in public_inputs.iter() {
-            self.insert_symbol(input.name(), IdentifierType::PublicInput(input.size()))?;
-            self.public_inputs
-                .push((input.name().to_string(), input.size()));
+            match self.insert_symbol(input.ident(), IdentifierType::PublicInput(input.size())) {
+                Ok(()) => self
+                    .public_inputs
+                    .push((input.name().to_string(), input.size())),
+                Err(err) => errors.push(err),
+            }
         }
 
-        Ok(())
+        into_result(errors)
     }
 
     /// Adds all periodic columns by their identifier names, their indices in the array of all
     /// periodic columns, and the lengths of their periodic cycles.
+    ///
+    /// # Errors
+    /// Returns the errors for all invalid declarations, including columns whose values are not
+    /// elements of the field. Columns with invalid cycles are still added to the table, so that
+    /// they aren't reported again as undeclared when they are used.
     pub(super) fn insert_periodic_columns(
         &mut self,
         columns: &[PeriodicColumn],
-    ) -> Result<(), SemanticError> {
+    ) -> Result<(), Vec<SemanticError>> {
+        let mut errors = Vec::new();
         for (index, column) in columns.iter().enumerate() {
-            validate_cycles(column)?;
-            let values = column.values().to_vec();
+            if let Err(err) = validate_cycles(column) {
+                errors.push(err);
+            }
+            let mut invalid_value = None;
+            let values: Vec<u128> = column
+                .values()
+                .iter()
+                .map(|literal| {
+                    resolve_literal(self.field, literal, column.span(), &mut invalid_value)
+                })
+                .collect();
+            errors.extend(invalid_value);
 
-            self.insert_symbol(
-                column.name(),
+            if let Err(err) = self.insert_symbol(
+                column.ident(),
                 IdentifierType::PeriodicColumn(index, values.len()),
-            )?;
+            ) {
+                errors.push(err);
+            }
             self.periodic_columns.push(values);
         }
 
+        into_result(errors)
+    }
+
+    /// Adds the number of random values declared for each auxiliary trace segment, and the names
+    /// bound to the random values by their segment and their index in the random values of the
+    /// segment. Once random values are declared, the random values referenced by the constraints
+    /// must be declared for their segment.
+    ///
+    /// # Errors
+    /// Returns the errors for all invalid declarations, including declarations for segments which
+    /// were not declared, declarations without any random value, and multiple declarations for the
+    /// same segment. Valid declarations are added to the table even if other declarations are
+    /// invalid.
+    pub(super) fn insert_random_values(
+        &mut self,
+        declarations: &[RandomValues],
+    ) -> Result<(), Vec<SemanticError>> {
+        let mut errors = Vec::new();
+        for declaration in declarations.iter() {
+            if let Err(err) = self.insert_random_values_declaration(declaration) {
+                errors.push(err);
+            }
+        }
+
+        into_result(errors)
+    }
+
+    /// Adds a single declaration of random values to the table.
+    fn insert_random_values_declaration(
+        &mut self,
+        declaration: &RandomValues,
+    ) -> Result<(), SemanticError> {
+        let name = random_values_name(declaration.segment());
+        let segment = self.get_aux_segment(declaration.segment())?;
+        if segment >= self.aux_segment_widths.len() {
+            return Err(SemanticError::InvalidUsage(
+                format!(
+                    "Random values {} were declared without an auxiliary trace segment",
+                    name
+                ),
+                declaration.span(),
+            ));
+        }
+        if declaration.size() == 0 {
+            return Err(SemanticError::InvalidUsage(
+                format!("Random values {} must contain at least one value", name),
+                declaration.span(),
+            ));
+        }
+        let random_values = self.random_values.get_or_insert_with(BTreeMap::new);
+        if random_values.contains_key(&segment) {
+            return Err(SemanticError::DuplicateIdentifier(
+                format!("Random values {} were declared more than once", name),
+                declaration.span(),
+            ));
+        }
+        random_values.insert(segment, declaration.size());
+
+        for (index, binding) in declaration.bindings().iter().enumerate() {
+            self.insert_symbol(binding, IdentifierType::RandomValue(segment, index))?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds a variable declared in a transition constraints section by its identifier name, the
+    /// type of the trace it is applied to, and the index of the node representing its value.
+    ///
+    /// # Errors
+    /// Returns an error if the identifier was already declared.
+    pub(super) fn insert_transition_variable(
+        &mut self,
+        ident: &Identifier,
+        constraint_type: ConstraintType,
+        node_index: NodeIndex,
+    ) -> Result<(), SemanticError> {
+        self.insert_symbol(
+            ident,
+            IdentifierType::TransitionVariable(constraint_type, node_index),
+        )
+    }
+
+    /// Adds a variable declared in a boundary constraints section by its identifier name and value.
+    ///
+    /// # Errors
+    /// Returns an error if the identifier was already declared.
+    pub(super) fn insert_boundary_variable(
+        &mut self,
+        ident: &Identifier,
+        value: BoundaryExpr,
+    ) -> Result<(), SemanticError> {
+        self.insert_symbol(ident, IdentifierType::BoundaryVariable(value))
+    }
+
+    /// Adds an evaluator function by its identifier name and declaration. The body of the evaluator
+    /// is only validated when the evaluator is called, since its parameters are bound to the
+    /// arguments of each call.
+    ///
+    /// # Errors
+    /// Returns an error if the identifier was already declared.
+    pub(super) fn insert_evaluator(
+        &mut self,
+        evaluator: &EvaluatorFunction,
+    ) -> Result<(), SemanticError> {
+        let key = match &self.module {
+            Some(module) => format!("{}::{}", module, evaluator.name()),
+            None => evaluator.name().to_string(),
+        };
+        self.insert_symbol(evaluator.ident(), IdentifierType::Evaluator(key.clone()))?;
+        self.evaluators
+            .insert(key, (self.module.clone(), evaluator.clone()));
+
         Ok(())
     }
 
+    /// Adds the specified item of a library module, whose declarations have already been
+    /// processed, to the module which is being processed.
+    ///
+    /// # Errors
+    /// Returns an error if the item is not in the namespace of the library module, or if its name
+    /// was already declared.
+    pub(super) fn import_item(
+        &mut self,
+        module: &Identifier,
+        item: &Identifier,
+    ) -> Result<(), SemanticError> {
+        let ident_type = self
+            .namespaces
+            .get(module.name())
+            .and_then(|namespace| namespace.get(item.name()))
+            .ok_or_else(|| {
+                SemanticError::InvalidImport(
+                    format!("Item {} was not declared in module {}", item, module),
+                    item.span(),
+                )
+            })?;
+        self.insert_symbol(item, ident_type.clone())
+    }
+
+    /// Starts processing the declarations of the specified library module, which are added to an
+    /// empty namespace. The identifiers of the module which is being processed are restored by
+    /// [SymbolTable::exit_module].
+    pub(super) fn enter_module(&mut self, name: &str) {
+        let enclosing = (
+            self.module.replace(name.to_string()),
+            std::mem::take(&mut self.identifiers),
+        );
+        self.enclosing_modules.push(enclosing);
+    }
+
+    /// Stops processing the declarations of the library module which was last entered, saves its
+    /// namespace so that its items can be imported, and restores the identifiers of the module
+    /// which imported it.
+    ///
+    /// # Panics
+    /// Panics if no library module is being processed.
+    pub(super) fn exit_module(&mut self) {
+        let (module, identifiers) = self
+            .enclosing_modules
+            .pop()
+            .expect("no library module is being processed");
+        let name = std::mem::replace(&mut self.module, module)
+            .expect("no library module is being processed");
+        let namespace = std::mem::replace(&mut self.identifiers, identifiers);
+        self.namespaces.insert(name, namespace);
+    }
+
+    /// Removes all variables from the table. Variables are scoped to the section in which they
+    /// are declared, so this is called once all statements of a section have been processed.
+    pub(super) fn clear_variables(&mut self) {
+        self.identifiers.retain(|_, ident_type| {
+            !matches!(
+                ident_type,
+                IdentifierType::TransitionVariable(..) | IdentifierType::BoundaryVariable(_)
+            )
+        });
+    }
+
     pub(super) fn into_declarations(self) -> (PublicInputs, PeriodicColumns) {
         (self.public_inputs, self.periodic_columns)
     }
 
     // --- ACCESSORS ------------------------------------------------------------------------------
 
-    /// Returns the type associated with the specified identifier name.
+    /// Returns the field over which the AIR is defined.
+    pub(super) fn field(&self) -> Field {
+        self.field
+    }
+
+    /// Returns the number of columns in the main execution trace and in each segment of the
+    /// auxiliary execution trace, in that order.
+    pub(super) fn trace_widths(&self) -> (usize, Vec<usize>) {
+        (self.main_trace_width, self.aux_segment_widths.clone())
+    }
+
+    /// Returns the number of random values declared for each auxiliary trace segment by the index
+    /// of the segment, or None if the random values were not declared.
+    pub(super) fn random_values(&self) -> Option<BTreeMap<usize, usize>> {
+        self.random_values.clone()
+    }
+
+    /// Returns the index of the auxiliary trace segment of the specified random value and the
+    /// index of the random value in the random values of that segment.
+    ///
+    /// # Errors
+    /// Returns an error if the segment of the random value was not declared, or if random values
+    /// were declared and the index is out of range of the random values of the segment.
+    pub(super) fn get_random_value(
+        &self,
+        access: &RandomValueAccess,
+    ) -> Result<(usize, usize), SemanticError> {
+        let segment = self.get_aux_segment(access.segment())?;
+        // a random value of the first segment can be referenced without naming the segment, which
+        // may not have been declared.
+        if segment >= self.aux_segment_widths.len() {
+            return Err(SemanticError::InvalidUsage(
+                format!(
+                    "Random value {} was used without an auxiliary trace segment",
+                    access
+                ),
+                access.span(),
+            ));
+        }
+        if let Some(random_values) = &self.random_values {
+            let size = random_values.get(&segment).copied().unwrap_or(0);
+            if access.idx() >= size {
+                return Err(SemanticError::IndexOutOfRange(
+                    format!(
+                        "Out-of-range index {} in random values {} of length {}",
+                        access.idx(),
+                        random_values_name(access.segment()),
+                        size
+                    ),
+                    access.span(),
+                ));
+            }
+        }
+
+        Ok((segment, access.idx()))
+    }
+
+    /// Returns the index of the auxiliary trace segment with the specified identifier, or of the
+    /// first segment if no identifier is specified.
+    ///
+    /// # Errors
+    /// Returns an error if the identifier was not declared or was not declared as an auxiliary
+    /// trace segment.
+    pub(super) fn get_aux_segment(
+        &self,
+        ident: Option<&Identifier>,
+    ) -> Result<usize, SemanticError> {
+        let Some(ident) = ident else {
+            return Ok(0);
+        };
+        match self.get_type(ident)? {
+            IdentifierType::AuxTraceSegment(index) => Ok(*index),
+            ident_type => Err(SemanticError::InvalidUsage(
+                format!(
+                    "Identifier {} was declared as a {} not as an auxiliary trace segment",
+                    ident, ident_type
+                ),
+                ident.span(),
+            )),
+        }
+    }
+
+    /// Returns true if the declarations of the specified library module have been processed.
+    pub(super) fn has_module(&self, name: &str) -> bool {
+        self.namespaces.contains_key(name)
+    }
+
+    /// Returns true if the declarations of the specified library module are being processed, i.e.
+    /// if the module is being imported into itself.
+    pub(super) fn is_processing_module(&self, name: &str) -> bool {
+        self.module.as_deref() == Some(name)
+            || self
+                .enclosing_modules
+                .iter()
+                .any(|(module, _)| module.as_deref() == Some(name))
+    }
+
+    /// Returns the type associated with the specified identifier.
     ///
     /// # Errors
     /// Returns an error if the identifier was not in the symbol table.
-    pub(super) fn get_type(&self, name: &str) -> Result<IdentifierType, SemanticError> {
-        if let Some(ident_type) = self.identifiers.get(name) {
-            Ok(*ident_type)
+    pub(super) fn get_type(&self, ident: &Identifier) -> Result<&IdentifierType, SemanticError> {
+        if let Some(ident_type) = self.identifiers.get(ident.name()) {
+            Ok(ident_type)
         } else {
-            Err(SemanticError::InvalidIdentifier(format!(
-                "Identifier {} was not declared",
-                name
-            )))
+            Err(SemanticError::InvalidIdentifier(
+                format!("Identifier {} was not declared", ident),
+                ident.span(),
+            ))
         }
     }
 
@@ -153,26 +631,420 @@ impl SymbolTable {
     /// - Returns an error if the index is not in the declared public input array.
     pub(super) fn validate_public_input(
         &self,
-        name: &str,
+        ident: &Identifier,
         index: usize,
     ) -> Result<(), SemanticError> {
-        let ident_type = self.get_type(name)?;
-        if let IdentifierType::PublicInput(size) = ident_type {
+        let ident_type = self.get_type(ident)?;
+        if let IdentifierType::PublicInput(size) = *ident_type {
             if index < size {
                 Ok(())
             } else {
-                Err(SemanticError::IndexOutOfRange(format!(
-                    "Out-of-range index {} in public input {} of length {}",
-                    index, name, size
-                )))
+                Err(SemanticError::IndexOutOfRange(
+                    format!(
+                        "Out-of-range index {} in public input {} of length {}",
+                        index, ident, size
+                    ),
+                    ident.span(),
+                ))
             }
         } else {
-            Err(SemanticError::InvalidUsage(format!(
-                "Identifier {} was declared as {}, not as a public input",
-                name, ident_type
-            )))
+            Err(SemanticError::InvalidUsage(
+                format!(
+                    "Identifier {} was declared as {}, not as a public input",
+                    ident, ident_type
+                ),
+                ident.span(),
+            ))
         }
     }
+
+    /// Returns the type of the trace column referenced by the specified access to an element of a
+    /// trace column group, i.e. the main or the auxiliary trace column with the index of the
+    /// element in its trace.
+    ///
+    /// # Errors
+    /// - Returns an error if the identifier was not declared as a trace column group.
+    /// - Returns an error if the index is not in the declared group.
+    pub(super) fn get_trace_column_group_element(
+        &self,
+        access: &VectorAccess,
+    ) -> Result<IdentifierType, SemanticError> {
+        let ident = access.ident();
+        let (range, column_type): (_, fn(usize) -> IdentifierType) = match self.get_type(ident)? {
+            IdentifierType::MainTraceColumnGroup(range) => (range, IdentifierType::MainTraceColumn),
+            IdentifierType::AuxTraceColumnGroup(range) => (range, IdentifierType::AuxTraceColumn),
+            ident_type => {
+                return Err(SemanticError::InvalidUsage(
+                    format!(
+                        "Identifier {} was declared as a {}, not as a trace column group",
+                        ident, ident_type
+                    ),
+                    ident.span(),
+                ))
+            }
+        };
+
+        if access.idx() < range.len() {
+            Ok(column_type(range.start + access.idx()))
+        } else {
+            Err(SemanticError::IndexOutOfRange(
+                format!(
+                    "Out-of-range index {} in trace column group {} of length {}",
+                    access.idx(),
+                    ident,
+                    range.len()
+                ),
+                ident.span(),
+            ))
+        }
+    }
+
+    /// Returns the declaration of the evaluator function with the specified identifier.
+    ///
+    /// # Errors
+    /// Returns an error if the identifier was not declared as an evaluator.
+    pub(super) fn get_evaluator(
+        &self,
+        ident: &Identifier,
+    ) -> Result<&EvaluatorFunction, SemanticError> {
+        let key = self.get_evaluator_key(ident)?;
+        Ok(&self.evaluators[key].1)
+    }
+
+    /// Returns the key of the declaration of the evaluator function with the specified identifier.
+    ///
+    /// # Errors
+    /// Returns an error if the identifier was not declared as an evaluator.
+    fn get_evaluator_key(&self, ident: &Identifier) -> Result<&str, SemanticError> {
+        match self.get_type(ident)? {
+            IdentifierType::Evaluator(key) => Ok(key),
+            ident_type => Err(SemanticError::InvalidUsage(
+                format!(
+                    "Identifier {} was declared as a {}, not as an evaluator",
+                    ident, ident_type
+                ),
+                ident.span(),
+            )),
+        }
+    }
+
+    /// Returns the symbol table with which the body of the evaluator is processed for the
+    /// specified call. In this table, the trace columns declared for the AIR are replaced by the
+    /// parameters of the evaluator, which are bound to the trace columns passed as arguments, and
+    /// all other declarations are kept. The body of an evaluator declared in a library module can
+    /// only reference the parameters and the items declared in or imported into that module.
+    ///
+    /// Each parameter is bound to the next columns of the flattened list of columns passed for its
+    /// trace, so a group parameter must be bound to consecutive columns.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The identifier was not declared as an evaluator, or the evaluator is called recursively.
+    /// - An argument does not reference trace columns of the trace of its parameters.
+    /// - The number of columns passed for a trace doesn't match the number of columns of the
+    ///   parameters for that trace.
+    /// - A group parameter is bound to columns which are not consecutive.
+    /// - A parameter has the same name as another parameter or as a declaration which is kept.
+    pub(super) fn evaluator_scope(&self, call: &EvaluatorCall) -> Result<Self, SemanticError> {
+        let key = self.get_evaluator_key(call.ident())?;
+        let (module, evaluator) = &self.evaluators[key];
+        if self.evaluator_calls.iter().any(|k| k == key) {
+            return Err(SemanticError::InvalidEvaluatorCall(
+                format!("Evaluator {} cannot be called recursively", call.ident()),
+                call.span(),
+            ));
+        }
+
+        let main_columns = self.get_evaluator_arg_columns(call, ConstraintType::Main)?;
+        let aux_columns = self.get_evaluator_arg_columns(call, ConstraintType::Auxiliary)?;
+        let mut params = bind_params(
+            call,
+            evaluator.main_params(),
+            &main_columns,
+            IdentifierType::MainTraceColumn,
+            IdentifierType::MainTraceColumnGroup,
+        )?;
+        params.extend(bind_params(
+            call,
+            evaluator.aux_params(),
+            &aux_columns,
+            IdentifierType::AuxTraceColumn,
+            IdentifierType::AuxTraceColumnGroup,
+        )?);
+
+        let mut scope = self.clone();
+        if let Some(module) = module {
+            scope.identifiers = self.namespaces[module].clone();
+        }
+        scope.identifiers.retain(|_, ident_type| {
+            !matches!(
+                ident_type,
+                IdentifierType::MainTraceColumn(_)
+                    | IdentifierType::AuxTraceColumn(_)
+                    | IdentifierType::MainTraceColumnGroup(_)
+                    | IdentifierType::AuxTraceColumnGroup(_)
+                    | IdentifierType::TransitionVariable(..)
+                    | IdentifierType::BoundaryVariable(_)
+            )
+        });
+        scope.evaluator_calls.push(key.to_string());
+        for (ident, ident_type) in params {
+            scope.insert_symbol(ident, ident_type)?;
+        }
+
+        Ok(scope)
+    }
+
+    /// Returns the indices of all trace columns passed as arguments of the call for the specified
+    /// trace, in order.
+    ///
+    /// # Errors
+    /// Returns an error if an argument doesn't reference a trace column or a group of trace
+    /// columns of the specified trace.
+    fn get_evaluator_arg_columns(
+        &self,
+        call: &EvaluatorCall,
+        trace: ConstraintType,
+    ) -> Result<Vec<usize>, SemanticError> {
+        let args = match trace {
+            ConstraintType::Main => call.main_args(),
+            ConstraintType::Auxiliary => call.aux_args(),
+        };
+
+        let mut columns = Vec::new();
+        for arg in args {
+            let arg_type = match arg {
+                EvaluatorArg::Named(ident) => self.get_type(ident)?.clone(),
+                EvaluatorArg::Indexed(access) => self.get_trace_column_group_element(access)?,
+            };
+            match (trace, arg_type) {
+                (ConstraintType::Main, IdentifierType::MainTraceColumn(idx))
+                | (ConstraintType::Auxiliary, IdentifierType::AuxTraceColumn(idx)) => {
+                    columns.push(idx)
+                }
+                (ConstraintType::Main, IdentifierType::MainTraceColumnGroup(range))
+                | (ConstraintType::Auxiliary, IdentifierType::AuxTraceColumnGroup(range)) => {
+                    columns.extend(range)
+                }
+                (_, arg_type) => {
+                    let expected = match trace {
+                        ConstraintType::Main => "main",
+                        ConstraintType::Auxiliary => "auxiliary",
+                    };
+                    return Err(SemanticError::InvalidEvaluatorCall(
+                        format!(
+                            "Argument {} is a {}, but {} trace columns were expected",
+                            arg, arg_type, expected
+                        ),
+                        arg.span(),
+                    ));
+                }
+            }
+        }
+
+        Ok(columns)
+    }
+
+    /// Returns the number of elements of the iterable with the specified identifier, which is
+    /// either a trace column group or a vector constant.
+    ///
+    /// # Errors
+    /// Returns an error if the identifier was not declared as a trace column group or as a vector
+    /// constant.
+    pub(super) fn get_iterable_len(&self, ident: &Identifier) -> Result<usize, SemanticError> {
+        match self.get_type(ident)? {
+            IdentifierType::MainTraceColumnGroup(range)
+            | IdentifierType::AuxTraceColumnGroup(range) => Ok(range.len()),
+            IdentifierType::Constant(ConstantType::Vector(values)) => Ok(values.len()),
+            ident_type => Err(SemanticError::InvalidUsage(
+                format!(
+                    "Identifier {} was declared as a {}, not as a trace column group or a vector constant",
+                    ident, ident_type
+                ),
+                ident.span(),
+            )),
+        }
+    }
+
+    /// Returns the value of the scalar constant with the specified identifier.
+    ///
+    /// # Errors
+    /// Returns an error if the identifier was not declared as a scalar constant.
+    pub(super) fn get_scalar_constant(&self, ident: &Identifier) -> Result<u128, SemanticError> {
+        match self.get_type(ident)? {
+            IdentifierType::Constant(ConstantType::Scalar(value)) => Ok(*value),
+            ident_type => Err(SemanticError::InvalidUsage(
+                format!(
+                    "Identifier {} was declared as a {}, not as a scalar constant",
+                    ident, ident_type
+                ),
+                ident.span(),
+            )),
+        }
+    }
+
+    /// Returns the value of the element of a vector constant referenced by the specified access.
+    ///
+    /// # Errors
+    /// - Returns an error if the identifier was not declared as a vector constant.
+    /// - Returns an error if the index is not in the declared vector.
+    pub(super) fn get_vector_constant_element(
+        &self,
+        access: &VectorAccess,
+    ) -> Result<u128, SemanticError> {
+        let ident = access.ident();
+        match self.get_type(ident)? {
+            IdentifierType::Constant(ConstantType::Vector(values)) => {
+                values.get(access.idx()).copied().ok_or_else(|| {
+                    SemanticError::IndexOutOfRange(
+                        format!(
+                            "Out-of-range index {} in vector constant {} of length {}",
+                            access.idx(),
+                            ident,
+                            values.len()
+                        ),
+                        ident.span(),
+                    )
+                })
+            }
+            ident_type => Err(SemanticError::InvalidUsage(
+                format!(
+                    "Identifier {} was declared as a {}, not as a vector constant",
+                    ident, ident_type
+                ),
+                ident.span(),
+            )),
+        }
+    }
+
+    /// Returns the value of the element of a matrix constant referenced by the specified access.
+    ///
+    /// # Errors
+    /// - Returns an error if the identifier was not declared as a matrix constant.
+    /// - Returns an error if the row or the column index is not in the declared matrix.
+    pub(super) fn get_matrix_constant_element(
+        &self,
+        access: &MatrixAccess,
+    ) -> Result<u128, SemanticError> {
+        let ident = access.ident();
+        match self.get_type(ident)? {
+            IdentifierType::Constant(ConstantType::Matrix(rows)) => rows
+                .get(access.row_idx())
+                .and_then(|row| row.get(access.col_idx()))
+                .copied()
+                .ok_or_else(|| {
+                    SemanticError::IndexOutOfRange(
+                        format!(
+                            "Out-of-range indices [{}][{}] in matrix constant {} of {} rows",
+                            access.row_idx(),
+                            access.col_idx(),
+                            ident,
+                            rows.len()
+                        ),
+                        ident.span(),
+                    )
+                }),
+            ident_type => Err(SemanticError::InvalidUsage(
+                format!(
+                    "Identifier {} was declared as a {}, not as a matrix constant",
+                    ident, ident_type
+                ),
+                ident.span(),
+            )),
+        }
+    }
+}
+
+/// Binds each of the parameters of an evaluator to the next columns of the provided list of
+/// columns, using the provided constructors to build the types of single columns and of column
+/// groups.
+///
+/// # Errors
+/// Returns an error if the number of columns doesn't match the number of columns of the parameters,
+/// or if a group parameter is bound to columns which are not consecutive.
+fn bind_params<'a>(
+    call: &EvaluatorCall,
+    params: &'a [TraceColumn],
+    columns: &[usize],
+    single_type: fn(usize) -> IdentifierType,
+    group_type: fn(Range<usize>) -> IdentifierType,
+) -> Result<Vec<(&'a Identifier, IdentifierType)>, SemanticError> {
+    let num_params: usize = params.iter().map(|param| param.size()).sum();
+    if num_params != columns.len() {
+        return Err(SemanticError::InvalidEvaluatorCall(
+            format!(
+                "Evaluator {} expects {} trace columns, but {} were passed",
+                call.ident(),
+                num_params,
+                columns.len()
+            ),
+            call.span(),
+        ));
+    }
+
+    let mut offset = 0;
+    let mut bindings = Vec::new();
+    for param in params {
+        let param_columns = &columns[offset..offset + param.size()];
+        let ident_type = match param {
+            TraceColumn::Single(_) => single_type(param_columns[0]),
+            TraceColumn::Group(..) => {
+                let start = param_columns[0];
+                if param_columns
+                    .iter()
+                    .zip(start..)
+                    .any(|(col, idx)| *col != idx)
+                {
+                    return Err(SemanticError::InvalidEvaluatorCall(
+                        format!(
+                            "Group parameter {} of evaluator {} must be bound to consecutive trace columns",
+                            param.ident(),
+                            call.ident()
+                        ),
+                        call.span(),
+                    ));
+                }
+                group_type(start..start + param.size())
+            }
+        };
+        bindings.push((param.ident(), ident_type));
+        offset += param.size();
+    }
+
+    Ok(bindings)
+}
+
+/// Returns the value of the provided literal of the declaration at the specified span as an element
+/// of the field. An invalid literal is resolved to zero, so that its declaration can still be added
+/// to the symbol table, and the first error of the declaration is recorded in `error`.
+fn resolve_literal(
+    field: Field,
+    literal: &Literal,
+    span: SourceSpan,
+    error: &mut Option<SemanticError>,
+) -> u128 {
+    field.resolve_literal(*literal, span).unwrap_or_else(|err| {
+        error.get_or_insert(err);
+        0
+    })
+}
+
+/// Validates that all rows of a matrix constant have the same length.
+fn validate_constant(constant: &Constant) -> Result<(), SemanticError> {
+    if let ConstantType::Matrix(rows) = constant.value() {
+        let row_len = rows[0].len();
+        if rows.iter().any(|row| row.len() != row_len) {
+            return Err(SemanticError::InvalidConstant(
+                format!(
+                    "All rows of matrix constant {} must have the same length",
+                    constant.name()
+                ),
+                constant.span(),
+            ));
+        }
+    }
+
+    Ok(())
 }
 
 /// Validates the cycle length of the specified periodic column.
@@ -181,18 +1053,42 @@ pub(super) fn validate_cycles(column: &PeriodicColumn) -> Result<(), SemanticErr
     let cycle = column.values().len();
 
     if !cycle.is_power_of_two() {
-        return Err(SemanticError::InvalidPeriodicColumn(format!(
-            "cycle length must be a power of two, but was {} for cycle {}",
-            cycle, name
-        )));
+        return Err(SemanticError::InvalidPeriodicColumn(
+            format!(
+                "cycle length must be a power of two, but was {} for cycle {}",
+                cycle, name
+            ),
+            column.span(),
+        ));
     }
 
     if cycle < MIN_CYCLE_LENGTH {
-        return Err(SemanticError::InvalidPeriodicColumn(format!(
-            "cycle length must be at least {}, but was {} for cycle {}",
-            MIN_CYCLE_LENGTH, cycle, name
-        )));
+        return Err(SemanticError::InvalidPeriodicColumn(
+            format!(
+                "cycle length must be at least {}, but was {} for cycle {}",
+                MIN_CYCLE_LENGTH, cycle, name
+            ),
+            column.span(),
+        ));
     }
 
     Ok(())
 }
+
+/// Returns the name under which the random values of the auxiliary trace segment with the
+/// specified identifier are accessed, e.g. `$rand.bus`, or `$rand` for the first segment.
+fn random_values_name(segment: Option<&Identifier>) -> String {
+    match segment {
+        Some(segment) => format!("$rand.{}", segment),
+        None => "$rand".to_string(),
+    }
+}
+
+/// Returns Ok if no errors were found, or all of the errors otherwise.
+fn into_result(errors: Vec<SemanticError>) -> Result<(), Vec<SemanticError>> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
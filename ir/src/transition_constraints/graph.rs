@@ -1,8 +1,10 @@
 use super::{
-    super::BTreeMap, degree::TransitionConstraintDegree, ConstraintType, SemanticError, SymbolTable,
+    degree::TransitionConstraintDegree, list_comprehension, BTreeMap, ConstraintType,
+    SemanticError, SymbolTable,
 };
 use crate::symbol_table::IdentifierType;
-use parser::ast::{Identifier, TransitionExpr};
+use parser::ast::{ConstantType, Identifier, TransitionExpr};
+use std::collections::HashMap;
 
 // ALGEBRAIC GRAPH
 // ================================================================================================
@@ -12,10 +14,12 @@ use parser::ast::{Identifier, TransitionExpr};
 /// nodes by their index in the vector.
 ///
 /// Within the graph, constraint expressions can overlap and share subgraphs, since new expressions
-/// reuse matching existing nodes when they are added, rather than creating new nodes.
+/// reuse matching existing nodes when they are added, rather than creating new nodes. Existing
+/// nodes are found in constant time by interning the operations of the nodes, and the operands of
+/// commutative operations are normalized, so that `a + b` and `b + a` share the same node.
 ///
 /// - Leaf nodes (with no outgoing edges) are constants or references to trace cells (i.e. column 0
-///   in the current row or column 5 in the next row).
+///   in the current row or column 5 in the row at offset 2 from the current row).
 /// - Tip nodes with no incoming edges (no parent nodes) always represent constraints, although they
 ///   do not necessarily represent all constraints. There could be constraints which are also
 ///   subgraphs of other constraints.
@@ -23,6 +27,8 @@ use parser::ast::{Identifier, TransitionExpr};
 pub struct AlgebraicGraph {
     /// All nodes in the graph.
     nodes: Vec<Node>,
+    /// The index of the node of each operation in the graph, keyed by the normalized operation.
+    node_indices: HashMap<Operation, NodeIndex>,
 }
 
 impl AlgebraicGraph {
@@ -33,6 +39,20 @@ impl AlgebraicGraph {
         &self.nodes[index.0]
     }
 
+    /// Returns the number of nodes in the graph.
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns an iterator over the nodes of the graph and their indices, in the order of their
+    /// indices, so every node is returned after the nodes it references.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeIndex, &Node)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (NodeIndex(index), node))
+    }
+
     /// Returns the degree of the subgraph which has the specified node as its tip.
     pub fn degree(&self, index: &NodeIndex) -> TransitionConstraintDegree {
         let mut cycles: BTreeMap<usize, usize> = BTreeMap::new();
@@ -49,11 +69,8 @@ impl AlgebraicGraph {
     fn accumulate_degree(&self, cycles: &mut BTreeMap<usize, usize>, index: &NodeIndex) -> usize {
         // recursively walk the subgraph and compute the degree from the operation and child nodes
         match self.node(index).op() {
-            Operation::Const(_) | Operation::RandomValue(_) => 0,
-            Operation::MainTraceCurrentRow(_)
-            | Operation::MainTraceNextRow(_)
-            | Operation::AuxTraceCurrentRow(_)
-            | Operation::AuxTraceNextRow(_) => 1,
+            Operation::Const(_) | Operation::RandomValue(..) | Operation::PublicInput(..) => 0,
+            Operation::MainTraceRow(..) | Operation::AuxTraceRow(..) => 1,
             Operation::PeriodicColumn(index, cycle_len) => {
                 cycles.insert(*index, *cycle_len);
                 0
@@ -76,8 +93,96 @@ impl AlgebraicGraph {
         }
     }
 
+    /// Returns the number of random values of the specified auxiliary trace segment referenced by
+    /// the graph, i.e. one more than the largest index of a random value of the segment in the
+    /// graph, or zero if there are none.
+    pub fn num_random_values(&self, segment: usize) -> usize {
+        self.nodes
+            .iter()
+            .filter_map(|node| match node.op() {
+                Operation::RandomValue(s, index) if *s == segment => Some(index + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the largest offset from the current row of the trace rows referenced by the graph,
+    /// e.g. 1 if the graph references at most the next row, or 0 if it references no trace rows
+    /// after the current row.
+    pub fn max_row_offset(&self) -> usize {
+        self.nodes
+            .iter()
+            .filter_map(|node| match node.op() {
+                Operation::MainTraceRow(_, offset) | Operation::AuxTraceRow(_, offset) => {
+                    Some(*offset)
+                }
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+            .max(0) as usize
+    }
+
+    /// Returns the smallest offset from the current row of the trace rows referenced by the
+    /// subgraph with the specified tip, e.g. -1 if it references the previous row, or 0 if it
+    /// references no trace rows before the current row.
+    pub(super) fn min_row_offset(&self, index: &NodeIndex) -> isize {
+        match self.node(index).op() {
+            Operation::MainTraceRow(_, offset) | Operation::AuxTraceRow(_, offset) => {
+                (*offset).min(0)
+            }
+            Operation::Neg(value) | Operation::Exp(value, _) => self.min_row_offset(value),
+            Operation::Add(lhs, rhs) | Operation::Mul(lhs, rhs) => {
+                self.min_row_offset(lhs).min(self.min_row_offset(rhs))
+            }
+            _ => 0,
+        }
+    }
+
     // --- MUTATORS -------------------------------------------------------------------------------
 
+    /// Adds a copy of the subgraph with the specified tip in which the offsets of all of the trace
+    /// rows are increased by the specified shift, and returns the index of its tip. The nodes of the
+    /// subgraph which don't reference trace rows are reused.
+    pub(super) fn shift_rows(&mut self, index: &NodeIndex, shift: isize) -> NodeIndex {
+        let mut shifted = BTreeMap::new();
+        self.shift_rows_cached(index, shift, &mut shifted)
+    }
+
+    /// Recursively shifts the rows of the subgraph with the specified tip, caching the index of the
+    /// copy of each node so that the shared nodes of the subgraph are only copied once.
+    fn shift_rows_cached(
+        &mut self,
+        index: &NodeIndex,
+        shift: isize,
+        shifted: &mut BTreeMap<NodeIndex, NodeIndex>,
+    ) -> NodeIndex {
+        if let Some(copy) = shifted.get(index) {
+            return *copy;
+        }
+        let op = match self.node(index).op().clone() {
+            Operation::MainTraceRow(col, offset) => Operation::MainTraceRow(col, offset + shift),
+            Operation::AuxTraceRow(col, offset) => Operation::AuxTraceRow(col, offset + shift),
+            Operation::Neg(value) => Operation::Neg(self.shift_rows_cached(&value, shift, shifted)),
+            Operation::Exp(value, power) => {
+                Operation::Exp(self.shift_rows_cached(&value, shift, shifted), power)
+            }
+            Operation::Add(lhs, rhs) => Operation::Add(
+                self.shift_rows_cached(&lhs, shift, shifted),
+                self.shift_rows_cached(&rhs, shift, shifted),
+            ),
+            Operation::Mul(lhs, rhs) => Operation::Mul(
+                self.shift_rows_cached(&lhs, shift, shifted),
+                self.shift_rows_cached(&rhs, shift, shifted),
+            ),
+            _ => return *index,
+        };
+        let copy = self.insert_op(op);
+        shifted.insert(*index, copy);
+        copy
+    }
+
     /// Add the expression to the graph and return the result index and a constraint type indicating
     /// whether it is applied to the main execution trace or an auxiliary trace. Expressions are
     /// added recursively to reuse existing matching nodes.
@@ -93,11 +198,61 @@ impl AlgebraicGraph {
                 let node_index = self.insert_op(Operation::Const(value));
                 Ok((constraint_type, node_index))
             }
-            TransitionExpr::Var(Identifier(ident)) => self.insert_variable(symbol_table, &ident),
-            TransitionExpr::Next(Identifier(ident)) => self.insert_next(symbol_table, &ident),
-            TransitionExpr::Rand(index) => {
+            TransitionExpr::Var(ident) => {
+                let col_type = symbol_table.get_type(&ident)?;
+                self.insert_variable(&ident, col_type)
+            }
+            TransitionExpr::VectorAccess(access) => match symbol_table.get_type(access.ident())? {
+                IdentifierType::MainTraceColumnGroup(_)
+                | IdentifierType::AuxTraceColumnGroup(_) => {
+                    let col_type = symbol_table.get_trace_column_group_element(&access)?;
+                    self.insert_variable(access.ident(), &col_type)
+                }
+                IdentifierType::PublicInput(_) => {
+                    // constraint target defaults to Main trace.
+                    let constraint_type = ConstraintType::Main;
+                    symbol_table.validate_public_input(access.ident(), access.idx())?;
+                    let node_index = self.insert_op(Operation::PublicInput(
+                        access.name().to_string(),
+                        access.idx(),
+                    ));
+                    Ok((constraint_type, node_index))
+                }
+                _ => {
+                    // constraint target defaults to Main trace.
+                    let constraint_type = ConstraintType::Main;
+                    let value = symbol_table.get_vector_constant_element(&access)?;
+                    let node_index = self.insert_op(Operation::Const(value));
+                    Ok((constraint_type, node_index))
+                }
+            },
+            TransitionExpr::MatrixAccess(access) => {
+                // constraint target defaults to Main trace.
+                let constraint_type = ConstraintType::Main;
+                let value = symbol_table.get_matrix_constant_element(&access)?;
+                let node_index = self.insert_op(Operation::Const(value));
+                Ok((constraint_type, node_index))
+            }
+            TransitionExpr::Next(ident) => {
+                let col_type = symbol_table.get_type(&ident)?;
+                self.insert_trace_row(&ident, col_type, 1)
+            }
+            TransitionExpr::NextVectorAccess(access) => {
+                let col_type = symbol_table.get_trace_column_group_element(&access)?;
+                self.insert_trace_row(access.ident(), &col_type, 1)
+            }
+            TransitionExpr::RowOffset(ident, offset) => {
+                let col_type = symbol_table.get_type(&ident)?;
+                self.insert_trace_row(&ident, col_type, offset)
+            }
+            TransitionExpr::VectorRowOffset(access, offset) => {
+                let col_type = symbol_table.get_trace_column_group_element(&access)?;
+                self.insert_trace_row(access.ident(), &col_type, offset)
+            }
+            TransitionExpr::Rand(access) => {
                 let constraint_type = ConstraintType::Auxiliary;
-                let node_index = self.insert_op(Operation::RandomValue(index));
+                let (segment, index) = symbol_table.get_random_value(&access)?;
+                let node_index = self.insert_op(Operation::RandomValue(segment, index));
                 Ok((constraint_type, node_index))
             }
             TransitionExpr::Add(lhs, rhs) => {
@@ -136,83 +291,118 @@ impl AlgebraicGraph {
                 let node_index = self.insert_op(Operation::Exp(lhs, rhs as usize));
                 Ok((constraint_type, node_index))
             }
+            TransitionExpr::Neg(expr) => {
+                // add the negated subexpression.
+                let (constraint_type, expr) = self.insert_expr(symbol_table, *expr)?;
+                let node_index = self.insert_op(Operation::Neg(expr));
+                Ok((constraint_type, node_index))
+            }
+            TransitionExpr::ListFolding(folding) => {
+                // add the expression which folds the elements of the list.
+                let expr = list_comprehension::fold_list(symbol_table, &folding)?;
+                self.insert_expr(symbol_table, expr)
+            }
         }
     }
 
-    fn insert_next(
+    /// Adds a reference to the row at the specified offset from the current row of the trace
+    /// column with the specified identifier and type, where the type is either the declared type
+    /// of the identifier or the type of the referenced element of a trace column group.
+    fn insert_trace_row(
         &mut self,
-        symbol_table: &SymbolTable,
-        ident: &str,
+        ident: &Identifier,
+        col_type: &IdentifierType,
+        offset: isize,
     ) -> Result<(ConstraintType, NodeIndex), SemanticError> {
-        let col_type = symbol_table.get_type(ident)?;
-
-        // a "next" variable expression always references an execution trace columns
-        match col_type {
+        // a row offset always references an execution trace column
+        match *col_type {
             IdentifierType::MainTraceColumn(index) => {
                 let constraint_type = ConstraintType::Main;
-                let node_index = self.insert_op(Operation::MainTraceNextRow(index));
+                let node_index = self.insert_op(Operation::MainTraceRow(index, offset));
                 Ok((constraint_type, node_index))
             }
             IdentifierType::AuxTraceColumn(index) => {
                 let constraint_type = ConstraintType::Auxiliary;
-                let node_index = self.insert_op(Operation::AuxTraceNextRow(index));
+                let node_index = self.insert_op(Operation::AuxTraceRow(index, offset));
                 Ok((constraint_type, node_index))
             }
-            _ => Err(SemanticError::InvalidUsage(format!(
-                "Identifier {} was declared as a {} not as a trace column",
-                ident, col_type
-            ))),
+            _ => Err(SemanticError::InvalidUsage(
+                format!(
+                    "Identifier {} was declared as a {} not as a trace column",
+                    ident, col_type
+                ),
+                ident.span(),
+            )),
         }
     }
 
+    /// Adds a reference to the value of the identifier with the specified type in the current
+    /// row, where the type is either the declared type of the identifier or the type of the
+    /// referenced element of a trace column group.
     fn insert_variable(
         &mut self,
-        symbol_table: &SymbolTable,
-        ident: &str,
+        ident: &Identifier,
+        col_type: &IdentifierType,
     ) -> Result<(ConstraintType, NodeIndex), SemanticError> {
-        let col_type = symbol_table.get_type(ident)?;
-
-        // since variable definitions are not possible yet, the identifier must match one of
-        // the declared trace columns or one of the declared periodic columns.
+        // the identifier must match one of the declared trace columns, periodic columns, scalar
+        // constants or variables. The values of constants are inlined into the graph, while
+        // variables reference the existing subgraph of their value.
         match col_type {
             IdentifierType::MainTraceColumn(index) => {
                 let constraint_type = ConstraintType::Main;
-                let node_index = self.insert_op(Operation::MainTraceCurrentRow(index));
+                let node_index = self.insert_op(Operation::MainTraceRow(*index, 0));
                 Ok((constraint_type, node_index))
             }
             IdentifierType::AuxTraceColumn(index) => {
                 let constraint_type = ConstraintType::Auxiliary;
-                let node_index = self.insert_op(Operation::AuxTraceCurrentRow(index));
+                let node_index = self.insert_op(Operation::AuxTraceRow(*index, 0));
+                Ok((constraint_type, node_index))
+            }
+            IdentifierType::RandomValue(segment, index) => {
+                let constraint_type = ConstraintType::Auxiliary;
+                let node_index = self.insert_op(Operation::RandomValue(*segment, *index));
                 Ok((constraint_type, node_index))
             }
             IdentifierType::PeriodicColumn(index, cycle_len) => {
                 // constraint target defaults to Main trace.
                 let constraint_type = ConstraintType::Main;
-                let node_index = self.insert_op(Operation::PeriodicColumn(index, cycle_len));
+                let node_index = self.insert_op(Operation::PeriodicColumn(*index, *cycle_len));
                 Ok((constraint_type, node_index))
             }
-            _ => Err(SemanticError::InvalidUsage(format!(
-                "Identifier {} was declared as a {} not as a trace column",
-                ident, col_type
-            ))),
+            IdentifierType::Constant(ConstantType::Scalar(value)) => {
+                // constraint target defaults to Main trace.
+                let constraint_type = ConstraintType::Main;
+                let node_index = self.insert_op(Operation::Const(*value));
+                Ok((constraint_type, node_index))
+            }
+            IdentifierType::TransitionVariable(constraint_type, node_index) => {
+                Ok((*constraint_type, *node_index))
+            }
+            _ => Err(SemanticError::InvalidUsage(
+                format!(
+                    "Identifier {} was declared as a {} not as a trace column",
+                    ident, col_type
+                ),
+                ident.span(),
+            )),
         }
     }
 
     /// Insert the operation and return its node index. If an identical node already exists, return
-    /// that index instead.
-    fn insert_op(&mut self, op: Operation) -> NodeIndex {
-        self.nodes.iter().position(|n| *n.op() == op).map_or_else(
-            || {
-                // create a new node.
-                let index = self.nodes.len();
-                self.nodes.push(Node { op });
-                NodeIndex(index)
-            },
-            |index| {
-                // return the existing node's index.
-                NodeIndex(index)
-            },
-        )
+    /// that index instead. Operations on the same operands in a different order are considered
+    /// identical when the operation is commutative.
+    pub(super) fn insert_op(&mut self, op: Operation) -> NodeIndex {
+        let key = op.normalize();
+        if let Some(index) = self.node_indices.get(&key) {
+            // return the existing node's index.
+            return *index;
+        }
+
+        // create a new node.
+        let index = NodeIndex(self.nodes.len());
+        self.nodes.push(Node { op });
+        self.node_indices.insert(key, index);
+        index
     }
 }
 
@@ -224,9 +414,10 @@ fn get_binop_constraint_type(lhs_type: ConstraintType, rhs_type: ConstraintType)
     }
 }
 
-/// Reference to a node in a graph by its index in the nodes vector of the graph struct.
-#[derive(Debug, Eq, PartialEq, Default)]
-pub struct NodeIndex(usize);
+/// Reference to a node in a graph by its index in the nodes vector of the graph struct. Since nodes
+/// are added after the nodes they reference, the nodes of a subgraph are ordered by their indices.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct NodeIndex(pub(super) usize);
 
 #[derive(Debug)]
 pub struct Node {
@@ -241,29 +432,28 @@ impl Node {
 }
 
 /// A transition constraint operation or value reference.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Operation {
-    Const(u64),
-    /// An identifier for a for a cell in the specified column in the current row in the main trace.
-    /// The inner value is the index of the column within the trace.
-    MainTraceCurrentRow(usize),
-    /// An identifier for a cell in the specified column in the next row in the main trace. The
-    /// inner value is the index of the column within the trace.
-    MainTraceNextRow(usize),
-    /// An identifier for a cell in the specified column in the current row in the auxiliary trace.
-    /// The inner value is the index of the column within the trace.
-    AuxTraceCurrentRow(usize),
-    /// An identifier for a cell in the specified column in the next row in the auxiliary trace. The
-    /// inner value is the index of the column within the trace.
-    AuxTraceNextRow(usize),
+    Const(u128),
+    /// An identifier for a cell in the main trace. The first inner value is the index of the column
+    /// within the trace, and the second inner value is the offset of the row from the current row,
+    /// i.e. 0 for the current row, 1 for the next row and -1 for the previous row.
+    MainTraceRow(usize, isize),
+    /// An identifier for a cell in the auxiliary trace. The first inner value is the index of the
+    /// column within the trace, and the second inner value is the offset of the row from the
+    /// current row, i.e. 0 for the current row, 1 for the next row and -1 for the previous row.
+    AuxTraceRow(usize, isize),
     /// An identifier for a periodic value from a specified periodic column. The first inner value
     /// is the index of the periodic column within the declared periodic columns. The second inner
     /// value is the length of the column's periodic cycle. The periodic value made available from
     /// the specified column is based on the current row of the trace.
     PeriodicColumn(usize, usize),
-    /// A random value provided by the verifier. The inner value is the index of this random value
-    /// in the array of all random values.
-    RandomValue(usize),
+    /// A random value provided by the verifier, identified by the index of the auxiliary trace
+    /// segment it was drawn for and its index in the random values of that segment.
+    RandomValue(usize, usize),
+    /// An element of a public input, identified by the name of the public input and the index of
+    /// the element within it. Its value is the same in every row of the trace.
+    PublicInput(String, usize),
     /// Negation operation applied to the node with the specified index.
     Neg(NodeIndex),
     /// Addition operation applied to the nodes with the specified indices.
@@ -274,3 +464,15 @@ pub enum Operation {
     /// value as the power.
     Exp(NodeIndex, usize),
 }
+
+impl Operation {
+    /// Returns a copy of the operation in which the operands of commutative operations are sorted
+    /// by their node indices, so that equivalent operations have the same representation.
+    fn normalize(&self) -> Self {
+        match *self {
+            Operation::Add(lhs, rhs) if rhs < lhs => Operation::Add(rhs, lhs),
+            Operation::Mul(lhs, rhs) if rhs < lhs => Operation::Mul(rhs, lhs),
+            ref op => op.clone(),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use super::{BTreeMap, SemanticError, SymbolTable};
+use parser::ast::{
+    ComprehensionContext, Identifier, ListComprehension, ListFolding, ListValue, TransitionExpr,
+    VectorAccess,
+};
+
+// LIST COMPREHENSIONS
+// ================================================================================================
+
+/// The bindings of a comprehension for a single element of its iterables, mapping the name of each
+/// binding to the identifier of its iterable and the index of the element in the iterable.
+type Bindings<'a> = BTreeMap<&'a str, (&'a Identifier, usize)>;
+
+/// Expands the provided comprehension into one expression for each element of its iterables, in
+/// which the references to the bindings are replaced by references to the elements of the
+/// iterables, e.g. `x' = x + 1 for x in state` is expanded into `state[0]' = state[0] + 1`,
+/// `state[1]' = state[1] + 1` and so on.
+///
+/// # Errors
+/// Returns an error if:
+/// - A binding has the same name as a declared identifier or as another binding of the context.
+/// - An iterable was not declared as a trace column group or as a vector constant.
+/// - The iterables have different lengths.
+pub(super) fn expand_comprehension(
+    symbol_table: &SymbolTable,
+    expr: &TransitionExpr,
+    context: &ComprehensionContext,
+) -> Result<Vec<TransitionExpr>, SemanticError> {
+    let len = validate_context(symbol_table, context)?;
+
+    let exprs = (0..len)
+        .map(|idx| {
+            let bindings = context
+                .iter()
+                .map(|(binding, iterable)| (binding.name(), (iterable, idx)))
+                .collect();
+            substitute(expr, &bindings)
+        })
+        .collect();
+
+    Ok(exprs)
+}
+
+/// Returns the expressions of the elements of the provided list.
+///
+/// # Errors
+/// Returns an error if the list references an invalid iterable or if it is an invalid list
+/// comprehension.
+fn expand_list(
+    symbol_table: &SymbolTable,
+    list: &ListValue,
+) -> Result<Vec<TransitionExpr>, SemanticError> {
+    match list {
+        ListValue::Var(ident) => {
+            let len = symbol_table.get_iterable_len(ident)?;
+            Ok((0..len)
+                .map(|idx| TransitionExpr::VectorAccess(VectorAccess::new(ident.clone(), idx)))
+                .collect())
+        }
+        ListValue::Vector(exprs) => Ok(exprs.clone()),
+        ListValue::Comprehension(comprehension) => {
+            expand_comprehension(symbol_table, comprehension.expr(), comprehension.context())
+        }
+    }
+}
+
+/// Returns a single expression which adds together or multiplies together all of the elements of
+/// the folded list.
+///
+/// # Errors
+/// Returns an error if the list references an invalid iterable or if it is an invalid list
+/// comprehension.
+pub(super) fn fold_list(
+    symbol_table: &SymbolTable,
+    folding: &ListFolding,
+) -> Result<TransitionExpr, SemanticError> {
+    let (list, op, identity): (_, fn(_, _) -> _, _) = match folding {
+        ListFolding::Sum(list) => (list, TransitionExpr::Add, 0),
+        ListFolding::Prod(list) => (list, TransitionExpr::Mul, 1),
+    };
+
+    let expr = expand_list(symbol_table, list)?
+        .into_iter()
+        .reduce(|acc, elem| op(Box::new(acc), Box::new(elem)))
+        .unwrap_or(TransitionExpr::Const(identity));
+
+    Ok(expr)
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Validates the bindings and the iterables of a comprehension context and returns the length of
+/// its iterables.
+fn validate_context(
+    symbol_table: &SymbolTable,
+    context: &ComprehensionContext,
+) -> Result<usize, SemanticError> {
+    let mut len = None;
+    for (idx, (binding, iterable)) in context.iter().enumerate() {
+        // bindings can't shadow declared identifiers or the other bindings of the context.
+        if let Ok(ident_type) = symbol_table.get_type(binding) {
+            return Err(SemanticError::DuplicateIdentifier(
+                format!(
+                    "Cannot bind {} in a comprehension, since it was already defined as a {}",
+                    binding, ident_type
+                ),
+                binding.span(),
+            ));
+        }
+        if context[..idx].iter().any(|(prev, _)| prev == binding) {
+            return Err(SemanticError::DuplicateIdentifier(
+                format!("Cannot bind {} more than once in a comprehension", binding),
+                binding.span(),
+            ));
+        }
+
+        let iterable_len = symbol_table.get_iterable_len(iterable)?;
+        match len {
+            None => len = Some(iterable_len),
+            Some(len) if len != iterable_len => {
+                return Err(SemanticError::InvalidListComprehension(
+                    format!(
+                        "Iterable {} has length {}, but the previous iterables have length {}",
+                        iterable, iterable_len, len
+                    ),
+                    iterable.span(),
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(len.unwrap_or(0))
+}
+
+/// Returns a copy of the expression in which the references to the specified bindings are replaced
+/// by references to the bound elements of their iterables.
+fn substitute(expr: &TransitionExpr, bindings: &Bindings) -> TransitionExpr {
+    match expr {
+        TransitionExpr::Var(ident) => match bindings.get(ident.name()) {
+            Some((iterable, idx)) => {
+                TransitionExpr::VectorAccess(bound_element(ident, iterable, *idx))
+            }
+            None => expr.clone(),
+        },
+        TransitionExpr::Next(ident) => match bindings.get(ident.name()) {
+            Some((iterable, idx)) => {
+                TransitionExpr::NextVectorAccess(bound_element(ident, iterable, *idx))
+            }
+            None => expr.clone(),
+        },
+        TransitionExpr::RowOffset(ident, offset) => match bindings.get(ident.name()) {
+            Some((iterable, idx)) => {
+                TransitionExpr::VectorRowOffset(bound_element(ident, iterable, *idx), *offset)
+            }
+            None => expr.clone(),
+        },
+        TransitionExpr::Add(lhs, rhs) => TransitionExpr::Add(
+            Box::new(substitute(lhs, bindings)),
+            Box::new(substitute(rhs, bindings)),
+        ),
+        TransitionExpr::Sub(lhs, rhs) => TransitionExpr::Sub(
+            Box::new(substitute(lhs, bindings)),
+            Box::new(substitute(rhs, bindings)),
+        ),
+        TransitionExpr::Mul(lhs, rhs) => TransitionExpr::Mul(
+            Box::new(substitute(lhs, bindings)),
+            Box::new(substitute(rhs, bindings)),
+        ),
+        TransitionExpr::Exp(lhs, exp) => {
+            TransitionExpr::Exp(Box::new(substitute(lhs, bindings)), *exp)
+        }
+        TransitionExpr::Neg(expr) => TransitionExpr::Neg(Box::new(substitute(expr, bindings))),
+        TransitionExpr::ListFolding(folding) => {
+            TransitionExpr::ListFolding(substitute_folding(folding, bindings))
+        }
+        TransitionExpr::Const(_)
+        | TransitionExpr::VectorAccess(_)
+        | TransitionExpr::MatrixAccess(_)
+        | TransitionExpr::NextVectorAccess(_)
+        | TransitionExpr::VectorRowOffset(..)
+        | TransitionExpr::Rand(..) => expr.clone(),
+    }
+}
+
+fn substitute_folding(folding: &ListFolding, bindings: &Bindings) -> ListFolding {
+    let substitute_list = |list: &ListValue| match list {
+        ListValue::Var(_) => list.clone(),
+        ListValue::Vector(exprs) => ListValue::Vector(
+            exprs
+                .iter()
+                .map(|expr| substitute(expr, bindings))
+                .collect(),
+        ),
+        ListValue::Comprehension(comprehension) => {
+            // the bindings of a nested comprehension shadow the bindings with the same names.
+            let bindings = bindings
+                .iter()
+                .filter(|(name, _)| {
+                    !comprehension
+                        .context()
+                        .iter()
+                        .any(|(binding, _)| binding.name() == **name)
+                })
+                .map(|(name, element)| (*name, *element))
+                .collect();
+            ListValue::Comprehension(ListComprehension::new(
+                substitute(comprehension.expr(), &bindings),
+                comprehension.context().clone(),
+            ))
+        }
+    };
+
+    match folding {
+        ListFolding::Sum(list) => ListFolding::Sum(substitute_list(list)),
+        ListFolding::Prod(list) => ListFolding::Prod(substitute_list(list)),
+    }
+}
+
+/// Returns a reference to the element of the iterable at the specified index, located at the
+/// reference to the binding so that errors point to the expression which uses it.
+fn bound_element(binding: &Identifier, iterable: &Identifier, idx: usize) -> VectorAccess {
+    VectorAccess::new(
+        Identifier::new(iterable.name().to_string(), binding.span()),
+        idx,
+    )
+}
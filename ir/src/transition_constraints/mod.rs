@@ -1,5 +1,5 @@
-use super::{SemanticError, SymbolTable};
-use parser::ast;
+use super::{BTreeMap, Field, SemanticError, SymbolTable};
+use parser::ast::{self, SourceSpan};
 
 mod degree;
 pub use degree::TransitionConstraintDegree;
@@ -7,16 +7,29 @@ pub use degree::TransitionConstraintDegree;
 mod graph;
 pub use graph::{AlgebraicGraph, NodeIndex, Operation};
 
+mod list_comprehension;
+
+mod optimizer;
+use optimizer::GraphOptimizer;
+
 // CONSTANTS
 // ================================================================================================
 
 pub const MIN_CYCLE_LENGTH: usize = 2;
 
+/// The minimum length of an execution trace accepted by the Winterfell prover.
+pub const MIN_TRACE_LENGTH: usize = 8;
+
+/// The maximum number of transition exemptions for which the last step is guaranteed to be a valid
+/// row, distinct from the first row, in every execution trace. This matches the limit enforced by
+/// Winterfell for traces of the minimum length.
+pub const MAX_TRANSITION_EXEMPTIONS: usize = MIN_TRACE_LENGTH / 2 + 1;
+
 // TRANSITION CONSTRAINTS
 // ================================================================================================
 
-#[derive(PartialEq)]
-enum ConstraintType {
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum ConstraintType {
     Main,
     Auxiliary,
 }
@@ -31,6 +44,18 @@ pub(super) struct TransitionConstraints {
     /// trace in the graph.
     aux_constraints: Vec<NodeIndex>,
 
+    /// The location in the source of each of the transition constraints against the main trace,
+    /// in the same order as the constraints.
+    main_spans: Vec<SourceSpan>,
+
+    /// The location in the source of each of the transition constraints against the auxiliary
+    /// trace, in the same order as the constraints.
+    aux_spans: Vec<SourceSpan>,
+
+    /// The names of the variables declared in the transition constraints sections and the indices
+    /// of the nodes in the graph which represent their values, in the order of declaration.
+    variables: Vec<(String, NodeIndex)>,
+
     /// A directed acyclic graph which represents all of the transition constraints.
     graph: AlgebraicGraph,
 }
@@ -68,31 +93,332 @@ impl TransitionConstraints {
         &self.aux_constraints
     }
 
+    /// Returns the names of all declared variables and the indices of the nodes representing their
+    /// values in the [AlgebraicGraph], in the order of declaration.
+    pub fn variables(&self) -> &[(String, NodeIndex)] {
+        &self.variables
+    }
+
     /// Returns the [AlgebraicGraph] representing all transition constraints.
     pub fn graph(&self) -> &AlgebraicGraph {
         &self.graph
     }
 
+    /// Returns the minimum number of rows at the end of the trace which must be exempt from the
+    /// transition constraints, so that the constraints never reference rows past the end of the
+    /// trace. At least one row is always exempt.
+    pub fn min_num_exemptions(&self) -> usize {
+        self.graph.max_row_offset().max(1)
+    }
+
+    /// Checks that the declared number of transition exemptions is valid for these transition
+    /// constraints and returns it.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The declared number of exemptions is smaller than the minimum number of exemptions
+    ///   required by the transition constraints.
+    /// - There are boundary constraints at the last row, and the declared number of exemptions is
+    ///   greater than [MAX_TRANSITION_EXEMPTIONS], so the last step might not be a valid row.
+    pub(super) fn validate_exemptions(
+        &self,
+        exemptions: &ast::TransitionExemptions,
+        has_last_boundary_constraints: bool,
+    ) -> Result<usize, SemanticError> {
+        let num_exemptions = exemptions.num_exemptions() as usize;
+        let min_num_exemptions = self.min_num_exemptions();
+
+        if num_exemptions < min_num_exemptions {
+            return Err(SemanticError::InvalidTransitionExemptions(
+                format!(
+                    "At least {} transition exemptions are required by the transition constraints, but {} were declared",
+                    min_num_exemptions, num_exemptions
+                ),
+                exemptions.span(),
+            ));
+        }
+
+        if has_last_boundary_constraints && num_exemptions > MAX_TRANSITION_EXEMPTIONS {
+            return Err(SemanticError::InvalidTransitionExemptions(
+                format!(
+                    "At most {} transition exemptions can be declared when boundary constraints are applied to the last row, but {} were declared",
+                    MAX_TRANSITION_EXEMPTIONS, num_exemptions
+                ),
+                exemptions.span(),
+            ));
+        }
+
+        Ok(num_exemptions)
+    }
+
     // --- MUTATORS -------------------------------------------------------------------------------
 
+    /// Simplifies the [AlgebraicGraph] once all of the transition constraints have been added, by
+    /// folding constant subexpressions in the provided field and applying algebraic identities such as
+    /// `x * 1 = x` or `x + 0 = x`.
+    ///
+    /// Constraints which are simplified to zero are always satisfied, so they are removed. Nodes
+    /// which are no longer referenced by any constraint or variable are then removed from the
+    /// graph, as are the variables whose values are no longer referenced.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - A constraint is simplified to a non-zero constant, so it can never be satisfied.
+    /// - All of the constraints against the main trace, or all of the constraints against the
+    ///   auxiliary trace, are always satisfied, so none of them would remain once they are removed.
+    pub(super) fn optimize(&mut self, field: Field) -> Result<(), Vec<SemanticError>> {
+        let optimizer = GraphOptimizer::new(&self.graph, field);
+        let mut errors = Vec::new();
+        let (main_constraints, main_spans) =
+            optimizer.constraints(&self.main_constraints, &self.main_spans, &mut errors);
+        let (aux_constraints, aux_spans) =
+            optimizer.constraints(&self.aux_constraints, &self.aux_spans, &mut errors);
+
+        // Winterfell requires at least one transition constraint against each trace.
+        if errors.is_empty() {
+            for (trace, constraints, spans) in [
+                ("main", &main_constraints, &self.main_spans),
+                ("auxiliary", &aux_constraints, &self.aux_spans),
+            ] {
+                if let (true, Some(span)) = (constraints.is_empty(), spans.first()) {
+                    errors.push(SemanticError::InvalidConstraint(
+                        format!(
+                            "All of the transition constraints of the {} trace are always satisfied, but at least one transition constraint of the {} trace is required",
+                            trace, trace
+                        ),
+                        *span,
+                    ));
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let variables: Vec<(String, NodeIndex)> = self
+            .variables
+            .iter()
+            .map(|(name, index)| (name.clone(), optimizer.index(index)))
+            .collect();
+
+        // variables are only kept if their values are part of a constraint.
+        let roots: Vec<NodeIndex> = main_constraints
+            .iter()
+            .chain(aux_constraints.iter())
+            .copied()
+            .collect();
+        let (graph, indices) = optimizer.into_graph(&roots);
+        let index = |index: &NodeIndex| indices[index.0].expect("constraint node was removed");
+
+        self.main_constraints = main_constraints.iter().map(index).collect();
+        self.aux_constraints = aux_constraints.iter().map(index).collect();
+        self.main_spans = main_spans;
+        self.aux_spans = aux_spans;
+        self.variables = variables
+            .into_iter()
+            .filter_map(|(name, index)| indices[index.0].map(|index| (name, index)))
+            .collect();
+        self.graph = graph;
+
+        Ok(())
+    }
+
+    /// Adds the provided parsed statement of a transition constraints section or of the body of an
+    /// evaluator function.
+    ///
+    /// # Errors
+    /// Returns an error if the statement is invalid.
+    pub(super) fn insert_stmt(
+        &mut self,
+        symbol_table: &mut SymbolTable,
+        stmt: &ast::TransitionStmt,
+    ) -> Result<(), SemanticError> {
+        match stmt {
+            ast::TransitionStmt::Constraint(constraint) => self.insert(symbol_table, constraint),
+            ast::TransitionStmt::ConstraintComprehension(constraint, context) => {
+                self.insert_comprehension(symbol_table, constraint, context)
+            }
+            ast::TransitionStmt::EvaluatorCall(call) => {
+                self.insert_evaluator_call(symbol_table, call)
+            }
+            ast::TransitionStmt::Match(constraint_match) => {
+                self.insert_match(symbol_table, constraint_match)
+            }
+            ast::TransitionStmt::Variable(variable) => self.insert_variable(symbol_table, variable),
+        }
+    }
+
     /// Adds the provided parsed transition constraint to the graph.
     ///
     /// The constraint is turned into a subgraph which is added to the [AlgebraicGraph] (reusing any
     /// existing nodes). The index of its entry node is then saved in the constraints array.
+    ///
+    /// # Errors
+    /// Returns an error if the constraint is invalid or if any of its constants is not an element
+    /// of the field of the AIR.
     pub(super) fn insert(
         &mut self,
         symbol_table: &SymbolTable,
         constraint: &ast::TransitionConstraint,
     ) -> Result<(), SemanticError> {
         let expr = constraint.expr();
+        symbol_table
+            .field()
+            .validate_transition_expr(&expr, constraint.span())?;
+        self.insert_expr(symbol_table, expr, constraint.span())
+    }
+
+    /// Adds one constraint to the graph for each element of the iterables of the provided
+    /// constraint comprehension, in the order of the elements.
+    ///
+    /// # Errors
+    /// Returns an error if the comprehension context is invalid or if any of the expanded
+    /// constraints is invalid.
+    pub(super) fn insert_comprehension(
+        &mut self,
+        symbol_table: &SymbolTable,
+        constraint: &ast::TransitionConstraint,
+        context: &ast::ComprehensionContext,
+    ) -> Result<(), SemanticError> {
+        let expr = constraint.expr();
+        symbol_table
+            .field()
+            .validate_transition_expr(&expr, constraint.span())?;
+        let exprs = list_comprehension::expand_comprehension(symbol_table, &expr, context)?;
+        for expr in exprs {
+            self.insert_expr(symbol_table, expr, constraint.span())?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds the selector-multiplied constraint of each case of the provided match to the graph, in
+    /// the order of the cases.
+    ///
+    /// For an exclusive match, the following constraints are then added for each selector `s`:
+    /// - `s^2 - s = 0`, which enforces that the selector is binary.
+    /// - `s * t = 0` for each selector `t` of a following case, which enforces that at most one
+    ///   of the selectors is non-zero.
+    ///
+    /// # Errors
+    /// Returns an error if any of the constraints or selectors of the match is invalid.
+    fn insert_match(
+        &mut self,
+        symbol_table: &SymbolTable,
+        constraint_match: &ast::ConstraintMatch,
+    ) -> Result<(), SemanticError> {
+        for case in constraint_match.cases() {
+            self.insert(symbol_table, case)?;
+        }
 
+        if constraint_match.is_exclusive() {
+            let selectors: Vec<&ast::TransitionExpr> = constraint_match
+                .cases()
+                .iter()
+                .filter_map(|case| case.selector())
+                .collect();
+            for (i, &selector) in selectors.iter().enumerate() {
+                let selector = Box::new(selector.clone());
+                self.insert_expr(
+                    symbol_table,
+                    ast::TransitionExpr::Sub(
+                        Box::new(ast::TransitionExpr::Exp(selector.clone(), 2)),
+                        selector.clone(),
+                    ),
+                    constraint_match.span(),
+                )?;
+                for &other in selectors[i + 1..].iter() {
+                    self.insert_expr(
+                        symbol_table,
+                        ast::TransitionExpr::Mul(selector.clone(), Box::new(other.clone())),
+                        constraint_match.span(),
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the constraints of the called evaluator function to the graph, with the parameters of
+    /// the evaluator bound to the trace columns passed as arguments. The statements of the body of
+    /// the evaluator are processed in a separate scope, so the variables they declare can't be
+    /// referenced outside of the body.
+    ///
+    /// # Errors
+    /// Returns an error if the call is invalid or if any of the statements of the body of the
+    /// evaluator is invalid.
+    fn insert_evaluator_call(
+        &mut self,
+        symbol_table: &SymbolTable,
+        call: &ast::EvaluatorCall,
+    ) -> Result<(), SemanticError> {
+        let evaluator = symbol_table.get_evaluator(call.ident())?;
+        let mut scope = symbol_table.evaluator_scope(call)?;
+        for stmt in evaluator.stmts() {
+            self.insert_stmt(&mut scope, stmt)?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds the value of the provided parsed variable to the graph and declares the variable in
+    /// the symbol table, so that it can be referenced by the following statements of its section.
+    ///
+    /// The value is turned into a subgraph which is added to the [AlgebraicGraph] (reusing any
+    /// existing nodes), so a variable referenced by several constraints is only evaluated once.
+    ///
+    /// # Errors
+    /// Returns an error if the value of the variable is invalid or if its name was already
+    /// declared.
+    pub(super) fn insert_variable(
+        &mut self,
+        symbol_table: &mut SymbolTable,
+        variable: &ast::TransitionVariable,
+    ) -> Result<(), SemanticError> {
+        symbol_table
+            .field()
+            .validate_transition_expr(variable.value(), variable.span())?;
+        let (constraint_type, entry_index) = self
+            .graph
+            .insert_expr(symbol_table, variable.value().clone())?;
+        symbol_table.insert_transition_variable(variable.ident(), constraint_type, entry_index)?;
+        self.variables
+            .push((variable.name().to_string(), entry_index));
+
+        Ok(())
+    }
+
+    /// Adds the expression of a transition constraint, which must be equal to zero, to the graph
+    /// and saves the index of its entry node and its location in the source in the appropriate
+    /// set of constraints.
+    ///
+    /// A constraint which references rows before the current row is shifted forward, so that its
+    /// earliest row becomes the current row. The rows at the end of the trace which are then
+    /// referenced past its end are covered by the transition exemptions.
+    fn insert_expr(
+        &mut self,
+        symbol_table: &SymbolTable,
+        expr: ast::TransitionExpr,
+        span: SourceSpan,
+    ) -> Result<(), SemanticError> {
         // add it to the transition constraints graph and get its entry index.
-        let (constraint_type, entry_index) = self.graph.insert_expr(symbol_table, expr)?;
+        let (constraint_type, mut entry_index) = self.graph.insert_expr(symbol_table, expr)?;
+        let min_row_offset = self.graph.min_row_offset(&entry_index);
+        if min_row_offset < 0 {
+            entry_index = self.graph.shift_rows(&entry_index, -min_row_offset);
+        }
 
         // add the transition constraint to the appropriate set of constraints.
         match constraint_type {
-            ConstraintType::Main => self.main_constraints.push(entry_index),
-            ConstraintType::Auxiliary => self.aux_constraints.push(entry_index),
+            ConstraintType::Main => {
+                self.main_constraints.push(entry_index);
+                self.main_spans.push(span);
+            }
+            ConstraintType::Auxiliary => {
+                self.aux_constraints.push(entry_index);
+                self.aux_spans.push(span);
+            }
         }
 
         Ok(())
@@ -0,0 +1,247 @@
+use super::{AlgebraicGraph, Field, NodeIndex, Operation, SemanticError, SourceSpan};
+
+// GRAPH OPTIMIZER
+// ================================================================================================
+
+/// Rewrites an [AlgebraicGraph] into an equivalent graph in which constant subexpressions are
+/// folded modulo the modulus of the field of the AIR and the following algebraic identities are applied:
+///
+/// - `x + 0 = x`, `x + (-x) = 0`
+/// - `x * 0 = 0`, `x * 1 = x`, `x * (-1) = -x`
+/// - `-(-x) = x`
+/// - `x^0 = 1`, `x^1 = x`
+///
+/// The nodes of the original graph are simplified in the order of their indices, so the children
+/// of each node are simplified before the node itself. Nodes which are no longer referenced once
+/// the graph is simplified are then removed, by counting the references to each node.
+pub(super) struct GraphOptimizer {
+    /// The field in which the constants are folded.
+    field: Field,
+    /// The simplified graph.
+    graph: AlgebraicGraph,
+    /// The index in the simplified graph of the node equivalent to each node of the original
+    /// graph.
+    indices: Vec<NodeIndex>,
+}
+
+impl GraphOptimizer {
+    // --- CONSTRUCTOR ----------------------------------------------------------------------------
+
+    /// Simplifies all of the nodes of the provided graph, folding the constants in the provided
+    /// field.
+    pub fn new(graph: &AlgebraicGraph, field: Field) -> Self {
+        let mut optimizer = Self {
+            field,
+            graph: AlgebraicGraph::default(),
+            indices: Vec::with_capacity(graph.num_nodes()),
+        };
+        for (_, node) in graph.nodes() {
+            let op = optimizer.map_op(node.op());
+            let index = optimizer.simplify(op);
+            optimizer.indices.push(index);
+        }
+        optimizer
+    }
+
+    // --- ACCESSORS ------------------------------------------------------------------------------
+
+    /// Returns the index in the simplified graph of the node equivalent to the specified node of
+    /// the original graph.
+    pub fn index(&self, index: &NodeIndex) -> NodeIndex {
+        self.indices[index.0]
+    }
+
+    /// Returns the indices in the simplified graph of the specified constraints of the original
+    /// graph and their locations in the source, without the constraints which are simplified to
+    /// zero, since they are always satisfied.
+    ///
+    /// An error is added to the provided errors for each constraint which is simplified to a
+    /// non-zero constant, since it can never be satisfied.
+    pub fn constraints(
+        &self,
+        constraints: &[NodeIndex],
+        spans: &[SourceSpan],
+        errors: &mut Vec<SemanticError>,
+    ) -> (Vec<NodeIndex>, Vec<SourceSpan>) {
+        let mut kept = (Vec::new(), Vec::new());
+        for (index, span) in constraints.iter().zip(spans) {
+            let index = self.index(index);
+            match self.const_value(&index) {
+                Some(0) => {}
+                Some(value) => errors.push(SemanticError::InvalidConstraint(
+                    format!(
+                        "The transition constraint is never satisfied, since it is equal to the non-zero constant {}",
+                        value
+                    ),
+                    *span,
+                )),
+                None => {
+                    kept.0.push(index);
+                    kept.1.push(*span);
+                }
+            }
+        }
+        kept
+    }
+
+    // --- FINALIZATION ---------------------------------------------------------------------------
+
+    /// Returns the simplified graph without the nodes which are not referenced by the specified
+    /// roots, either directly or through other nodes, along with the index in the returned graph
+    /// of each node of the simplified graph which was kept.
+    ///
+    /// The nodes are visited in reverse order, so the reference count of each node is final once
+    /// it is reached: a node which is not referenced is removed, and the references it holds to its
+    /// children are released.
+    pub fn into_graph(self, roots: &[NodeIndex]) -> (AlgebraicGraph, Vec<Option<NodeIndex>>) {
+        let nodes: Vec<&Operation> = self.graph.nodes().map(|(_, node)| node.op()).collect();
+
+        let mut ref_counts = vec![0_usize; nodes.len()];
+        for root in roots {
+            ref_counts[root.0] += 1;
+        }
+        for op in nodes.iter() {
+            for child in children(op) {
+                ref_counts[child.0] += 1;
+            }
+        }
+        for (index, op) in nodes.iter().enumerate().rev() {
+            if ref_counts[index] == 0 {
+                for child in children(op) {
+                    ref_counts[child.0] -= 1;
+                }
+            }
+        }
+
+        // the nodes which are kept are added in the same order, so they remain sorted.
+        let mut graph = AlgebraicGraph::default();
+        let mut indices: Vec<Option<NodeIndex>> = Vec::with_capacity(nodes.len());
+        for (index, op) in nodes.into_iter().enumerate() {
+            if ref_counts[index] == 0 {
+                indices.push(None);
+                continue;
+            }
+            let op = map_children(op, |child| {
+                indices[child.0].expect("a referenced node references a removed node")
+            });
+            indices.push(Some(graph.insert_op(op)));
+        }
+
+        (graph, indices)
+    }
+
+    // --- HELPERS --------------------------------------------------------------------------------
+
+    /// Returns the operation of a node of the original graph with its children replaced by their
+    /// equivalent nodes in the simplified graph.
+    fn map_op(&self, op: &Operation) -> Operation {
+        map_children(op, |child| self.indices[child.0])
+    }
+
+    /// Adds the simplest equivalent of the provided operation, whose children are nodes of the
+    /// simplified graph, to the simplified graph and returns its index.
+    fn simplify(&mut self, op: Operation) -> NodeIndex {
+        let field = self.field;
+        match op {
+            Operation::Neg(value) => {
+                if let Operation::Neg(inner) = self.graph.node(&value).op() {
+                    return *inner;
+                }
+                match self.const_value(&value) {
+                    Some(value) => self.fold(field.neg(value)),
+                    None => self.graph.insert_op(op),
+                }
+            }
+            Operation::Add(lhs, rhs) => match (self.const_value(&lhs), self.const_value(&rhs)) {
+                (Some(l), Some(r)) => self.fold(field.add(l, r)),
+                (Some(0), None) => rhs,
+                (None, Some(0)) => lhs,
+                _ if self.is_negation(&lhs, &rhs) || self.is_negation(&rhs, &lhs) => {
+                    self.graph.insert_op(Operation::Const(0))
+                }
+                _ => self.graph.insert_op(op),
+            },
+            Operation::Mul(lhs, rhs) => match (self.const_value(&lhs), self.const_value(&rhs)) {
+                (Some(l), Some(r)) => self.fold(field.mul(l, r)),
+                (Some(0), _) | (_, Some(0)) => self.graph.insert_op(Operation::Const(0)),
+                (Some(1), _) => rhs,
+                (_, Some(1)) => lhs,
+                (Some(value), _) if value == field.modulus() - 1 => {
+                    self.simplify(Operation::Neg(rhs))
+                }
+                (_, Some(value)) if value == field.modulus() - 1 => {
+                    self.simplify(Operation::Neg(lhs))
+                }
+                _ => self.graph.insert_op(op),
+            },
+            Operation::Exp(base, power) => match (power, self.const_value(&base)) {
+                (0, _) => self.graph.insert_op(Operation::Const(1)),
+                (1, _) => base,
+                (_, Some(value)) => self.fold(field.exp(value, power)),
+                _ => self.graph.insert_op(op),
+            },
+            op => self.graph.insert_op(op),
+        }
+    }
+
+    /// Adds the provided field element, which is the value of the provided operation, to the
+    /// simplified graph. Elements greater than half of the modulus are added as the negation of
+    /// their additive inverse, so that small negative values such as `-1` remain readable in the
+    /// generated code.
+    fn fold(&mut self, value: u128) -> NodeIndex {
+        if value > self.field.modulus() / 2 {
+            let inverse = self
+                .graph
+                .insert_op(Operation::Const(self.field.neg(value)));
+            return self.graph.insert_op(Operation::Neg(inverse));
+        }
+        self.graph.insert_op(Operation::Const(value))
+    }
+
+    /// Returns the value of the specified node of the simplified graph as a field element if it is
+    /// a constant or the negation of a constant, or None otherwise.
+    fn const_value(&self, index: &NodeIndex) -> Option<u128> {
+        let modulus = self.field.modulus();
+        match self.graph.node(index).op() {
+            Operation::Const(value) => Some(*value % modulus),
+            Operation::Neg(inner) => match self.graph.node(inner).op() {
+                Operation::Const(value) => Some(self.field.neg(*value % modulus)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns true if the first node of the simplified graph is the negation of the second one.
+    fn is_negation(&self, index: &NodeIndex, other: &NodeIndex) -> bool {
+        matches!(self.graph.node(index).op(), Operation::Neg(inner) if inner == other)
+    }
+}
+
+/// Returns the children of the provided operation.
+fn children(op: &Operation) -> Vec<NodeIndex> {
+    match op {
+        Operation::Neg(index) | Operation::Exp(index, _) => vec![*index],
+        Operation::Add(lhs, rhs) | Operation::Mul(lhs, rhs) => vec![*lhs, *rhs],
+        _ => vec![],
+    }
+}
+
+/// Returns a copy of the provided operation in which each child is replaced by the result of the
+/// provided function.
+fn map_children(op: &Operation, mut f: impl FnMut(&NodeIndex) -> NodeIndex) -> Operation {
+    match op {
+        Operation::Const(value) => Operation::Const(*value),
+        Operation::MainTraceRow(index, offset) => Operation::MainTraceRow(*index, *offset),
+        Operation::AuxTraceRow(index, offset) => Operation::AuxTraceRow(*index, *offset),
+        Operation::PeriodicColumn(index, cycle_len) => {
+            Operation::PeriodicColumn(*index, *cycle_len)
+        }
+        Operation::RandomValue(segment, index) => Operation::RandomValue(*segment, *index),
+        Operation::PublicInput(name, index) => Operation::PublicInput(name.clone(), *index),
+        Operation::Neg(index) => Operation::Neg(f(index)),
+        Operation::Add(lhs, rhs) => Operation::Add(f(lhs), f(rhs)),
+        Operation::Mul(lhs, rhs) => Operation::Mul(f(lhs), f(rhs)),
+        Operation::Exp(base, power) => Operation::Exp(f(base), *power),
+    }
+}
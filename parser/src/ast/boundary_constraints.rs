@@ -1,34 +1,51 @@
-use super::Identifier;
+use super::{Identifier, MatrixAccess, RandomValueAccess, SourceSpan, VectorAccess};
 use std::fmt::Display;
 
 // BOUNDARY CONSTRAINTS
 // ================================================================================================
 
-/// Stores the boundary constraints to be enforced on the trace column values.
+/// Stores the statements of a boundary constraints section in the order in which they were
+/// declared, which are the boundary constraints to be enforced on the trace column values and the
+/// variables used to describe them.
 #[derive(Debug, PartialEq)]
 pub struct BoundaryConstraints {
-    pub boundary_constraints: Vec<BoundaryConstraint>,
+    pub stmts: Vec<BoundaryStmt>,
 }
 
-/// Stores the expression corresponding to the boundary constraint.
+/// A statement in a boundary constraints section.
 #[derive(Debug, PartialEq)]
+pub enum BoundaryStmt {
+    Constraint(BoundaryConstraint),
+    Variable(BoundaryVariable),
+}
+
+/// Stores the expression corresponding to the boundary constraint.
+#[derive(Debug)]
 pub struct BoundaryConstraint {
-    column: Identifier,
+    column: BoundaryColumn,
     boundary: Boundary,
     value: BoundaryExpr,
+    span: SourceSpan,
 }
 
 impl BoundaryConstraint {
-    pub fn new(column: Identifier, boundary: Boundary, value: BoundaryExpr) -> Self {
+    pub fn new(
+        column: BoundaryColumn,
+        boundary: Boundary,
+        value: BoundaryExpr,
+        span: SourceSpan,
+    ) -> Self {
         Self {
             column,
             boundary,
             value,
+            span,
         }
     }
 
-    pub fn column(&self) -> &str {
-        &self.column.0
+    /// Returns the reference to the column to which the constraint is applied.
+    pub fn column(&self) -> &BoundaryColumn {
+        &self.column
     }
 
     pub fn boundary(&self) -> Boundary {
@@ -39,13 +56,105 @@ impl BoundaryConstraint {
     pub fn value(&self) -> BoundaryExpr {
         self.value.clone()
     }
+
+    /// Returns the location of the whole constraint in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for BoundaryConstraint {
+    fn eq(&self, other: &Self) -> bool {
+        self.column == other.column && self.boundary == other.boundary && self.value == other.value
+    }
+}
+
+/// Reference to the trace column to which a boundary constraint is applied.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BoundaryColumn {
+    /// A column referenced by its name.
+    Named(Identifier),
+    /// A column within a group of trace columns, referenced by the name of the group and the index
+    /// of the column within the group.
+    Indexed(VectorAccess),
+}
+
+impl BoundaryColumn {
+    /// Returns the identifier of the referenced column or group of columns.
+    pub fn ident(&self) -> &Identifier {
+        match self {
+            Self::Named(ident) => ident,
+            Self::Indexed(access) => access.ident(),
+        }
+    }
+
+    /// Returns the location of the reference in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.ident().span()
+    }
+}
+
+impl Display for BoundaryColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Named(ident) => write!(f, "{}", ident),
+            Self::Indexed(access) => write!(f, "{}", access),
+        }
+    }
+}
+
+/// Declaration of a variable in a boundary constraints section, which binds a name to an
+/// expression. A variable can be referenced by the statements which follow it in the same section.
+#[derive(Debug)]
+pub struct BoundaryVariable {
+    name: Identifier,
+    value: BoundaryExpr,
+    span: SourceSpan,
+}
+
+impl BoundaryVariable {
+    pub fn new(name: Identifier, value: BoundaryExpr, span: SourceSpan) -> Self {
+        Self { name, value, span }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.name()
+    }
+
+    /// Returns the identifier under which the variable was declared.
+    pub fn ident(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn value(&self) -> &BoundaryExpr {
+        &self.value
+    }
+
+    /// Returns the location of the whole declaration in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for BoundaryVariable {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value
+    }
 }
 
-/// Describes the type of boundary in the boundary constraint.
+/// Describes the rows of the column to which a boundary constraint is applied.
 #[derive(Debug, Eq, Copy, Clone, PartialEq)]
 pub enum Boundary {
     First,
     Last,
+    /// The row at the specified index, e.g. `a.row(5)`.
+    Row(usize),
+    /// Every `stride`-th row, starting from the row at index `first_row`, e.g. `a.every(8, 3)` for
+    /// the rows 3, 11, 19, and so on. The first row is 0 when it is omitted, e.g. `a.every(8)`.
+    Periodic {
+        first_row: usize,
+        stride: usize,
+    },
 }
 
 impl Display for Boundary {
@@ -53,6 +162,10 @@ impl Display for Boundary {
         match &self {
             Boundary::First => write!(f, "first boundary"),
             Boundary::Last => write!(f, "last boundary"),
+            Boundary::Row(row) => write!(f, "row {}", row),
+            Boundary::Periodic { first_row, stride } => {
+                write!(f, "every {} rows from row {}", stride, first_row)
+            }
         }
     }
 }
@@ -60,15 +173,25 @@ impl Display for Boundary {
 /// Arithmetic expressions for evaluation of boundary constraints.
 #[derive(Debug, PartialEq, Clone)]
 pub enum BoundaryExpr {
-    Const(u64),
-    /// Reference to a public input element, identified by the name of a public input array and the
-    /// index of the cell.
-    PubInput(Identifier, usize),
-    /// Represents a random value provided by the verifier. The inner value is the index of this
-    /// random value in the array of all random values.
-    Rand(usize),
+    Const(u128),
+    /// Reference to a scalar constant or to a variable.
+    Var(Identifier),
+    /// Reference to an element of a vector, which is either a public input array or a vector
+    /// constant.
+    VectorAccess(VectorAccess),
+    /// Reference to an element of a matrix constant.
+    MatrixAccess(MatrixAccess),
+    /// Represents a random value provided by the verifier, identified by its auxiliary trace
+    /// segment and its index in the random values of that segment.
+    Rand(RandomValueAccess),
     Add(Box<BoundaryExpr>, Box<BoundaryExpr>),
     Sub(Box<BoundaryExpr>, Box<BoundaryExpr>),
     Mul(Box<BoundaryExpr>, Box<BoundaryExpr>),
+    /// Division of the first expression by the second, e.g. `a / b`.
+    Div(Box<BoundaryExpr>, Box<BoundaryExpr>),
     Exp(Box<BoundaryExpr>, u64),
+    /// Multiplicative inverse of an expression, e.g. `inv(a)`.
+    Inv(Box<BoundaryExpr>),
+    /// Negation of an expression, e.g. `-a`.
+    Neg(Box<BoundaryExpr>),
 }
@@ -0,0 +1,71 @@
+use super::{Identifier, Literal, SourceSpan};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Declaration of a named constant for an AIR. A constant can be a scalar, a vector or a matrix of
+/// field element values, which can be referenced by name in boundary and transition constraints.
+#[derive(Debug, Eq)]
+pub struct Constant {
+    name: Identifier,
+    value: ConstantType,
+    span: SourceSpan,
+}
+
+impl Constant {
+    pub(crate) fn new(name: Identifier, value: ConstantType, span: SourceSpan) -> Self {
+        Self { name, value, span }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.name()
+    }
+
+    /// Returns the identifier under which the constant was declared.
+    pub fn ident(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn value(&self) -> &ConstantType {
+        &self.value
+    }
+
+    /// Returns the location of the whole constant declaration in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for Constant {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value
+    }
+}
+
+/// The value of a constant. The rows of a matrix are expected to have the same length, but this is
+/// not checked during parsing.
+///
+/// The values of a parsed constant are [Literal]s, which are resolved to field elements once the
+/// field of the AIR is known.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ConstantType<T = Literal> {
+    Scalar(T),
+    Vector(Vec<T>),
+    Matrix(Vec<Vec<T>>),
+}
+
+impl<T> ConstantType<T> {
+    /// Returns a constant with the same shape, whose values are the results of applying the
+    /// provided function to the values of this constant.
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> ConstantType<U> {
+        match self {
+            Self::Scalar(value) => ConstantType::Scalar(f(value)),
+            Self::Vector(values) => ConstantType::Vector(values.iter().map(f).collect()),
+            Self::Matrix(rows) => ConstantType::Matrix(
+                rows.iter()
+                    .map(|row| row.iter().map(&mut f).collect())
+                    .collect(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use super::{Identifier, SourceSpan, TraceColumn, TransitionStmt, VectorAccess};
+use std::fmt;
+
+// EVALUATOR FUNCTIONS
+// ================================================================================================
+
+/// Declaration of an evaluator function, which describes a set of transition constraints over its
+/// trace column parameters. An evaluator is applied to the trace columns of an AIR by calling it
+/// from a transition constraints section, e.g. `enf is_binary([a])`.
+#[derive(Debug, Clone)]
+pub struct EvaluatorFunction {
+    name: Identifier,
+    main_params: Vec<TraceColumn>,
+    aux_params: Vec<TraceColumn>,
+    stmts: Vec<TransitionStmt>,
+    span: SourceSpan,
+}
+
+impl EvaluatorFunction {
+    pub(crate) fn new(
+        name: Identifier,
+        main_params: Vec<TraceColumn>,
+        aux_params: Vec<TraceColumn>,
+        stmts: Vec<TransitionStmt>,
+        span: SourceSpan,
+    ) -> Self {
+        Self {
+            name,
+            main_params,
+            aux_params,
+            stmts,
+            span,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.name()
+    }
+
+    /// Returns the identifier under which the evaluator was declared.
+    pub fn ident(&self) -> &Identifier {
+        &self.name
+    }
+
+    /// Returns the parameters which are bound to columns of the main trace.
+    pub fn main_params(&self) -> &[TraceColumn] {
+        &self.main_params
+    }
+
+    /// Returns the parameters which are bound to columns of the auxiliary trace.
+    pub fn aux_params(&self) -> &[TraceColumn] {
+        &self.aux_params
+    }
+
+    /// Returns the statements of the body of the evaluator in the order in which they were
+    /// declared.
+    pub fn stmts(&self) -> &[TransitionStmt] {
+        &self.stmts
+    }
+
+    /// Returns the location of the whole evaluator declaration in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for EvaluatorFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.main_params == other.main_params
+            && self.aux_params == other.aux_params
+            && self.stmts == other.stmts
+    }
+}
+
+/// A call of an evaluator function in a transition constraints section, which enforces the
+/// constraints of the evaluator on the trace columns passed as arguments.
+#[derive(Debug, Clone)]
+pub struct EvaluatorCall {
+    name: Identifier,
+    main_args: Vec<EvaluatorArg>,
+    aux_args: Vec<EvaluatorArg>,
+    span: SourceSpan,
+}
+
+impl EvaluatorCall {
+    pub fn new(
+        name: Identifier,
+        main_args: Vec<EvaluatorArg>,
+        aux_args: Vec<EvaluatorArg>,
+        span: SourceSpan,
+    ) -> Self {
+        Self {
+            name,
+            main_args,
+            aux_args,
+            span,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.name()
+    }
+
+    /// Returns the identifier of the called evaluator.
+    pub fn ident(&self) -> &Identifier {
+        &self.name
+    }
+
+    /// Returns the arguments which are bound to the main trace parameters of the evaluator.
+    pub fn main_args(&self) -> &[EvaluatorArg] {
+        &self.main_args
+    }
+
+    /// Returns the arguments which are bound to the auxiliary trace parameters of the evaluator.
+    pub fn aux_args(&self) -> &[EvaluatorArg] {
+        &self.aux_args
+    }
+
+    /// Returns the location of the whole call in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for EvaluatorCall {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.main_args == other.main_args
+            && self.aux_args == other.aux_args
+    }
+}
+
+/// An argument of an evaluator call, which references trace columns declared in the
+/// `trace_columns` section.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum EvaluatorArg {
+    /// A single column or all of the columns of a group, referenced by name.
+    Named(Identifier),
+    /// A column within a group of trace columns, referenced by the name of the group and the index
+    /// of the column within the group.
+    Indexed(VectorAccess),
+}
+
+impl EvaluatorArg {
+    /// Returns the identifier of the referenced column or group.
+    pub fn ident(&self) -> &Identifier {
+        match self {
+            Self::Named(ident) => ident,
+            Self::Indexed(access) => access.ident(),
+        }
+    }
+
+    /// Returns the location of the argument in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.ident().span()
+    }
+}
+
+impl fmt::Display for EvaluatorArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Named(ident) => write!(f, "{}", ident),
+            Self::Indexed(access) => write!(f, "{}", access),
+        }
+    }
+}
@@ -1,17 +1,31 @@
 use std::fmt;
 
+pub use diagnostics::{SourceLocation, SourceSpan};
+
+pub mod constants;
+pub use constants::{Constant, ConstantType};
+
 pub mod pub_inputs;
 pub use pub_inputs::PublicInput;
 
 pub mod periodic_columns;
 pub use periodic_columns::PeriodicColumn;
 
+pub mod random_values;
+pub use random_values::RandomValues;
+
 pub mod boundary_constraints;
 pub use boundary_constraints::*;
 
 pub mod transition_constraints;
 pub use transition_constraints::*;
 
+pub mod evaluator_functions;
+pub use evaluator_functions::*;
+
+pub mod modules;
+pub use modules::*;
+
 // AST
 // ================================================================================================
 
@@ -20,48 +34,315 @@ pub use transition_constraints::*;
 pub struct Source(pub Vec<SourceSection>);
 
 /// Source is divided into SourceSections.
-/// There are 6 types of Source Sections:
+/// There are 12 types of Source Sections:
 /// - AirDef: Name of the air constraints module.
+/// - Field: Name of the field over which the AIR is defined, e.g. `f64`. This section is optional.
+/// - Import: Constants and evaluator functions imported from a library module, e.g.
+///   `use bitwise::*`. This section is optional and can be repeated.
+/// - Constants: Named scalar, vector and matrix constants which can be used in the constraints.
+///   This section is optional.
 /// - TraceCols: Trace Columns representing columns of the execution trace.
 /// - PublicInputs: Public inputs are each represented by a fixed-size array. At least one public
 ///   input is required, but there is no limit to the number of public inputs that can be specified.
 /// - PeriodicColumns: Periodic columns are each represented by a fixed-size array with all of its
 ///   elements specified. The array length is expected to be a power of 2, but this is not checked
 ///   during parsing.
+/// - RandomValues: The number of random values provided by the verifier for each segment of the
+///   auxiliary trace, optionally with a name bound to each of them. This section is optional.
 /// - BoundaryConstraints: Boundary Constraints to be enforced on the boundaries of columns defined
 ///   in the TraceCols section. Currently there are two types of boundaries, First and Last
 ///   representing the first and last rows of the column.
 /// - TransitionConstraints: Transition Constraints to be enforced on the trace columns defined
 ///   in the TraceCols section.
+/// - TransitionExemptions: The number of rows at the end of the trace against which the transition
+///   constraints are not enforced. This section is optional.
+/// - EvaluatorFunction: A set of transition constraints over the trace columns passed to it as
+///   parameters, which can be enforced by calling it from the TransitionConstraints section.
 #[derive(Debug, PartialEq)]
 pub enum SourceSection {
     AirDef(Identifier),
+    Field(Identifier),
+    Import(Import),
+    Constants(Vec<Constant>),
     TraceCols(TraceCols),
     PublicInputs(Vec<PublicInput>),
     PeriodicColumns(Vec<PeriodicColumn>),
+    RandomValues(Vec<RandomValues>),
     BoundaryConstraints(BoundaryConstraints),
     TransitionConstraints(TransitionConstraints),
+    TransitionExemptions(TransitionExemptions),
+    EvaluatorFunction(EvaluatorFunction),
 }
 
 // TRACE
 // ================================================================================================
 
-/// [TraceCols] contains the main and auxiliary trace columns of the execution trace.
-#[derive(Debug, Eq, PartialEq)]
+/// [TraceCols] contains the main trace columns and the segments of auxiliary trace columns of the
+/// execution trace.
+#[derive(Debug, Eq)]
 pub struct TraceCols {
-    pub main_cols: Vec<Identifier>,
-    pub aux_cols: Vec<Identifier>,
+    pub main_cols: Vec<TraceColumn>,
+    pub aux_segments: Vec<AuxSegment>,
+    pub span: SourceSpan,
+}
+
+impl PartialEq for TraceCols {
+    fn eq(&self, other: &Self) -> bool {
+        self.main_cols == other.main_cols && self.aux_segments == other.aux_segments
+    }
+}
+
+/// Declaration of a segment of the auxiliary trace, e.g. `aux bus: [p, q]`. The segments are built
+/// by the prover in the order of their declaration, each using its own random values provided by
+/// the verifier. The name of a segment is optional, and is used to access its random values.
+#[derive(Debug, Clone, Eq)]
+pub struct AuxSegment {
+    name: Option<Identifier>,
+    cols: Vec<TraceColumn>,
+    span: SourceSpan,
+}
+
+impl AuxSegment {
+    pub(crate) fn new(name: Option<Identifier>, cols: Vec<TraceColumn>, span: SourceSpan) -> Self {
+        Self { name, cols, span }
+    }
+
+    /// Returns the identifier under which the segment was declared, or None if it is unnamed.
+    pub fn ident(&self) -> Option<&Identifier> {
+        self.name.as_ref()
+    }
+
+    /// Returns the columns declared in the segment.
+    pub fn cols(&self) -> &[TraceColumn] {
+        &self.cols
+    }
+
+    /// Returns the location of the whole segment declaration in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for AuxSegment {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.cols == other.cols
+    }
+}
+
+/// Declaration of a single trace column, or of a group of consecutive trace columns.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TraceColumn {
+    /// A single column, which is referenced by its name.
+    Single(Identifier),
+    /// A group of the specified number of columns, which are referenced by the name of the group
+    /// and the index of the column within the group, e.g. `state[0]`.
+    Group(Identifier, usize),
+}
+
+impl TraceColumn {
+    pub fn name(&self) -> &str {
+        self.ident().name()
+    }
+
+    /// Returns the identifier under which the column or the group was declared.
+    pub fn ident(&self) -> &Identifier {
+        match self {
+            Self::Single(ident) | Self::Group(ident, _) => ident,
+        }
+    }
+
+    /// Returns the number of declared columns, which is 1 for a single column.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Single(_) => 1,
+            Self::Group(_, size) => *size,
+        }
+    }
 }
 
 // SHARED ATOMIC TYPES
 // ================================================================================================
 
-/// [Identifier] is used to represent variable names.
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
-pub struct Identifier(pub String);
+/// [Identifier] is used to represent variable names. Identifiers are compared and ordered by name
+/// only, regardless of where in the source they were found.
+#[derive(Debug, Clone)]
+pub struct Identifier {
+    name: String,
+    span: SourceSpan,
+}
+
+impl Identifier {
+    pub fn new(name: String, span: SourceSpan) -> Self {
+        Self { name, span }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the location of this identifier in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for Identifier {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Identifier {}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Identifier {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.name.cmp(&other.name)
+    }
+}
 
 impl fmt::Display for Identifier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", &self.0)
+        write!(f, "{}", &self.name)
+    }
+}
+
+/// A literal value of a constant or of a periodic column, which must be an element of the field of
+/// the AIR. A literal relative to the modulus `p` of the field, e.g. `p - 1`, can only be resolved
+/// once the field is known.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Literal {
+    Int(u128),
+    /// The modulus of the field minus the specified offset, e.g. `p - 1`.
+    ModulusMinus(u128),
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{}", value),
+            Self::ModulusMinus(offset) => write!(f, "p - {}", offset),
+        }
+    }
+}
+
+/// [VectorAccess] is used to represent an element inside a vector, e.g. a public input, a vector
+/// constant or a group of trace columns, identified by the name of the vector and the index of the
+/// element.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VectorAccess {
+    name: Identifier,
+    idx: usize,
+}
+
+impl VectorAccess {
+    pub fn new(name: Identifier, idx: usize) -> Self {
+        Self { name, idx }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.name()
+    }
+
+    /// Returns the identifier of the accessed vector.
+    pub fn ident(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn idx(&self) -> usize {
+        self.idx
+    }
+}
+
+/// [MatrixAccess] is used to represent an element inside a matrix constant, identified by the name
+/// of the matrix and the indices of the row and the column of the element.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MatrixAccess {
+    name: Identifier,
+    row_idx: usize,
+    col_idx: usize,
+}
+
+impl fmt::Display for VectorAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]", self.name, self.idx)
+    }
+}
+
+/// [RandomValueAccess] is used to represent a random value provided by the verifier, identified by
+/// the name of the auxiliary trace segment it was drawn for, or None for the first segment, and
+/// its index in the random values of that segment, e.g. `$rand[0]` or `$rand.bus[1]`.
+#[derive(Debug, Clone, Eq)]
+pub struct RandomValueAccess {
+    segment: Option<Identifier>,
+    idx: usize,
+    span: SourceSpan,
+}
+
+impl RandomValueAccess {
+    pub fn new(segment: Option<Identifier>, idx: usize, span: SourceSpan) -> Self {
+        Self { segment, idx, span }
+    }
+
+    /// Returns the identifier of the auxiliary trace segment of the random value, or None for the
+    /// first segment.
+    pub fn segment(&self) -> Option<&Identifier> {
+        self.segment.as_ref()
+    }
+
+    pub fn idx(&self) -> usize {
+        self.idx
+    }
+
+    /// Returns the location of this access in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for RandomValueAccess {
+    fn eq(&self, other: &Self) -> bool {
+        self.segment == other.segment && self.idx == other.idx
+    }
+}
+
+impl fmt::Display for RandomValueAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.segment {
+            Some(segment) => write!(f, "$rand.{}[{}]", segment, self.idx),
+            None => write!(f, "$rand[{}]", self.idx),
+        }
+    }
+}
+
+impl MatrixAccess {
+    pub fn new(name: Identifier, row_idx: usize, col_idx: usize) -> Self {
+        Self {
+            name,
+            row_idx,
+            col_idx,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.name()
+    }
+
+    /// Returns the identifier of the accessed matrix.
+    pub fn ident(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn row_idx(&self) -> usize {
+        self.row_idx
+    }
+
+    pub fn col_idx(&self) -> usize {
+        self.col_idx
     }
 }
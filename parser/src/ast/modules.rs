@@ -0,0 +1,72 @@
+use super::{Constant, EvaluatorFunction, Identifier, SourceSpan};
+
+// LIBRARY MODULES
+// ================================================================================================
+
+/// [Library] is the root node of the AST representing a library module, i.e. a file starting with
+/// `mod <name>` which declares constants and evaluator functions to be imported by other modules.
+#[derive(Debug, PartialEq)]
+pub struct Library {
+    pub name: Identifier,
+    pub sections: Vec<LibrarySection>,
+}
+
+/// The sections which can be declared in a library module. Unlike the root module, a library
+/// can't declare trace columns, public inputs, periodic columns or constraints.
+#[derive(Debug, PartialEq)]
+pub enum LibrarySection {
+    Import(Import),
+    Constants(Vec<Constant>),
+    EvaluatorFunction(EvaluatorFunction),
+}
+
+// IMPORTS
+// ================================================================================================
+
+/// Declaration of the items imported from a library module, e.g. `use bitwise::*` or
+/// `use bitwise::is_binary`.
+#[derive(Debug, Clone, Eq)]
+pub struct Import {
+    module: Identifier,
+    items: ImportItems,
+    span: SourceSpan,
+}
+
+impl Import {
+    pub fn new(module: Identifier, items: ImportItems, span: SourceSpan) -> Self {
+        Self {
+            module,
+            items,
+            span,
+        }
+    }
+
+    /// Returns the identifier of the module from which the items are imported.
+    pub fn module(&self) -> &Identifier {
+        &self.module
+    }
+
+    pub fn items(&self) -> &ImportItems {
+        &self.items
+    }
+
+    /// Returns the location of the whole import declaration in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for Import {
+    fn eq(&self, other: &Self) -> bool {
+        self.module == other.module && self.items == other.items
+    }
+}
+
+/// The items imported by an [Import] declaration.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ImportItems {
+    /// All of the constants and evaluator functions declared in the module, i.e. `module::*`.
+    All,
+    /// A single constant or evaluator function declared in the module, i.e. `module::item`.
+    Single(Identifier),
+}
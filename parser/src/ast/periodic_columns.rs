@@ -1,4 +1,4 @@
-use super::Identifier;
+use super::{Identifier, Literal, SourceSpan};
 
 // PERIODIC COLUMNS
 // ================================================================================================
@@ -7,27 +7,43 @@ use super::Identifier;
 /// of values. The values declared for the periodic column should be the cycle of values that will
 /// be repeated. The length of the values vector is expected to be a power of 2 with a minimum
 /// length of 2, but this is not enforced here.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq)]
 pub struct PeriodicColumn {
     name: Identifier,
-    values: Vec<u64>,
+    values: Vec<Literal>,
+    span: SourceSpan,
 }
 
 impl PeriodicColumn {
-    pub(crate) fn new(name: Identifier, values: Vec<u64>) -> Self {
-        Self { name, values }
+    pub(crate) fn new(name: Identifier, values: Vec<Literal>, span: SourceSpan) -> Self {
+        Self { name, values, span }
     }
 
     pub fn name(&self) -> &str {
-        let Identifier(name) = &self.name;
-        name
+        self.name.name()
+    }
+
+    /// Returns the identifier under which the periodic column was declared.
+    pub fn ident(&self) -> &Identifier {
+        &self.name
     }
 
     pub fn period(&self) -> usize {
         self.values.len()
     }
 
-    pub fn values(&self) -> &[u64] {
+    pub fn values(&self) -> &[Literal] {
         &self.values
     }
+
+    /// Returns the location of the whole periodic column declaration in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for PeriodicColumn {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.values == other.values
+    }
 }
@@ -1,30 +1,47 @@
-use super::Identifier;
+use super::{Identifier, SourceSpan};
 
 // PUBLIC INPUTS
 // ================================================================================================
 
 /// Declaration of a public input for an AIR. Public inputs are represented by a named identifier
 /// which is used to identify a fixed size array of length `size`.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq)]
 pub struct PublicInput {
     name: Identifier,
     size: usize,
+    span: SourceSpan,
 }
 
 impl PublicInput {
-    pub(crate) fn new(name: Identifier, size: u64) -> Self {
+    pub(crate) fn new(name: Identifier, size: u64, span: SourceSpan) -> Self {
         Self {
             name,
             size: size as usize,
+            span,
         }
     }
 
     pub fn name(&self) -> &str {
-        let Identifier(name) = &self.name;
-        name
+        self.name.name()
+    }
+
+    /// Returns the identifier under which the public input was declared.
+    pub fn ident(&self) -> &Identifier {
+        &self.name
     }
 
     pub fn size(&self) -> usize {
         self.size
     }
+
+    /// Returns the location of the whole public input declaration in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for PublicInput {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.size == other.size
+    }
 }
@@ -0,0 +1,70 @@
+use super::{Identifier, SourceSpan};
+
+// RANDOM VALUES
+// ================================================================================================
+
+/// Declaration of the random values provided by the verifier for a segment of the auxiliary trace,
+/// which is identified by its name, or None for the first segment. The random values are either
+/// declared by their number, e.g. `$rand: [4]`, or by binding a name to each of them, e.g.
+/// `$rand.bus: [alpha, beta]`.
+#[derive(Debug, Eq)]
+pub struct RandomValues {
+    segment: Option<Identifier>,
+    size: usize,
+    bindings: Vec<Identifier>,
+    span: SourceSpan,
+}
+
+impl RandomValues {
+    /// Returns a declaration of the specified number of random values without names.
+    pub(crate) fn new(segment: Option<Identifier>, size: u64, span: SourceSpan) -> Self {
+        Self {
+            segment,
+            size: size as usize,
+            bindings: Vec::new(),
+            span,
+        }
+    }
+
+    /// Returns a declaration of one random value for each of the provided names.
+    pub(crate) fn with_bindings(
+        segment: Option<Identifier>,
+        bindings: Vec<Identifier>,
+        span: SourceSpan,
+    ) -> Self {
+        Self {
+            segment,
+            size: bindings.len(),
+            bindings,
+            span,
+        }
+    }
+
+    /// Returns the identifier of the auxiliary trace segment for which the random values are
+    /// declared, or None for the first segment.
+    pub fn segment(&self) -> Option<&Identifier> {
+        self.segment.as_ref()
+    }
+
+    /// Returns the number of declared random values.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the names bound to the random values, in the order of their indices, or an empty
+    /// slice if the random values are only declared by their number.
+    pub fn bindings(&self) -> &[Identifier] {
+        &self.bindings
+    }
+
+    /// Returns the location of the whole declaration in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for RandomValues {
+    fn eq(&self, other: &Self) -> bool {
+        self.segment == other.segment && self.size == other.size && self.bindings == other.bindings
+    }
+}
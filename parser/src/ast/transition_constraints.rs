@@ -1,44 +1,288 @@
-use super::Identifier;
+use super::{EvaluatorCall, Identifier, MatrixAccess, RandomValueAccess, SourceSpan, VectorAccess};
 
 // TRANSITION CONSTRAINTS
 // ================================================================================================
 
-/// Stores the transition constraints to be enforced on the trace column values.
+/// Stores the statements of a transition constraints section in the order in which they were
+/// declared, which are the transition constraints to be enforced on the trace column values and
+/// the variables used to describe them.
 #[derive(Debug, PartialEq)]
 pub struct TransitionConstraints {
-    pub transition_constraints: Vec<TransitionConstraint>,
+    pub stmts: Vec<TransitionStmt>,
 }
 
-/// Stores the expression corresponding to the transition constraint.
+/// A statement in a transition constraints section.
 #[derive(Debug, PartialEq, Clone)]
+pub enum TransitionStmt {
+    Constraint(TransitionConstraint),
+    /// A transition constraint which is enforced once for each element of the iterables in its
+    /// comprehension context, e.g. `enf x' = x + 1 for x in state`.
+    ConstraintComprehension(TransitionConstraint, ComprehensionContext),
+    /// A call of an evaluator function, which enforces the constraints of the evaluator on the
+    /// trace columns passed as arguments.
+    EvaluatorCall(EvaluatorCall),
+    /// A set of transition constraints which are gated by different selectors, e.g.
+    /// `enf match { case s0: a' = a + 1, case s1: a' = a }`.
+    Match(ConstraintMatch),
+    Variable(TransitionVariable),
+}
+
+/// Stores the expression corresponding to the transition constraint.
+#[derive(Debug, Clone)]
 pub struct TransitionConstraint {
     lhs: TransitionExpr,
     rhs: TransitionExpr,
+    selector: Option<TransitionExpr>,
+    span: SourceSpan,
 }
 
 impl TransitionConstraint {
-    pub fn new(lhs: TransitionExpr, rhs: TransitionExpr) -> Self {
-        Self { lhs, rhs }
+    pub fn new(lhs: TransitionExpr, rhs: TransitionExpr, span: SourceSpan) -> Self {
+        Self {
+            lhs,
+            rhs,
+            selector: None,
+            span,
+        }
+    }
+
+    /// Returns a transition constraint which is only enforced when the selector is non-zero, e.g.
+    /// `enf a' = a + 1 when s`.
+    pub fn conditional(
+        lhs: TransitionExpr,
+        rhs: TransitionExpr,
+        selector: TransitionExpr,
+        span: SourceSpan,
+    ) -> Self {
+        Self {
+            lhs,
+            rhs,
+            selector: Some(selector),
+            span,
+        }
+    }
+
+    /// Returns the selector of the constraint, or None if the constraint is always enforced.
+    pub fn selector(&self) -> Option<&TransitionExpr> {
+        self.selector.as_ref()
+    }
+
+    /// Returns the location of the whole constraint in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
     }
 
     /// Clones the left and right internal expressions and creates a single new expression that
-    /// represents the transition constraint when it is equal to zero.
+    /// represents the transition constraint when it is equal to zero. The expression of a
+    /// constraint with a selector is multiplied by the selector, so that it is equal to zero
+    /// whenever the selector is zero.
     pub fn expr(&self) -> TransitionExpr {
-        TransitionExpr::Sub(Box::new(self.lhs.clone()), Box::new(self.rhs.clone()))
+        let expr = TransitionExpr::Sub(Box::new(self.lhs.clone()), Box::new(self.rhs.clone()));
+        match &self.selector {
+            Some(selector) => TransitionExpr::Mul(Box::new(selector.clone()), Box::new(expr)),
+            None => expr,
+        }
+    }
+}
+
+impl PartialEq for TransitionConstraint {
+    fn eq(&self, other: &Self) -> bool {
+        self.lhs == other.lhs && self.rhs == other.rhs && self.selector == other.selector
+    }
+}
+
+/// A set of transition constraints, each of which is only enforced when its selector is
+/// non-zero. An exclusive match additionally enforces that its selectors are binary and that at
+/// most one of them is non-zero in each row.
+#[derive(Debug, Clone)]
+pub struct ConstraintMatch {
+    cases: Vec<TransitionConstraint>,
+    exclusive: bool,
+    span: SourceSpan,
+}
+
+impl ConstraintMatch {
+    pub fn new(cases: Vec<TransitionConstraint>, exclusive: bool, span: SourceSpan) -> Self {
+        Self {
+            cases,
+            exclusive,
+            span,
+        }
+    }
+
+    /// Returns the constraints of the match, each of which has a selector.
+    pub fn cases(&self) -> &[TransitionConstraint] {
+        &self.cases
+    }
+
+    /// Returns true if the selectors of the match must be binary and mutually exclusive.
+    pub fn is_exclusive(&self) -> bool {
+        self.exclusive
+    }
+
+    /// Returns the location of the whole match in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for ConstraintMatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.cases == other.cases && self.exclusive == other.exclusive
+    }
+}
+
+/// Declaration of a variable in a transition constraints section, which binds a name to an
+/// expression. A variable can be referenced by the statements which follow it in the same section.
+#[derive(Debug, Clone)]
+pub struct TransitionVariable {
+    name: Identifier,
+    value: TransitionExpr,
+    span: SourceSpan,
+}
+
+impl TransitionVariable {
+    pub fn new(name: Identifier, value: TransitionExpr, span: SourceSpan) -> Self {
+        Self { name, value, span }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.name()
+    }
+
+    /// Returns the identifier under which the variable was declared.
+    pub fn ident(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn value(&self) -> &TransitionExpr {
+        &self.value
+    }
+
+    /// Returns the location of the whole declaration in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for TransitionVariable {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value
+    }
+}
+
+/// Stores the number of rows at the end of the execution trace against which the transition
+/// constraints are not enforced.
+#[derive(Debug, Clone, Eq)]
+pub struct TransitionExemptions {
+    num_exemptions: u64,
+    span: SourceSpan,
+}
+
+impl TransitionExemptions {
+    pub fn new(num_exemptions: u64, span: SourceSpan) -> Self {
+        Self {
+            num_exemptions,
+            span,
+        }
+    }
+
+    pub fn num_exemptions(&self) -> u64 {
+        self.num_exemptions
+    }
+
+    /// Returns the location of the declaration in the source.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl PartialEq for TransitionExemptions {
+    fn eq(&self, other: &Self) -> bool {
+        self.num_exemptions == other.num_exemptions
     }
 }
 
 /// Arithmetic expressions for evaluation of transition constraints.
 #[derive(Debug, PartialEq, Clone)]
 pub enum TransitionExpr {
-    Const(u64),
+    Const(u128),
     Var(Identifier),
+    /// Reference to an element of a vector, such as a vector constant or a group of trace columns.
+    VectorAccess(VectorAccess),
+    /// Reference to an element of a matrix constant.
+    MatrixAccess(MatrixAccess),
     Next(Identifier),
-    /// Represents a random value provided by the verifier. The inner value is the index of this
-    /// random value in the array of all random values.
-    Rand(usize),
+    /// Reference to the next row of a column within a group of trace columns.
+    NextVectorAccess(VectorAccess),
+    /// Reference to the row of a trace column at the specified offset from the current row, such
+    /// as `a''`, `a[+2]` or `a[-1]`, where a negative offset is a previous row.
+    RowOffset(Identifier, isize),
+    /// Reference to the row of a column within a group of trace columns at the specified offset
+    /// from the current row, such as `b[1]''`, `b[1][+2]` or `b[1][-1]`.
+    VectorRowOffset(VectorAccess, isize),
+    /// Represents a random value provided by the verifier, identified by its auxiliary trace
+    /// segment and its index in the random values of that segment.
+    Rand(RandomValueAccess),
     Add(Box<TransitionExpr>, Box<TransitionExpr>),
     Sub(Box<TransitionExpr>, Box<TransitionExpr>),
     Mul(Box<TransitionExpr>, Box<TransitionExpr>),
     Exp(Box<TransitionExpr>, u64),
+    /// Negation of an expression, e.g. `-a`.
+    Neg(Box<TransitionExpr>),
+    /// Folding of the elements of a list into a single value.
+    ListFolding(ListFolding),
+}
+
+// LIST COMPREHENSIONS
+// ================================================================================================
+
+/// The bindings of a comprehension, each of which is a pair of the identifier bound to the
+/// elements of an iterable and the identifier of the iterable, such as `(x, y)` and `(a, b)` in
+/// `for (x, y) in (a, b)`. All of the iterables are expected to have the same length, but this is
+/// not checked during parsing.
+pub type ComprehensionContext = Vec<(Identifier, Identifier)>;
+
+/// Folding of the elements of a list into a single value, by adding them all together or by
+/// multiplying them all together.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ListFolding {
+    Sum(ListValue),
+    Prod(ListValue),
+}
+
+/// A list of transition expressions, which can only be used as the value of a [ListFolding].
+#[derive(Debug, PartialEq, Clone)]
+pub enum ListValue {
+    /// The elements of a vector constant or of a group of trace columns.
+    Var(Identifier),
+    /// A list of expressions, e.g. `[a, b * c]`.
+    Vector(Vec<TransitionExpr>),
+    /// A list comprehension, e.g. `[x * y for (x, y) in (a, b)]`.
+    Comprehension(ListComprehension),
+}
+
+/// A list comprehension, which evaluates its expression once for each element of the iterables in
+/// its context.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ListComprehension {
+    expr: Box<TransitionExpr>,
+    context: ComprehensionContext,
+}
+
+impl ListComprehension {
+    pub fn new(expr: TransitionExpr, context: ComprehensionContext) -> Self {
+        Self {
+            expr: Box::new(expr),
+            context,
+        }
+    }
+
+    pub fn expr(&self) -> &TransitionExpr {
+        &self.expr
+    }
+
+    pub fn context(&self) -> &ComprehensionContext {
+        &self.context
+    }
 }
@@ -1,9 +1,11 @@
-use crate::lexer::Span;
+use crate::{ast::SourceSpan, lexer::Span, lexer::Token};
+use diagnostics::{Diagnostic, Label, ToDiagnostic};
+use lalrpop_util::ErrorRecovery;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Error {
     ScanError(Span),
-    ParseError(ParseError),
+    ParseError(ParseError, SourceSpan),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -11,4 +13,192 @@ pub enum ParseError {
     InvalidInt(String),
     InvalidTraceCols(String),
     MissingMainTraceCols(String),
+    InvalidListComprehension(String),
+    InvalidTransitionExpr(String),
+    InvalidModulusLiteral(String),
+    InvalidBoundary(String),
+}
+
+impl ToDiagnostic for Error {
+    fn to_diagnostic(&self) -> Diagnostic {
+        match self {
+            Self::ScanError(span) => Diagnostic::error("invalid character")
+                .with_code("E0001")
+                .with_label(
+                    Label::primary(SourceSpan::new(span.start, span.end))
+                        .with_message("this character is not allowed here"),
+                ),
+            Self::ParseError(err, span) => {
+                let (code, msg) = match err {
+                    ParseError::InvalidInt(msg) => ("E0010", msg),
+                    ParseError::InvalidTraceCols(msg) => ("E0011", msg),
+                    ParseError::MissingMainTraceCols(msg) => ("E0012", msg),
+                    ParseError::InvalidListComprehension(msg) => ("E0013", msg),
+                    ParseError::InvalidTransitionExpr(msg) => ("E0014", msg),
+                    ParseError::InvalidModulusLiteral(msg) => ("E0015", msg),
+                    ParseError::InvalidBoundary(msg) => ("E0016", msg),
+                };
+                let diagnostic = Diagnostic::error(msg)
+                    .with_code(code)
+                    .with_label(Label::primary(*span));
+                match err {
+                    ParseError::InvalidInt(_) => diagnostic.with_note(
+                        "values must fit in an unsigned 128-bit integer, and sizes, indices and \
+                        exponents in an unsigned 64-bit integer",
+                    ),
+                    ParseError::InvalidTransitionExpr(_) => diagnostic.with_note(
+                        "division and inversion are only supported in boundary constraints, whose \
+                        values are computed once by the verifier",
+                    ),
+                    ParseError::InvalidModulusLiteral(_) => diagnostic.with_note(
+                        "values relative to the modulus of the field are written as `p - k`, e.g. \
+                        `p - 1`",
+                    ),
+                    ParseError::InvalidBoundary(_) => diagnostic.with_note(
+                        "a boundary is one of `first`, `last`, `row(i)` or \
+                        `every(stride)`/`every(stride, first_row)`",
+                    ),
+                    _ => diagnostic,
+                }
+            }
+        }
+    }
+}
+
+/// Records an error found by a grammar action, so that parsing can continue and report any
+/// further errors in the source.
+pub(crate) fn push_user_error(errors: &mut Vec<ErrorRecovery<usize, Token, Error>>, error: Error) {
+    errors.push(ErrorRecovery {
+        error: lalrpop_util::ParseError::User { error },
+        dropped_tokens: Vec::new(),
+    });
+}
+
+// SYNTAX ERROR
+// ================================================================================================
+
+/// An error found while parsing an AirScript source, either while scanning the source into tokens
+/// or while parsing the tokens into an AST.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SyntaxError(lalrpop_util::ParseError<usize, Token, Error>);
+
+impl SyntaxError {
+    /// Returns the underlying error returned by the generated parser.
+    pub fn inner(&self) -> &lalrpop_util::ParseError<usize, Token, Error> {
+        &self.0
+    }
+}
+
+impl From<lalrpop_util::ParseError<usize, Token, Error>> for SyntaxError {
+    fn from(err: lalrpop_util::ParseError<usize, Token, Error>) -> Self {
+        Self(err)
+    }
+}
+
+impl ToDiagnostic for SyntaxError {
+    fn to_diagnostic(&self) -> Diagnostic {
+        match &self.0 {
+            lalrpop_util::ParseError::InvalidToken { location } => {
+                Diagnostic::error("invalid token")
+                    .with_code("E0001")
+                    .with_label(Label::primary(SourceSpan::new(*location, *location)))
+            }
+            lalrpop_util::ParseError::UnrecognizedEOF { location, expected } => {
+                Diagnostic::error("unexpected end of file")
+                    .with_code("E0002")
+                    .with_label(
+                        Label::primary(SourceSpan::new(*location, *location))
+                            .with_message("the source ended here"),
+                    )
+                    .with_note(expected_tokens_note(expected))
+            }
+            lalrpop_util::ParseError::UnrecognizedToken {
+                token: (start, token, end),
+                expected,
+            } => Diagnostic::error(format!("unexpected token `{}`", token))
+                .with_code("E0003")
+                .with_label(
+                    Label::primary(SourceSpan::new(*start, *end))
+                        .with_message("this token was not expected here"),
+                )
+                .with_note(expected_tokens_note(expected)),
+            lalrpop_util::ParseError::ExtraToken {
+                token: (start, token, end),
+            } => Diagnostic::error(format!("extra token `{}`", token))
+                .with_code("E0004")
+                .with_label(
+                    Label::primary(SourceSpan::new(*start, *end))
+                        .with_message("no more tokens were expected"),
+                ),
+            lalrpop_util::ParseError::User { error } => error.to_diagnostic(),
+        }
+    }
+}
+
+/// Returns a note listing the tokens which the parser expected, in a human-readable form.
+fn expected_tokens_note(expected: &[String]) -> String {
+    let expected: Vec<String> = expected
+        .iter()
+        .map(|token| {
+            if token == "number" {
+                // integer literals are scanned by the lexer as `number` tokens.
+                "integer".to_string()
+            } else if let Some(keyword) = token.strip_prefix('"').and_then(|t| t.strip_suffix('"'))
+            {
+                format!("`{}`", keyword)
+            } else {
+                token.to_string()
+            }
+        })
+        .collect();
+
+    match expected.len() {
+        0 => "no more tokens were expected".to_string(),
+        1 => format!("expected {}", expected[0]),
+        _ => format!("expected one of {}", expected.join(", ")),
+    }
+}
+
+// PROGRAM ERROR
+// ================================================================================================
+
+/// An error found while loading the modules of an AirScript program which is split across several
+/// files.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProgramError {
+    /// A syntax error in one of the files of the program.
+    Syntax(SyntaxError),
+    /// The file of an imported module could not be read.
+    ModuleNotFound(String, SourceSpan),
+    /// The name declared by an imported module does not match the name under which it was
+    /// imported.
+    InvalidModuleName(String, SourceSpan),
+    /// A module imports itself, either directly or through the modules it imports.
+    ImportCycle(String, SourceSpan),
+}
+
+impl ToDiagnostic for ProgramError {
+    fn to_diagnostic(&self) -> Diagnostic {
+        let (code, msg, span) = match self {
+            Self::Syntax(err) => return err.to_diagnostic(),
+            Self::ModuleNotFound(msg, span) => ("E0020", msg, span),
+            Self::InvalidModuleName(msg, span) => ("E0021", msg, span),
+            Self::ImportCycle(msg, span) => ("E0022", msg, span),
+        };
+        let diagnostic = Diagnostic::error(msg)
+            .with_code(code)
+            .with_label(Label::primary(*span));
+        match self {
+            Self::ModuleNotFound(..) => diagnostic.with_note(
+                "modules are loaded from the file `<module>.air` in the directory of the root file",
+            ),
+            _ => diagnostic,
+        }
+    }
+}
+
+impl From<SyntaxError> for ProgramError {
+    fn from(err: SyntaxError) -> Self {
+        Self::Syntax(err)
+    }
 }
@@ -1,5 +1,6 @@
 use crate::error::Error;
 use core::ops::Range;
+use std::fmt::Display;
 
 pub use logos::{Lexer, Logos};
 
@@ -17,8 +18,9 @@ pub enum Token {
     #[regex("[a-zA-Z][a-zA-Z0-9_]*", |tok| tok.slice().to_string())]
     Ident(String),
 
-    /// Integers should only contain numeric characters.
-    #[regex(r"[0-9]+", |tok| tok.slice().to_string())]
+    /// Integers are written in decimal, in hexadecimal with the `0x` prefix or in binary with the
+    /// `0b` prefix, and their digits can be separated by underscores, e.g. `1_000` or `0xff_ff`.
+    #[regex(r"[0-9][0-9_]*|0x[0-9a-fA-F][0-9a-fA-F_]*|0b[01][01_]*", |tok| tok.slice().to_string())]
     Num(String),
 
     // DECLARATION KEYWORDS
@@ -27,6 +29,14 @@ pub enum Token {
     #[token("def")]
     Def,
 
+    /// Used to declare the field over which the AIR is defined.
+    #[token("field")]
+    Field,
+
+    /// Keyword to declare the constants declaration section for the AIR.
+    #[token("constants")]
+    Constants,
+
     /// Used to declare trace columns section in the AIR constraints module.
     #[token("trace_columns")]
     TraceColumnns,
@@ -47,6 +57,10 @@ pub enum Token {
     #[token("periodic_columns")]
     PeriodicColumns,
 
+    /// Keyword to declare the random values declaration section for the AIR.
+    #[token("random_values")]
+    RandomValues,
+
     // BOUNDARY CONSTRAINT KEYWORDS
     // --------------------------------------------------------------------------------------------
     /// Marks the beginning of boundary constraints section in the constraints file.
@@ -61,12 +75,21 @@ pub enum Token {
     #[token("last")]
     Last,
 
+    /// Used to compute the multiplicative inverse of an expression in a boundary constraint.
+    #[token("inv")]
+    Inv,
+
     // TRANSITION CONSTRAINT KEYWORDS
     // --------------------------------------------------------------------------------------------
     /// Marks the beginning of transition constraints section in the constraints file.
     #[token("transition_constraints")]
     TransitionConstraints,
 
+    /// Used to declare the number of rows at the end of the trace against which the transition
+    /// constraints are not enforced.
+    #[token("transition_exemptions")]
+    TransitionExemptions,
+
     /// A modifier for identifiers used to indicate the next row.
     #[token("'")]
     Next,
@@ -75,12 +98,69 @@ pub enum Token {
     #[token("$rand")]
     Rand,
 
+    // EVALUATOR FUNCTION KEYWORDS
+    // --------------------------------------------------------------------------------------------
+    /// Marks the beginning of the declaration of an evaluator function.
+    #[token("ev")]
+    Ev,
+
+    // SELECTOR KEYWORDS
+    // --------------------------------------------------------------------------------------------
+    /// Keyword to enforce a transition constraint only when a selector is non-zero.
+    #[token("when")]
+    When,
+
+    /// Keyword to declare a set of transition constraints gated by different selectors.
+    #[token("match")]
+    Match,
+
+    /// Keyword to declare a selector and its constraint within a match.
+    #[token("case")]
+    Case,
+
+    /// Modifier for a match which enforces that its selectors are binary and mutually exclusive.
+    #[token("exclusive")]
+    Exclusive,
+
+    // MODULE KEYWORDS
+    // --------------------------------------------------------------------------------------------
+    /// Used to declare a library module, which can be imported by other modules.
+    #[token("mod")]
+    Mod,
+
+    /// Keyword to import items from a library module.
+    #[token("use")]
+    Use,
+
     // GENERAL KEYWORDS
     // --------------------------------------------------------------------------------------------
     /// Keyword to signify that a constraint needs to be enforced
     #[token("enf")]
     Enf,
 
+    /// Keyword to declare a variable in a constraints section.
+    #[token("let")]
+    Let,
+
+    /// Keyword to declare the bindings of a comprehension.
+    #[token("for")]
+    For,
+
+    /// Keyword to separate the bindings of a comprehension from the iterables they are bound to.
+    #[token("in")]
+    In,
+
+    /// Adds together all of the elements of a list. The opening parenthesis is part of the token,
+    /// so that `sum` remains a valid identifier when it is not immediately followed by one.
+    #[token("sum(")]
+    Sum,
+
+    /// Multiplies together all of the elements of a list. The opening parenthesis is part of the
+    /// token, so that `prod` remains a valid identifier when it is not immediately followed by
+    /// one.
+    #[token("prod(")]
+    Prod,
+
     // OPERATORS
     // --------------------------------------------------------------------------------------------
     /// Asserts LHS of the expression is equal to RHS of the expression.
@@ -96,6 +176,10 @@ pub enum Token {
     #[token("*")]
     Mul,
 
+    /// Division, which is only supported in boundary constraints.
+    #[token("/")]
+    Div,
+
     #[token("^")]
     Exp,
 
@@ -105,6 +189,10 @@ pub enum Token {
     #[token(":")]
     Colon,
 
+    /// Separates the name of a module from the items imported from it.
+    #[token("::")]
+    DoubleColon,
+
     #[token(",")]
     Comma,
 
@@ -123,6 +211,12 @@ pub enum Token {
     #[token(")")]
     Rparen,
 
+    #[token("{")]
+    Lbrace,
+
+    #[token("}")]
+    Rbrace,
+
     // UNDEFINED TOKENS AND TOKENS TO IGNORE
     // --------------------------------------------------------------------------------------------
     /// Error is returned on encountering unrecognized tokens.
@@ -135,6 +229,62 @@ pub enum Token {
     Error,
 }
 
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ident(ident) => write!(f, "{}", ident),
+            Self::Num(num) => write!(f, "{}", num),
+            Self::Def => write!(f, "def"),
+            Self::Field => write!(f, "field"),
+            Self::Constants => write!(f, "constants"),
+            Self::TraceColumnns => write!(f, "trace_columns"),
+            Self::Main => write!(f, "main"),
+            Self::Aux => write!(f, "aux"),
+            Self::PublicInputs => write!(f, "public_inputs"),
+            Self::PeriodicColumns => write!(f, "periodic_columns"),
+            Self::RandomValues => write!(f, "random_values"),
+            Self::BoundaryConstraints => write!(f, "boundary_constraints"),
+            Self::First => write!(f, "first"),
+            Self::Last => write!(f, "last"),
+            Self::Inv => write!(f, "inv"),
+            Self::TransitionConstraints => write!(f, "transition_constraints"),
+            Self::TransitionExemptions => write!(f, "transition_exemptions"),
+            Self::Next => write!(f, "'"),
+            Self::Rand => write!(f, "$rand"),
+            Self::Ev => write!(f, "ev"),
+            Self::When => write!(f, "when"),
+            Self::Match => write!(f, "match"),
+            Self::Case => write!(f, "case"),
+            Self::Exclusive => write!(f, "exclusive"),
+            Self::Mod => write!(f, "mod"),
+            Self::Use => write!(f, "use"),
+            Self::Enf => write!(f, "enf"),
+            Self::Let => write!(f, "let"),
+            Self::For => write!(f, "for"),
+            Self::In => write!(f, "in"),
+            Self::Sum => write!(f, "sum("),
+            Self::Prod => write!(f, "prod("),
+            Self::Equal => write!(f, "="),
+            Self::Plus => write!(f, "+"),
+            Self::Minus => write!(f, "-"),
+            Self::Mul => write!(f, "*"),
+            Self::Div => write!(f, "/"),
+            Self::Exp => write!(f, "^"),
+            Self::Colon => write!(f, ":"),
+            Self::DoubleColon => write!(f, "::"),
+            Self::Comma => write!(f, ","),
+            Self::Lsqb => write!(f, "["),
+            Self::Rsqb => write!(f, "]"),
+            Self::Dot => write!(f, "."),
+            Self::Lparen => write!(f, "("),
+            Self::Rparen => write!(f, ")"),
+            Self::Lbrace => write!(f, "{{"),
+            Self::Rbrace => write!(f, "}}"),
+            Self::Error => write!(f, "<error>"),
+        }
+    }
+}
+
 impl Token {
     /// Convert logos tokens to tokens accepted by lalrpop.
     pub fn to_spanned((t, r): (Token, Span)) -> Result<(usize, Token, usize), Error> {
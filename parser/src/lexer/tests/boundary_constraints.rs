@@ -31,6 +31,35 @@ fn last_boundary_constant() {
     expect_valid_tokenization(source, tokens);
 }
 
+#[test]
+fn row_and_periodic_boundaries() {
+    let source = "enf clk.row(3) = 1
+enf clk.every(8, 2) = 0";
+    let tokens = vec![
+        Token::Enf,
+        Token::Ident("clk".to_string()),
+        Token::Dot,
+        Token::Ident("row".to_string()),
+        Token::Lparen,
+        Token::Num("3".to_string()),
+        Token::Rparen,
+        Token::Equal,
+        Token::Num("1".to_string()),
+        Token::Enf,
+        Token::Ident("clk".to_string()),
+        Token::Dot,
+        Token::Ident("every".to_string()),
+        Token::Lparen,
+        Token::Num("8".to_string()),
+        Token::Comma,
+        Token::Num("2".to_string()),
+        Token::Rparen,
+        Token::Equal,
+        Token::Num("0".to_string()),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
 #[test]
 fn boundary_with_pub_input() {
     let source = "enf clk.first = stack_inputs[0]";
@@ -68,3 +97,22 @@ fn boundary_expression() {
     ];
     expect_valid_tokenization(source, tokens);
 }
+
+#[test]
+fn boundary_division_and_inverse() {
+    let source = "enf clk.first = 1 / inv(a)";
+    let tokens = vec![
+        Token::Enf,
+        Token::Ident("clk".to_string()),
+        Token::Dot,
+        Token::First,
+        Token::Equal,
+        Token::Num("1".to_string()),
+        Token::Div,
+        Token::Inv,
+        Token::Lparen,
+        Token::Ident("a".to_string()),
+        Token::Rparen,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
@@ -0,0 +1,78 @@
+use super::{expect_valid_tokenization, Token};
+
+#[test]
+fn constants_kw() {
+    let source = "constants";
+    let tokens = vec![Token::Constants];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn constants_scalar_vector_and_matrix() {
+    let source = "
+constants:
+    A: 1
+    B: [1, 0]
+    C: [[1, 0], [0, 1]]";
+
+    let tokens = vec![
+        Token::Constants,
+        Token::Colon,
+        Token::Ident("A".to_string()),
+        Token::Colon,
+        Token::Num("1".to_string()),
+        Token::Ident("B".to_string()),
+        Token::Colon,
+        Token::Lsqb,
+        Token::Num("1".to_string()),
+        Token::Comma,
+        Token::Num("0".to_string()),
+        Token::Rsqb,
+        Token::Ident("C".to_string()),
+        Token::Colon,
+        Token::Lsqb,
+        Token::Lsqb,
+        Token::Num("1".to_string()),
+        Token::Comma,
+        Token::Num("0".to_string()),
+        Token::Rsqb,
+        Token::Comma,
+        Token::Lsqb,
+        Token::Num("0".to_string()),
+        Token::Comma,
+        Token::Num("1".to_string()),
+        Token::Rsqb,
+        Token::Rsqb,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn constants_hex_binary_and_modulus_literals() {
+    let source = "
+constants:
+    A: 0xFF_ff
+    B: [0b10_01, 1_000]
+    C: p - 1";
+
+    let tokens = vec![
+        Token::Constants,
+        Token::Colon,
+        Token::Ident("A".to_string()),
+        Token::Colon,
+        Token::Num("0xFF_ff".to_string()),
+        Token::Ident("B".to_string()),
+        Token::Colon,
+        Token::Lsqb,
+        Token::Num("0b10_01".to_string()),
+        Token::Comma,
+        Token::Num("1_000".to_string()),
+        Token::Rsqb,
+        Token::Ident("C".to_string()),
+        Token::Colon,
+        Token::Ident("p".to_string()),
+        Token::Minus,
+        Token::Num("1".to_string()),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
@@ -0,0 +1,55 @@
+use super::expect_valid_tokenization;
+use crate::lexer::Token;
+
+// EVALUATOR FUNCTIONS VALID TOKENIZATION
+// ================================================================================================
+
+#[test]
+fn evaluator_function() {
+    let source = "ev is_binary([a], [p[2]]):
+    enf a^2 = a";
+    let tokens = vec![
+        Token::Ev,
+        Token::Ident("is_binary".to_string()),
+        Token::Lparen,
+        Token::Lsqb,
+        Token::Ident("a".to_string()),
+        Token::Rsqb,
+        Token::Comma,
+        Token::Lsqb,
+        Token::Ident("p".to_string()),
+        Token::Lsqb,
+        Token::Num("2".to_string()),
+        Token::Rsqb,
+        Token::Rsqb,
+        Token::Rparen,
+        Token::Colon,
+        Token::Enf,
+        Token::Ident("a".to_string()),
+        Token::Exp,
+        Token::Num("2".to_string()),
+        Token::Equal,
+        Token::Ident("a".to_string()),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn evaluator_call() {
+    let source = "enf is_binary([b, c[1]])";
+    let tokens = vec![
+        Token::Enf,
+        Token::Ident("is_binary".to_string()),
+        Token::Lparen,
+        Token::Lsqb,
+        Token::Ident("b".to_string()),
+        Token::Comma,
+        Token::Ident("c".to_string()),
+        Token::Lsqb,
+        Token::Num("1".to_string()),
+        Token::Rsqb,
+        Token::Rsqb,
+        Token::Rparen,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
@@ -113,3 +113,69 @@ fn ops_without_matching_closing_parens() {
     ];
     expect_valid_tokenization(source, tokens);
 }
+
+#[test]
+fn variable_declaration() {
+    let source = "let x = clk * 2";
+    let tokens = vec![
+        Token::Let,
+        Token::Ident("x".to_string()),
+        Token::Equal,
+        Token::Ident("clk".to_string()),
+        Token::Mul,
+        Token::Num("2".to_string()),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn list_comprehension() {
+    let source = "enf x' = sum([x * y for (x, y) in (a, b)]) + prod(c)";
+    let tokens = vec![
+        Token::Enf,
+        Token::Ident("x".to_string()),
+        Token::Next,
+        Token::Equal,
+        Token::Sum,
+        Token::Lsqb,
+        Token::Ident("x".to_string()),
+        Token::Mul,
+        Token::Ident("y".to_string()),
+        Token::For,
+        Token::Lparen,
+        Token::Ident("x".to_string()),
+        Token::Comma,
+        Token::Ident("y".to_string()),
+        Token::Rparen,
+        Token::In,
+        Token::Lparen,
+        Token::Ident("a".to_string()),
+        Token::Comma,
+        Token::Ident("b".to_string()),
+        Token::Rparen,
+        Token::Rsqb,
+        Token::Rparen,
+        Token::Plus,
+        Token::Prod,
+        Token::Ident("c".to_string()),
+        Token::Rparen,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn sum_and_prod_without_parenthesis_are_identifiers() {
+    let source = "enf sum' = prod * sum(c)";
+    let tokens = vec![
+        Token::Enf,
+        Token::Ident("sum".to_string()),
+        Token::Next,
+        Token::Equal,
+        Token::Ident("prod".to_string()),
+        Token::Mul,
+        Token::Sum,
+        Token::Ident("c".to_string()),
+        Token::Rparen,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
@@ -19,6 +19,13 @@ fn keywords_with_identifiers() {
     expect_valid_tokenization(source, tokens);
 }
 
+#[test]
+fn field_declaration() {
+    let source = "field: f64";
+    let tokens = vec![Token::Field, Token::Colon, Token::Ident("f64".to_string())];
+    expect_valid_tokenization(source, tokens);
+}
+
 #[test]
 fn keyword_and_identifier_without_space() {
     let source = "enfclk' = clkdef + 1";
@@ -4,10 +4,15 @@ use crate::{
 };
 
 mod boundary_constraints;
+mod constants;
+mod evaluator_functions;
 mod expressions;
 mod identifiers;
+mod modules;
 mod periodic_columns;
 mod pub_inputs;
+mod random_values;
+mod selectors;
 
 // TEST HELPERS
 // ================================================================================================
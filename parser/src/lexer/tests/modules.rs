@@ -0,0 +1,29 @@
+use super::expect_valid_tokenization;
+use crate::lexer::Token;
+
+// MODULES VALID TOKENIZATION
+// ================================================================================================
+
+#[test]
+fn module_declaration() {
+    let source = "mod bitwise";
+    let tokens = vec![Token::Mod, Token::Ident("bitwise".to_string())];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn imports() {
+    let source = "use bitwise::*
+use hash::rounds";
+    let tokens = vec![
+        Token::Use,
+        Token::Ident("bitwise".to_string()),
+        Token::DoubleColon,
+        Token::Mul,
+        Token::Use,
+        Token::Ident("hash".to_string()),
+        Token::DoubleColon,
+        Token::Ident("rounds".to_string()),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
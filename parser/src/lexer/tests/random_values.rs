@@ -0,0 +1,36 @@
+use super::{expect_valid_tokenization, Token};
+
+#[test]
+fn random_values_kw() {
+    let source = "random_values";
+    let tokens = vec![Token::RandomValues];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn random_values_declarations() {
+    let source = "
+random_values:
+    $rand: [4]
+    $rand.bus: [alpha, beta]";
+
+    let tokens = vec![
+        Token::RandomValues,
+        Token::Colon,
+        Token::Rand,
+        Token::Colon,
+        Token::Lsqb,
+        Token::Num("4".to_string()),
+        Token::Rsqb,
+        Token::Rand,
+        Token::Dot,
+        Token::Ident("bus".to_string()),
+        Token::Colon,
+        Token::Lsqb,
+        Token::Ident("alpha".to_string()),
+        Token::Comma,
+        Token::Ident("beta".to_string()),
+        Token::Rsqb,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
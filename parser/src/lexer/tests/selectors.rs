@@ -0,0 +1,40 @@
+use super::expect_valid_tokenization;
+use crate::lexer::Token;
+
+// SELECTORS VALID TOKENIZATION
+// ================================================================================================
+
+#[test]
+fn constraint_with_selector() {
+    let source = "enf clk' = clk when s";
+    let tokens = vec![
+        Token::Enf,
+        Token::Ident("clk".to_string()),
+        Token::Next,
+        Token::Equal,
+        Token::Ident("clk".to_string()),
+        Token::When,
+        Token::Ident("s".to_string()),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn match_constraints() {
+    let source = "enf match exclusive { case s: clk' = 0 }";
+    let tokens = vec![
+        Token::Enf,
+        Token::Match,
+        Token::Exclusive,
+        Token::Lbrace,
+        Token::Case,
+        Token::Ident("s".to_string()),
+        Token::Colon,
+        Token::Ident("clk".to_string()),
+        Token::Next,
+        Token::Equal,
+        Token::Num("0".to_string()),
+        Token::Rbrace,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
@@ -4,16 +4,92 @@ extern crate lalrpop_util;
 pub mod ast;
 
 mod error;
-use error::Error;
+pub use error::{ProgramError, SyntaxError};
 
 mod lexer;
 use lexer::{Lexer, Token};
 
 mod parser;
-use crate::parser::SourceParser;
+use crate::parser::{LibraryParser, SourceParser};
+
+mod program;
+pub use program::{parse_program, Program};
 
 /// Parses the provided source and returns the AST.
-pub fn parse(source: &str) -> Result<ast::Source, lalrpop_util::ParseError<usize, Token, Error>> {
-    let lex = Lexer::new(source).spanned().map(Token::to_spanned);
-    SourceParser::new().parse(lex)
+///
+/// The parser recovers from errors in constraints and declarations by skipping to the next one,
+/// so that all the syntax errors in the source are reported at once.
+///
+/// # Errors
+/// Returns a list of [SyntaxError]s, in the order they were found, if the source contains invalid
+/// characters or cannot be parsed. Each error can be rendered with its location in the source by
+/// converting it into a diagnostic with [diagnostics::ToDiagnostic].
+pub fn parse(source: &str) -> Result<ast::Source, Vec<SyntaxError>> {
+    parse_source_at(source, 0)
+}
+
+/// Parses the provided source of a library module, i.e. a source starting with `mod <name>`, and
+/// returns the AST.
+///
+/// # Errors
+/// Returns a list of [SyntaxError]s, in the order they were found, if the source contains invalid
+/// characters or cannot be parsed, or if it declares sections which are not allowed in a library.
+pub fn parse_library(source: &str) -> Result<ast::Library, Vec<SyntaxError>> {
+    parse_library_at(source, 0)
+}
+
+/// Parses the provided source of a root module, where the spans of the AST nodes are shifted by
+/// the specified offset.
+pub(crate) fn parse_source_at(
+    source: &str,
+    offset: usize,
+) -> Result<ast::Source, Vec<SyntaxError>> {
+    let mut recovered_errors = Vec::new();
+    let result = SourceParser::new().parse(&mut recovered_errors, tokens(source, offset));
+    collect_errors(result, recovered_errors)
+}
+
+/// Parses the provided source of a library module, where the spans of the AST nodes are shifted
+/// by the specified offset.
+pub(crate) fn parse_library_at(
+    source: &str,
+    offset: usize,
+) -> Result<ast::Library, Vec<SyntaxError>> {
+    let mut recovered_errors = Vec::new();
+    let result = LibraryParser::new().parse(&mut recovered_errors, tokens(source, offset));
+    collect_errors(result, recovered_errors)
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the tokens of the provided source, where the spans of the tokens are shifted by the
+/// specified offset.
+fn tokens(
+    source: &str,
+    offset: usize,
+) -> impl Iterator<Item = Result<(usize, Token, usize), error::Error>> + '_ {
+    Lexer::new(source).spanned().map(move |(token, span)| {
+        Token::to_spanned((token, span.start + offset..span.end + offset))
+    })
+}
+
+/// Returns the parsed AST if no errors were found, or all of the errors in the order they were
+/// found otherwise.
+fn collect_errors<T>(
+    result: Result<T, lalrpop_util::ParseError<usize, Token, error::Error>>,
+    recovered_errors: Vec<lalrpop_util::ErrorRecovery<usize, Token, error::Error>>,
+) -> Result<T, Vec<SyntaxError>> {
+    let mut errors: Vec<SyntaxError> = recovered_errors
+        .into_iter()
+        .map(|recovery| SyntaxError::from(recovery.error))
+        .collect();
+    match result {
+        Ok(ast) if errors.is_empty() => Ok(ast),
+        Ok(_) => Err(errors),
+        Err(err) => {
+            errors.push(SyntaxError::from(err));
+            Err(errors)
+        }
+    }
 }
@@ -1,7 +1,7 @@
 #![allow(clippy::all)]
 lalrpop_mod!(grammar, "/parser/grammar.rs");
 
-pub use grammar::SourceParser;
+pub use grammar::{LibraryParser, SourceParser};
 
 #[cfg(test)]
 mod tests;
@@ -1,6 +1,7 @@
 use super::{
-    build_parse_test, Boundary, BoundaryConstraint, BoundaryConstraints, BoundaryExpr, Identifier,
-    Source, SourceSection,
+    build_parse_test, ident, Boundary, BoundaryColumn, BoundaryConstraint, BoundaryConstraints,
+    BoundaryExpr, BoundaryStmt, BoundaryVariable, Error, MatrixAccess, ParseError, Source,
+    SourceSection, SourceSpan, VectorAccess,
 };
 
 // BOUNDARY CONSTRAINTS
@@ -13,11 +14,12 @@ fn boundary_constraint_at_first() {
         enf clk.first = 0";
     let expected = Source(vec![SourceSection::BoundaryConstraints(
         BoundaryConstraints {
-            boundary_constraints: vec![BoundaryConstraint::new(
-                Identifier("clk".to_string()),
+            stmts: vec![BoundaryStmt::Constraint(BoundaryConstraint::new(
+                BoundaryColumn::Named(ident("clk")),
                 Boundary::First,
                 BoundaryExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -30,16 +32,100 @@ fn boundary_constraint_at_last() {
         enf clk.last = 15";
     let expected = Source(vec![SourceSection::BoundaryConstraints(
         BoundaryConstraints {
-            boundary_constraints: vec![BoundaryConstraint::new(
-                Identifier("clk".to_string()),
+            stmts: vec![BoundaryStmt::Constraint(BoundaryConstraint::new(
+                BoundaryColumn::Named(ident("clk")),
                 Boundary::Last,
                 BoundaryExpr::Const(15),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
 }
 
+#[test]
+fn boundary_constraint_at_row() {
+    let source = "
+    boundary_constraints:
+        enf clk.row(5) = 15";
+    let expected = Source(vec![SourceSection::BoundaryConstraints(
+        BoundaryConstraints {
+            stmts: vec![BoundaryStmt::Constraint(BoundaryConstraint::new(
+                BoundaryColumn::Named(ident("clk")),
+                Boundary::Row(5),
+                BoundaryExpr::Const(15),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn periodic_boundary_constraints() {
+    let source = "
+    boundary_constraints:
+        enf clk.every(8) = 0
+        enf b[1].every(4, 3) = stack_inputs";
+    let expected = Source(vec![SourceSection::BoundaryConstraints(
+        BoundaryConstraints {
+            stmts: vec![
+                BoundaryStmt::Constraint(BoundaryConstraint::new(
+                    BoundaryColumn::Named(ident("clk")),
+                    Boundary::Periodic {
+                        first_row: 0,
+                        stride: 8,
+                    },
+                    BoundaryExpr::Const(0),
+                    SourceSpan::default(),
+                )),
+                BoundaryStmt::Constraint(BoundaryConstraint::new(
+                    BoundaryColumn::Indexed(VectorAccess::new(ident("b"), 1)),
+                    Boundary::Periodic {
+                        first_row: 3,
+                        stride: 4,
+                    },
+                    BoundaryExpr::Var(ident("stack_inputs")),
+                    SourceSpan::default(),
+                )),
+            ],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn err_periodic_boundary_without_stride() {
+    let source = "
+    boundary_constraints:
+        enf clk.every() = 0";
+    build_parse_test!(source).expect_unrecognized_token();
+}
+
+#[test]
+fn err_unknown_boundary() {
+    let source = "
+    boundary_constraints:
+        enf clk.step(2) = 0";
+    let error = Error::ParseError(
+        ParseError::InvalidBoundary("Invalid boundary step(2)".to_string()),
+        SourceSpan::new(43, 50),
+    );
+    build_parse_test!(source).expect_error(error);
+}
+
+#[test]
+fn err_row_boundary_with_too_many_args() {
+    let source = "
+    boundary_constraints:
+        enf clk.row(2, 3) = 0";
+    let error = Error::ParseError(
+        ParseError::InvalidBoundary("Invalid boundary row(2, 3)".to_string()),
+        SourceSpan::new(43, 52),
+    );
+    build_parse_test!(source).expect_error(error);
+}
+
 #[test]
 fn error_invalid_boundary() {
     let source = "
@@ -56,17 +142,19 @@ fn multiple_boundary_constraints() {
         enf clk.last = 1";
     let expected = Source(vec![SourceSection::BoundaryConstraints(
         BoundaryConstraints {
-            boundary_constraints: vec![
-                BoundaryConstraint::new(
-                    Identifier("clk".to_string()),
+            stmts: vec![
+                BoundaryStmt::Constraint(BoundaryConstraint::new(
+                    BoundaryColumn::Named(ident("clk")),
                     Boundary::First,
                     BoundaryExpr::Const(0),
-                ),
-                BoundaryConstraint::new(
-                    Identifier("clk".to_string()),
+                    SourceSpan::default(),
+                )),
+                BoundaryStmt::Constraint(BoundaryConstraint::new(
+                    BoundaryColumn::Named(ident("clk")),
                     Boundary::Last,
                     BoundaryExpr::Const(1),
-                ),
+                    SourceSpan::default(),
+                )),
             ],
         },
     )]);
@@ -80,11 +168,12 @@ fn boundary_constraint_with_pub_input() {
         enf clk.first = a[0]";
     let expected = Source(vec![SourceSection::BoundaryConstraints(
         BoundaryConstraints {
-            boundary_constraints: vec![BoundaryConstraint::new(
-                Identifier("clk".to_string()),
+            stmts: vec![BoundaryStmt::Constraint(BoundaryConstraint::new(
+                BoundaryColumn::Named(ident("clk")),
                 Boundary::First,
-                BoundaryExpr::PubInput(Identifier("a".to_string()), 0),
-            )],
+                BoundaryExpr::VectorAccess(VectorAccess::new(ident("a"), 0)),
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -97,30 +186,169 @@ fn boundary_constraint_with_expr() {
         enf clk.first = 5 + a[3] + 6";
     let expected = Source(vec![SourceSection::BoundaryConstraints(
         BoundaryConstraints {
-            boundary_constraints: vec![BoundaryConstraint::new(
-                Identifier("clk".to_string()),
+            stmts: vec![BoundaryStmt::Constraint(BoundaryConstraint::new(
+                BoundaryColumn::Named(ident("clk")),
                 Boundary::First,
                 BoundaryExpr::Add(
                     Box::new(BoundaryExpr::Add(
                         Box::new(BoundaryExpr::Const(5)),
-                        Box::new(BoundaryExpr::PubInput(Identifier("a".to_string()), 3)),
+                        Box::new(BoundaryExpr::VectorAccess(VectorAccess::new(ident("a"), 3))),
                     )),
                     Box::new(BoundaryExpr::Const(6)),
                 ),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
 }
 
 #[test]
-fn err_boundary_constraint_with_identifier() {
-    // TODO: ending the constraint with a gives "UnrecognizedEOF" error. These errors should be
-    // improved to be more useful and consistent.
+fn boundary_constraint_with_negation() {
     let source = "
     boundary_constraints:
-        enf clk.first = a + 5";
-    build_parse_test!(source).expect_unrecognized_token();
+        enf clk.first = -1 - -a[3]";
+    let expected = Source(vec![SourceSection::BoundaryConstraints(
+        BoundaryConstraints {
+            stmts: vec![BoundaryStmt::Constraint(BoundaryConstraint::new(
+                BoundaryColumn::Named(ident("clk")),
+                Boundary::First,
+                BoundaryExpr::Sub(
+                    Box::new(BoundaryExpr::Neg(Box::new(BoundaryExpr::Const(1)))),
+                    Box::new(BoundaryExpr::Neg(Box::new(BoundaryExpr::VectorAccess(
+                        VectorAccess::new(ident("a"), 3),
+                    )))),
+                ),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn boundary_constraint_with_division() {
+    let source = "
+    boundary_constraints:
+        enf clk.first = a / b * inv(c + 1)";
+    // division has the same precedence as multiplication, so the operations are evaluated from
+    // left to right.
+    let expected = Source(vec![SourceSection::BoundaryConstraints(
+        BoundaryConstraints {
+            stmts: vec![BoundaryStmt::Constraint(BoundaryConstraint::new(
+                BoundaryColumn::Named(ident("clk")),
+                Boundary::First,
+                BoundaryExpr::Mul(
+                    Box::new(BoundaryExpr::Div(
+                        Box::new(BoundaryExpr::Var(ident("a"))),
+                        Box::new(BoundaryExpr::Var(ident("b"))),
+                    )),
+                    Box::new(BoundaryExpr::Inv(Box::new(BoundaryExpr::Add(
+                        Box::new(BoundaryExpr::Var(ident("c"))),
+                        Box::new(BoundaryExpr::Const(1)),
+                    )))),
+                ),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn boundary_constraint_on_column_group() {
+    let source = "
+    boundary_constraints:
+        enf state[3].last = 1";
+    let expected = Source(vec![SourceSection::BoundaryConstraints(
+        BoundaryConstraints {
+            stmts: vec![BoundaryStmt::Constraint(BoundaryConstraint::new(
+                BoundaryColumn::Indexed(VectorAccess::new(ident("state"), 3)),
+                Boundary::Last,
+                BoundaryExpr::Const(1),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn vector_boundary_constraint_on_column_group() {
+    let source = "
+    boundary_constraints:
+        enf state.first = stack_inputs";
+    let expected = Source(vec![SourceSection::BoundaryConstraints(
+        BoundaryConstraints {
+            stmts: vec![BoundaryStmt::Constraint(BoundaryConstraint::new(
+                BoundaryColumn::Named(ident("state")),
+                Boundary::First,
+                BoundaryExpr::Var(ident("stack_inputs")),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn boundary_constraint_with_variables() {
+    let source = "
+    boundary_constraints:
+        let x = a[0] + 1
+        enf clk.first = x * x";
+    let expected = Source(vec![SourceSection::BoundaryConstraints(
+        BoundaryConstraints {
+            stmts: vec![
+                BoundaryStmt::Variable(BoundaryVariable::new(
+                    ident("x"),
+                    BoundaryExpr::Add(
+                        Box::new(BoundaryExpr::VectorAccess(VectorAccess::new(ident("a"), 0))),
+                        Box::new(BoundaryExpr::Const(1)),
+                    ),
+                    SourceSpan::default(),
+                )),
+                BoundaryStmt::Constraint(BoundaryConstraint::new(
+                    BoundaryColumn::Named(ident("clk")),
+                    Boundary::First,
+                    BoundaryExpr::Mul(
+                        Box::new(BoundaryExpr::Var(ident("x"))),
+                        Box::new(BoundaryExpr::Var(ident("x"))),
+                    ),
+                    SourceSpan::default(),
+                )),
+            ],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn boundary_constraint_with_constants() {
+    let source = "
+    boundary_constraints:
+        enf clk.first = A + B[1] * C[0][1]";
+    let expected = Source(vec![SourceSection::BoundaryConstraints(
+        BoundaryConstraints {
+            stmts: vec![BoundaryStmt::Constraint(BoundaryConstraint::new(
+                BoundaryColumn::Named(ident("clk")),
+                Boundary::First,
+                BoundaryExpr::Add(
+                    Box::new(BoundaryExpr::Var(ident("A"))),
+                    Box::new(BoundaryExpr::Mul(
+                        Box::new(BoundaryExpr::VectorAccess(VectorAccess::new(ident("B"), 1))),
+                        Box::new(BoundaryExpr::MatrixAccess(MatrixAccess::new(
+                            ident("C"),
+                            0,
+                            1,
+                        ))),
+                    )),
+                ),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
 }
 
 #[test]
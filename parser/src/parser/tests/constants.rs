@@ -0,0 +1,151 @@
+use super::{
+    build_parse_test, ident, literals, Constant, ConstantType, Error, Literal, ParseError, Source,
+    SourceSection, SourceSpan,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+#[test]
+fn constants_scalars() {
+    let source = "
+    constants:
+        A: 1
+        B: 2";
+    let expected = Source(vec![SourceSection::Constants(vec![
+        Constant::new(
+            ident("A"),
+            ConstantType::Scalar(Literal::Int(1)),
+            SourceSpan::default(),
+        ),
+        Constant::new(
+            ident("B"),
+            ConstantType::Scalar(Literal::Int(2)),
+            SourceSpan::default(),
+        ),
+    ])]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn constants_vectors() {
+    let source = "
+    constants:
+        A: [1, 2, 3, 4]
+        B: [5, 6, 7, 8]";
+    let expected = Source(vec![SourceSection::Constants(vec![
+        Constant::new(
+            ident("A"),
+            ConstantType::Vector(literals(&[1, 2, 3, 4])),
+            SourceSpan::default(),
+        ),
+        Constant::new(
+            ident("B"),
+            ConstantType::Vector(literals(&[5, 6, 7, 8])),
+            SourceSpan::default(),
+        ),
+    ])]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn constants_matrix() {
+    let source = "
+    constants:
+        A: [[1, 2], [3, 4]]
+        B: [[5, 6], [7, 8]]";
+    let expected = Source(vec![SourceSection::Constants(vec![
+        Constant::new(
+            ident("A"),
+            ConstantType::Matrix(vec![literals(&[1, 2]), literals(&[3, 4])]),
+            SourceSpan::default(),
+        ),
+        Constant::new(
+            ident("B"),
+            ConstantType::Matrix(vec![literals(&[5, 6]), literals(&[7, 8])]),
+            SourceSpan::default(),
+        ),
+    ])]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn constants_with_hex_binary_and_modulus_literals() {
+    let source = "
+    constants:
+        A: 0xff_ff
+        B: [0b1010, 1_000_000]
+        C: [[p - 1, 0x10], [1, p - 0x2]]";
+    let expected = Source(vec![SourceSection::Constants(vec![
+        Constant::new(
+            ident("A"),
+            ConstantType::Scalar(Literal::Int(0xffff)),
+            SourceSpan::default(),
+        ),
+        Constant::new(
+            ident("B"),
+            ConstantType::Vector(literals(&[10, 1_000_000])),
+            SourceSpan::default(),
+        ),
+        Constant::new(
+            ident("C"),
+            ConstantType::Matrix(vec![
+                vec![Literal::ModulusMinus(1), Literal::Int(16)],
+                vec![Literal::Int(1), Literal::ModulusMinus(2)],
+            ]),
+            SourceSpan::default(),
+        ),
+    ])]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn err_constant_too_big() {
+    let source = "
+    constants:
+        A: 0x1_0000_0000_0000_0000_0000_0000_0000_0000";
+    let start = source.find("0x").unwrap();
+    let error = Error::ParseError(
+        ParseError::InvalidInt(
+            "Int too big : 0x1_0000_0000_0000_0000_0000_0000_0000_0000".to_string(),
+        ),
+        SourceSpan::new(start, source.len()),
+    );
+    build_parse_test!(source).expect_error(error);
+}
+
+#[test]
+fn err_constant_relative_to_invalid_modulus() {
+    let source = "
+    constants:
+        A: q - 1";
+    let start = source.find('q').unwrap();
+    let error = Error::ParseError(
+        ParseError::InvalidModulusLiteral(
+            "Invalid literal q - 1, only an integer can be subtracted from the modulus p"
+                .to_string(),
+        ),
+        SourceSpan::new(start, source.len()),
+    );
+    build_parse_test!(source).expect_error(error);
+}
+
+#[test]
+fn err_constant_without_value() {
+    let source = "
+    constants:
+        A: [1, 2]
+        B:
+    trace_columns:
+        main: [clk]";
+    build_parse_test!(source).expect_unrecognized_token();
+}
+
+#[test]
+fn err_empty_constants() {
+    let source = "
+    constants:
+    trace_columns:
+        main: [clk]";
+    build_parse_test!(source).expect_unrecognized_token();
+}
@@ -0,0 +1,92 @@
+use super::SourceSpan;
+use crate::parse;
+use diagnostics::ToDiagnostic;
+
+// DIAGNOSTICS
+// ================================================================================================
+
+#[test]
+fn unrecognized_token_diagnostic() {
+    let source = "
+    transition_constraints:
+        enf clk' = clk = 1";
+    let diagnostic = parse(source).unwrap_err()[0].to_diagnostic();
+
+    assert_eq!(diagnostic.code(), Some("E0003"));
+    assert_eq!(diagnostic.message(), "unexpected token `=`");
+    let start = source.rfind('=').unwrap();
+    assert_eq!(
+        diagnostic.labels()[0].span(),
+        SourceSpan::new(start, start + 1)
+    );
+    assert!(diagnostic.notes()[0].starts_with("expected one of "));
+}
+
+#[test]
+fn invalid_int_diagnostic() {
+    let source = "
+    transition_constraints:
+        enf clk' = clk + 340282366920938463463374607431768211456";
+    let diagnostic = parse(source).unwrap_err()[0].to_diagnostic();
+
+    assert_eq!(diagnostic.code(), Some("E0010"));
+    assert_eq!(
+        diagnostic.message(),
+        "Int too big : 340282366920938463463374607431768211456"
+    );
+    let start = source
+        .find("340282366920938463463374607431768211456")
+        .unwrap();
+    assert_eq!(
+        diagnostic.labels()[0].span(),
+        SourceSpan::new(start, source.len())
+    );
+    assert_eq!(
+        diagnostic.notes(),
+        ["values must fit in an unsigned 128-bit integer, and sizes, indices and exponents in an unsigned 64-bit integer"]
+    );
+}
+
+#[test]
+fn invalid_modulus_literal_diagnostic() {
+    let source = "
+    constants:
+        A: q - 1";
+    let diagnostic = parse(source).unwrap_err()[0].to_diagnostic();
+
+    assert_eq!(diagnostic.code(), Some("E0015"));
+    assert_eq!(
+        diagnostic.message(),
+        "Invalid literal q - 1, only an integer can be subtracted from the modulus p"
+    );
+    assert_eq!(
+        diagnostic.notes(),
+        ["values relative to the modulus of the field are written as `p - k`, e.g. `p - 1`"]
+    );
+}
+
+#[test]
+fn unexpected_eof_diagnostic_names_integers() {
+    let source = "
+    public_inputs:
+        stack_inputs: [";
+    let diagnostic = parse(source).unwrap_err()[0].to_diagnostic();
+
+    assert_eq!(diagnostic.code(), Some("E0002"));
+    assert_eq!(diagnostic.notes(), ["expected integer"]);
+}
+
+#[test]
+fn scan_error_diagnostic() {
+    let source = "
+    transition_constraints:
+        enf clk@' = clk + 1";
+    let diagnostic = parse(source).unwrap_err()[0].to_diagnostic();
+
+    assert_eq!(diagnostic.code(), Some("E0001"));
+    let start = source.find('@').unwrap();
+    assert_eq!(
+        diagnostic.labels()[0].span(),
+        SourceSpan::new(start, start + 1)
+    );
+}
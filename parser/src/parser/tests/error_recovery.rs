@@ -0,0 +1,93 @@
+use super::{build_parse_test, Error, ParseError, SourceSpan};
+use crate::parse;
+use lalrpop_util::ParseError as LalrpopError;
+
+// ERROR RECOVERY
+// ================================================================================================
+
+#[test]
+fn multiple_invalid_transition_constraints() {
+    let source = "
+    trace_columns:
+        main: [clk, fmp]
+    transition_constraints:
+        enf clk' = clk = 1
+        enf fmp' = fmp + 1
+        enf fmp' = + 1";
+    build_parse_test!(source).expect_error_count(2);
+}
+
+#[test]
+fn multiple_invalid_boundary_constraints() {
+    let source = "
+    boundary_constraints:
+        enf clk.middle = 0
+        enf clk.first = 0
+        enf clk.last 1";
+    build_parse_test!(source).expect_error_count(2);
+}
+
+#[test]
+fn errors_in_multiple_sections() {
+    let source = "
+    public_inputs:
+        stack_inputs: [16
+        stack_outputs: [16]
+    periodic_columns:
+        k0: [1, 1, 0, 0]
+        k1 1, 0]
+    boundary_constraints:
+        enf clk.first = $rand[]
+    transition_constraints:
+        enf clk' = clk + 1";
+    build_parse_test!(source).expect_error_count(3);
+}
+
+#[test]
+fn invalid_int_does_not_stop_parsing() {
+    let source = "
+    transition_constraints:
+        enf clk' = clk + 340282366920938463463374607431768211456
+        enf clk' = = 1";
+    let errors = parse(source).unwrap_err();
+    assert_eq!(errors.len(), 2);
+
+    let start = source
+        .find("340282366920938463463374607431768211456")
+        .unwrap();
+    let expected = LalrpopError::User {
+        error: Error::ParseError(
+            ParseError::InvalidInt(
+                "Int too big : 340282366920938463463374607431768211456".to_string(),
+            ),
+            SourceSpan::new(start, start + 39),
+        ),
+    };
+    assert_eq!(errors[0].inner(), &expected);
+    assert!(matches!(
+        errors[1].inner(),
+        LalrpopError::UnrecognizedToken { .. }
+    ));
+}
+
+#[test]
+fn errors_are_reported_in_source_order() {
+    let source = "
+    transition_constraints:
+        enf clk' = clk = 1
+        enf clk' = clk + 1 1";
+    let errors = parse(source).unwrap_err();
+    let locations: Vec<usize> = errors
+        .iter()
+        .map(|err| match err.inner() {
+            LalrpopError::UnrecognizedToken {
+                token: (start, ..), ..
+            } => *start,
+            err => panic!("unexpected error {:?}", err),
+        })
+        .collect();
+    assert_eq!(
+        locations,
+        vec![source.find("= 1").unwrap(), source.rfind('1').unwrap()]
+    );
+}
@@ -0,0 +1,124 @@
+use super::{
+    build_parse_test, ident, EvaluatorArg, EvaluatorCall, EvaluatorFunction, RandomValueAccess,
+    Source, SourceSection, SourceSpan, TraceColumn, TransitionConstraint, TransitionConstraints,
+    TransitionExpr, TransitionStmt, TransitionVariable, VectorAccess,
+};
+
+// EVALUATOR FUNCTIONS
+// ================================================================================================
+
+#[test]
+fn evaluator_function() {
+    let source = "
+    ev is_binary([a]):
+        enf a^2 = a";
+    let expected = Source(vec![SourceSection::EvaluatorFunction(
+        EvaluatorFunction::new(
+            ident("is_binary"),
+            vec![TraceColumn::Single(ident("a"))],
+            vec![],
+            vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Exp(Box::new(TransitionExpr::Var(ident("a"))), 2),
+                TransitionExpr::Var(ident("a")),
+                SourceSpan::default(),
+            ))],
+            SourceSpan::default(),
+        ),
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn evaluator_function_with_aux_params() {
+    let source = "
+    ev running_product([], [p, q[2]]):
+        let r = q[0] + $rand[0]
+        enf p' = p * r";
+    let expected = Source(vec![SourceSection::EvaluatorFunction(
+        EvaluatorFunction::new(
+            ident("running_product"),
+            vec![],
+            vec![
+                TraceColumn::Single(ident("p")),
+                TraceColumn::Group(ident("q"), 2),
+            ],
+            vec![
+                TransitionStmt::Variable(TransitionVariable::new(
+                    ident("r"),
+                    TransitionExpr::Add(
+                        Box::new(TransitionExpr::VectorAccess(VectorAccess::new(
+                            ident("q"),
+                            0,
+                        ))),
+                        Box::new(TransitionExpr::Rand(RandomValueAccess::new(
+                            None,
+                            0,
+                            SourceSpan::default(),
+                        ))),
+                    ),
+                    SourceSpan::default(),
+                )),
+                TransitionStmt::Constraint(TransitionConstraint::new(
+                    TransitionExpr::Next(ident("p")),
+                    TransitionExpr::Mul(
+                        Box::new(TransitionExpr::Var(ident("p"))),
+                        Box::new(TransitionExpr::Var(ident("r"))),
+                    ),
+                    SourceSpan::default(),
+                )),
+            ],
+            SourceSpan::default(),
+        ),
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn evaluator_call() {
+    let source = "
+    transition_constraints:
+        enf is_binary([b, c[1]])
+        enf running_product([], [p])";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![
+                TransitionStmt::EvaluatorCall(EvaluatorCall::new(
+                    ident("is_binary"),
+                    vec![
+                        EvaluatorArg::Named(ident("b")),
+                        EvaluatorArg::Indexed(VectorAccess::new(ident("c"), 1)),
+                    ],
+                    vec![],
+                    SourceSpan::default(),
+                )),
+                TransitionStmt::EvaluatorCall(EvaluatorCall::new(
+                    ident("running_product"),
+                    vec![],
+                    vec![EvaluatorArg::Named(ident("p"))],
+                    SourceSpan::default(),
+                )),
+            ],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+// ERRORS
+// ================================================================================================
+
+#[test]
+fn err_evaluator_without_body() {
+    let source = "
+    ev is_binary([a]):
+    transition_constraints:
+        enf is_binary([b])";
+    build_parse_test!(source).expect_unrecognized_token();
+}
+
+#[test]
+fn err_evaluator_call_with_expression_argument() {
+    let source = "
+    transition_constraints:
+        enf is_binary([b + 1])";
+    build_parse_test!(source).expect_unrecognized_token();
+}
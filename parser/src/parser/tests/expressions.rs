@@ -1,6 +1,6 @@
 use super::{
-    build_parse_test, Identifier, Source, SourceSection, TransitionConstraint,
-    TransitionConstraints, TransitionExpr,
+    build_parse_test, ident, Error, ParseError, Source, SourceSection, SourceSpan,
+    TransitionConstraint, TransitionConstraints, TransitionExpr, TransitionStmt,
 };
 
 // EXPRESSIONS
@@ -14,13 +14,14 @@ fn single_addition() {
         enf clk' + clk = 0";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Add(
-                    Box::new(TransitionExpr::Next(Identifier("clk".to_string()))),
-                    Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                    Box::new(TransitionExpr::Next(ident("clk"))),
+                    Box::new(TransitionExpr::Var(ident("clk"))),
                 ),
                 TransitionExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -34,16 +35,17 @@ fn multi_addition() {
         enf clk' + clk + 2 = 0";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Add(
                     Box::new(TransitionExpr::Add(
-                        Box::new(TransitionExpr::Next(Identifier("clk".to_string()))),
-                        Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                        Box::new(TransitionExpr::Next(ident("clk"))),
+                        Box::new(TransitionExpr::Var(ident("clk"))),
                     )),
                     Box::new(TransitionExpr::Const(2)),
                 ),
                 TransitionExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -57,13 +59,14 @@ fn single_subtraction() {
         enf clk' - clk = 0";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Sub(
-                    Box::new(TransitionExpr::Next(Identifier("clk".to_string()))),
-                    Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                    Box::new(TransitionExpr::Next(ident("clk"))),
+                    Box::new(TransitionExpr::Var(ident("clk"))),
                 ),
                 TransitionExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -77,16 +80,17 @@ fn multi_subtraction() {
         enf clk' - clk - 1 = 0";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Sub(
                     Box::new(TransitionExpr::Sub(
-                        Box::new(TransitionExpr::Next(Identifier("clk".to_string()))),
-                        Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                        Box::new(TransitionExpr::Next(ident("clk"))),
+                        Box::new(TransitionExpr::Var(ident("clk"))),
                     )),
                     Box::new(TransitionExpr::Const(1)),
                 ),
                 TransitionExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -100,13 +104,14 @@ fn single_multiplication() {
         enf clk' * clk = 0";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Mul(
-                    Box::new(TransitionExpr::Next(Identifier("clk".to_string()))),
-                    Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                    Box::new(TransitionExpr::Next(ident("clk"))),
+                    Box::new(TransitionExpr::Var(ident("clk"))),
                 ),
                 TransitionExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -120,16 +125,17 @@ fn multi_multiplication() {
         enf clk' * clk * 2 = 0";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Mul(
                     Box::new(TransitionExpr::Mul(
-                        Box::new(TransitionExpr::Next(Identifier("clk".to_string()))),
-                        Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                        Box::new(TransitionExpr::Next(ident("clk"))),
+                        Box::new(TransitionExpr::Var(ident("clk"))),
                     )),
                     Box::new(TransitionExpr::Const(2)),
                 ),
                 TransitionExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -143,13 +149,14 @@ fn unit_with_parens() {
         enf (2) + 1 = 3";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Add(
                     Box::new(TransitionExpr::Const(2)),
                     Box::new(TransitionExpr::Const(1)),
                 ),
                 TransitionExpr::Const(3),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -163,16 +170,17 @@ fn ops_with_parens() {
         enf (clk' + clk) * 2 = 4";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Mul(
                     Box::new(TransitionExpr::Add(
-                        Box::new(TransitionExpr::Next(Identifier("clk".to_string()))),
-                        Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                        Box::new(TransitionExpr::Next(ident("clk"))),
+                        Box::new(TransitionExpr::Var(ident("clk"))),
                     )),
                     Box::new(TransitionExpr::Const(2)),
                 ),
                 TransitionExpr::Const(4),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -186,13 +194,115 @@ fn single_exponentiation() {
         enf clk'^2 = 1";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
-                TransitionExpr::Exp(
-                    Box::new(TransitionExpr::Next(Identifier("clk".to_string()))),
-                    2,
-                ),
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Exp(Box::new(TransitionExpr::Next(ident("clk"))), 2),
                 TransitionExpr::Const(1),
-            )],
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn single_negation() {
+    // the operation must be put into a source section, or parsing will fail
+    let source = "
+    transition_constraints:
+        enf clk' = -clk";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Next(ident("clk")),
+                TransitionExpr::Neg(Box::new(TransitionExpr::Var(ident("clk")))),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn negation_precedence() {
+    // the operation must be put into a source section, or parsing will fail
+    let source = "
+    transition_constraints:
+        enf clk' - -clk^2 * -(clk - 1) = 0";
+    // Negation binds more tightly than multiplication and less tightly than exponentiation, so
+    // `-clk^2` is the negation of `clk^2`.
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Sub(
+                    Box::new(TransitionExpr::Next(ident("clk"))),
+                    Box::new(TransitionExpr::Mul(
+                        Box::new(TransitionExpr::Neg(Box::new(TransitionExpr::Exp(
+                            Box::new(TransitionExpr::Var(ident("clk"))),
+                            2,
+                        )))),
+                        Box::new(TransitionExpr::Neg(Box::new(TransitionExpr::Sub(
+                            Box::new(TransitionExpr::Var(ident("clk"))),
+                            Box::new(TransitionExpr::Const(1)),
+                        )))),
+                    )),
+                ),
+                TransitionExpr::Const(0),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn err_division_in_transition_constraints() {
+    let source = "
+    transition_constraints:
+        enf clk' = clk / 2";
+    let start = source.find("clk /").unwrap();
+    let error = Error::ParseError(
+        ParseError::InvalidTransitionExpr(
+            "Division is not supported in transition constraints".to_string(),
+        ),
+        SourceSpan::new(start, source.len()),
+    );
+    build_parse_test!(source).expect_error(error);
+}
+
+#[test]
+fn err_inverse_in_transition_constraints() {
+    let source = "
+    transition_constraints:
+        enf clk' = inv(clk)";
+    let start = source.find("inv").unwrap();
+    let error = Error::ParseError(
+        ParseError::InvalidTransitionExpr(
+            "Inversion is not supported in transition constraints".to_string(),
+        ),
+        SourceSpan::new(start, source.len()),
+    );
+    build_parse_test!(source).expect_error(error);
+}
+
+#[test]
+fn hex_and_binary_literals() {
+    // the operation must be put into a source section, or parsing will fail
+    let source = "
+    transition_constraints:
+        enf clk' = clk + 0x1_0 * 0b11";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Next(ident("clk")),
+                TransitionExpr::Add(
+                    Box::new(TransitionExpr::Var(ident("clk"))),
+                    Box::new(TransitionExpr::Mul(
+                        Box::new(TransitionExpr::Const(16)),
+                        Box::new(TransitionExpr::Const(3)),
+                    )),
+                ),
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -233,19 +343,20 @@ fn multi_arithmetic_ops_same_precedence() {
         enf clk' - clk - 2 + 1 = 0";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Add(
                     Box::new(TransitionExpr::Sub(
                         Box::new(TransitionExpr::Sub(
-                            Box::new(TransitionExpr::Next(Identifier("clk".to_string()))),
-                            Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                            Box::new(TransitionExpr::Next(ident("clk"))),
+                            Box::new(TransitionExpr::Var(ident("clk"))),
                         )),
                         Box::new(TransitionExpr::Const(2)),
                     )),
                     Box::new(TransitionExpr::Const(1)),
                 ),
                 TransitionExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -264,22 +375,23 @@ fn multi_arithmetic_ops_different_precedence() {
     // These operations are evaluated in the order of decreasing precedence.
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Sub(
                     Box::new(TransitionExpr::Sub(
                         Box::new(TransitionExpr::Exp(
-                            Box::new(TransitionExpr::Next(Identifier("clk".to_string()))),
+                            Box::new(TransitionExpr::Next(ident("clk"))),
                             2,
                         )),
                         Box::new(TransitionExpr::Mul(
-                            Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                            Box::new(TransitionExpr::Var(ident("clk"))),
                             Box::new(TransitionExpr::Const(2)),
                         )),
                     )),
                     Box::new(TransitionExpr::Const(1)),
                 ),
                 TransitionExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -299,12 +411,12 @@ fn multi_arithmetic_ops_different_precedence_w_parens() {
     // These operations are evaluated in the order of decreasing precedence.
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Sub(
-                    Box::new(TransitionExpr::Next(Identifier("clk".to_string()))),
+                    Box::new(TransitionExpr::Next(ident("clk"))),
                     Box::new(TransitionExpr::Mul(
                         Box::new(TransitionExpr::Exp(
-                            Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                            Box::new(TransitionExpr::Var(ident("clk"))),
                             2,
                         )),
                         Box::new(TransitionExpr::Sub(
@@ -314,7 +426,8 @@ fn multi_arithmetic_ops_different_precedence_w_parens() {
                     )),
                 ),
                 TransitionExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
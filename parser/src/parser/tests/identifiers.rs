@@ -1,4 +1,4 @@
-use super::{build_parse_test, Error, ParseError};
+use super::{build_parse_test, Error, ParseError, SourceSpan};
 
 // TODO: clean up this test file
 // IDENTIFIERS
@@ -6,15 +6,39 @@ use super::{build_parse_test, Error, ParseError};
 
 #[test]
 fn error_invalid_int() {
-    let num: u128 = u64::max_value() as u128 + 1;
+    let num = "340282366920938463463374607431768211456";
     let source = format!(
         "
     transition_constraints:
         enf clk' = clk + {}",
         num
     );
-    // Integers can only be of type u64.
-    let error = Error::ParseError(ParseError::InvalidInt(format!("Int too big : {}", num)));
+    // Values can only be of type u128.
+    let start = source.rfind(num).unwrap();
+    let span = SourceSpan::new(start, source.len());
+    let error = Error::ParseError(
+        ParseError::InvalidInt(format!("Int too big : {}", num)),
+        span,
+    );
+    build_parse_test!(source.as_str()).expect_error(error);
+}
+
+#[test]
+fn error_invalid_exponent() {
+    let num: u128 = u64::max_value() as u128 + 1;
+    let source = format!(
+        "
+    transition_constraints:
+        enf clk' = clk^{}",
+        num
+    );
+    // Exponents can only be of type u64.
+    let start = source.rfind(&num.to_string()).unwrap();
+    let span = SourceSpan::new(start, source.len());
+    let error = Error::ParseError(
+        ParseError::InvalidInt(format!("Int too big : {}", num)),
+        span,
+    );
     build_parse_test!(source.as_str()).expect_error(error);
 }
 
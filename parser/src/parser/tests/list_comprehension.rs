@@ -0,0 +1,154 @@
+use super::{
+    build_parse_test, ident, Error, ListComprehension, ListFolding, ListValue, ParseError, Source,
+    SourceSection, SourceSpan, TransitionConstraint, TransitionConstraints, TransitionExpr,
+    TransitionStmt, VectorAccess,
+};
+
+// LIST FOLDING
+// ================================================================================================
+
+#[test]
+fn sum_of_column_group() {
+    let source = "
+    transition_constraints:
+        enf clk' = sum(state)";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Next(ident("clk")),
+                TransitionExpr::ListFolding(ListFolding::Sum(ListValue::Var(ident("state")))),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn sum_and_prod_as_variables() {
+    let source = "
+    transition_constraints:
+        enf sum' = sum + prod(prod)";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Next(ident("sum")),
+                TransitionExpr::Add(
+                    Box::new(TransitionExpr::Var(ident("sum"))),
+                    Box::new(TransitionExpr::ListFolding(ListFolding::Prod(
+                        ListValue::Var(ident("prod")),
+                    ))),
+                ),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn prod_of_vector() {
+    let source = "
+    transition_constraints:
+        enf clk' = prod([a, b * c])";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Next(ident("clk")),
+                TransitionExpr::ListFolding(ListFolding::Prod(ListValue::Vector(vec![
+                    TransitionExpr::Var(ident("a")),
+                    TransitionExpr::Mul(
+                        Box::new(TransitionExpr::Var(ident("b"))),
+                        Box::new(TransitionExpr::Var(ident("c"))),
+                    ),
+                ]))),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn sum_of_list_comprehension() {
+    let source = "
+    transition_constraints:
+        enf clk' = sum([x * y for (x, y) in (a, b)]) + 1";
+    let comprehension = ListComprehension::new(
+        TransitionExpr::Mul(
+            Box::new(TransitionExpr::Var(ident("x"))),
+            Box::new(TransitionExpr::Var(ident("y"))),
+        ),
+        vec![(ident("x"), ident("a")), (ident("y"), ident("b"))],
+    );
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Next(ident("clk")),
+                TransitionExpr::Add(
+                    Box::new(TransitionExpr::ListFolding(ListFolding::Sum(
+                        ListValue::Comprehension(comprehension),
+                    ))),
+                    Box::new(TransitionExpr::Const(1)),
+                ),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+// CONSTRAINT COMPREHENSION
+// ================================================================================================
+
+#[test]
+fn constraint_comprehension() {
+    let source = "
+    transition_constraints:
+        enf x' = x + c[0] for x in state";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::ConstraintComprehension(
+                TransitionConstraint::new(
+                    TransitionExpr::Next(ident("x")),
+                    TransitionExpr::Add(
+                        Box::new(TransitionExpr::Var(ident("x"))),
+                        Box::new(TransitionExpr::VectorAccess(VectorAccess::new(
+                            ident("c"),
+                            0,
+                        ))),
+                    ),
+                    SourceSpan::default(),
+                ),
+                vec![(ident("x"), ident("state"))],
+            )],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+// ERRORS
+// ================================================================================================
+
+#[test]
+fn err_bindings_and_iterables_mismatch() {
+    let source = "
+    transition_constraints:
+        enf x' = x + y for (x, y) in state";
+    let start = source.find("for").unwrap();
+    let error = Error::ParseError(
+        ParseError::InvalidListComprehension(
+            "Number of bindings (2) does not match the number of iterables (1)".to_string(),
+        ),
+        SourceSpan::new(start, source.len()),
+    );
+    build_parse_test!(source).expect_error(error);
+}
+
+#[test]
+fn err_list_comprehension_without_folding() {
+    let source = "
+    transition_constraints:
+        enf clk' = [x for x in state]";
+    build_parse_test!(source).expect_unrecognized_token();
+}
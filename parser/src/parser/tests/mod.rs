@@ -1,4 +1,3 @@
-use super::SourceParser;
 use crate::{
     ast::*,
     build_parse_test,
@@ -9,15 +8,38 @@ use std::fs;
 mod utils;
 
 mod boundary_constraints;
+mod constants;
+mod evaluator_functions;
+mod list_comprehension;
+mod modules;
 mod periodic_columns;
 mod pub_inputs;
+mod random_values;
 mod sections;
+mod selectors;
 mod trace_columns;
 mod transition_constraints;
 
 mod comments;
+mod diagnostics;
+mod error_recovery;
 mod expressions;
 mod identifiers;
+mod spans;
+
+// HELPERS
+// ================================================================================================
+
+/// Returns an [Identifier] with the specified name. Spans are not considered when comparing AST
+/// nodes, so the identifier is given an empty span.
+fn ident(name: &str) -> Identifier {
+    Identifier::new(name.to_string(), SourceSpan::default())
+}
+
+/// Returns the integer [Literal]s with the specified values.
+fn literals(values: &[u128]) -> Vec<Literal> {
+    values.iter().copied().map(Literal::Int).collect()
+}
 
 // FULL AIR FILE
 // ================================================================================================
@@ -28,37 +50,40 @@ fn full_air_file() {
         fs::read_to_string("src/parser/tests/input/system.air").expect("Could not read file");
     let expected = Source(vec![
         // def SystemAir
-        SourceSection::AirDef(Identifier("SystemAir".to_string())),
+        SourceSection::AirDef(ident("SystemAir")),
         // trace_columns:
         //     main: [clk, fmp, ctx]
         SourceSection::TraceCols(TraceCols {
             main_cols: vec![
-                Identifier("clk".to_string()),
-                Identifier("fmp".to_string()),
-                Identifier("ctx".to_string()),
+                TraceColumn::Single(ident("clk")),
+                TraceColumn::Single(ident("fmp")),
+                TraceColumn::Single(ident("ctx")),
             ],
-            aux_cols: vec![],
+            aux_segments: vec![],
+            span: SourceSpan::default(),
         }),
         // transition_constraints:
         //     enf clk' = clk + 1
         SourceSection::TransitionConstraints(TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 // clk' = clk + 1
-                TransitionExpr::Next(Identifier("clk".to_string())),
+                TransitionExpr::Next(ident("clk")),
                 TransitionExpr::Add(
-                    Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                    Box::new(TransitionExpr::Var(ident("clk"))),
                     Box::new(TransitionExpr::Const(1)),
                 ),
-            )],
+                SourceSpan::default(),
+            ))],
         }),
         // boundary_constraints:
         //     enf clk.first = 0
         SourceSection::BoundaryConstraints(BoundaryConstraints {
-            boundary_constraints: vec![BoundaryConstraint::new(
-                Identifier("clk".to_string()),
+            stmts: vec![BoundaryStmt::Constraint(BoundaryConstraint::new(
+                BoundaryColumn::Named(ident("clk")),
                 Boundary::First,
                 BoundaryExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
         }),
     ]);
     build_parse_test!(source.as_str()).expect_ast(expected);
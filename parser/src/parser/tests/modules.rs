@@ -0,0 +1,203 @@
+use super::{
+    build_parse_test, ident, Constant, ConstantType, EvaluatorFunction, Import, ImportItems,
+    Library, LibrarySection, Literal, Source, SourceSection, SourceSpan, TraceColumn,
+    TransitionConstraint, TransitionExpr, TransitionStmt,
+};
+use crate::{parse_library, parse_program, Program, ProgramError};
+use diagnostics::{SourceFiles, ToDiagnostic};
+use lalrpop_util::ParseError;
+use std::{fs, path::Path};
+
+// IMPORTS
+// ================================================================================================
+
+#[test]
+fn imports() {
+    let source = "
+    def ImportsAir
+    use bitwise::*
+    use hash::ROUNDS";
+    let expected = Source(vec![
+        SourceSection::AirDef(ident("ImportsAir")),
+        SourceSection::Import(Import::new(
+            ident("bitwise"),
+            ImportItems::All,
+            SourceSpan::default(),
+        )),
+        SourceSection::Import(Import::new(
+            ident("hash"),
+            ImportItems::Single(ident("ROUNDS")),
+            SourceSpan::default(),
+        )),
+    ]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn err_import_without_items() {
+    let source = "
+    def ImportsAir
+    use bitwise";
+    assert!(matches!(
+        crate::parse(source).unwrap_err()[0].inner(),
+        ParseError::UnrecognizedEOF { .. }
+    ));
+}
+
+#[test]
+fn err_module_declaration_in_root_module() {
+    let source = "mod bitwise";
+    build_parse_test!(source).expect_unrecognized_token();
+}
+
+// LIBRARY MODULES
+// ================================================================================================
+
+#[test]
+fn library_module() {
+    let source = "
+    mod bitwise
+
+    use hash::*
+
+    constants:
+        ONE: 1
+
+    ev is_binary([a]):
+        enf a^2 = a";
+    let expected = Library {
+        name: ident("bitwise"),
+        sections: vec![
+            LibrarySection::Import(Import::new(
+                ident("hash"),
+                ImportItems::All,
+                SourceSpan::default(),
+            )),
+            LibrarySection::Constants(vec![Constant::new(
+                ident("ONE"),
+                ConstantType::Scalar(Literal::Int(1)),
+                SourceSpan::default(),
+            )]),
+            LibrarySection::EvaluatorFunction(EvaluatorFunction::new(
+                ident("is_binary"),
+                vec![TraceColumn::Single(ident("a"))],
+                vec![],
+                vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                    TransitionExpr::Exp(Box::new(TransitionExpr::Var(ident("a"))), 2),
+                    TransitionExpr::Var(ident("a")),
+                    SourceSpan::default(),
+                ))],
+                SourceSpan::default(),
+            )),
+        ],
+    };
+    assert_eq!(parse_library(source).unwrap(), expected);
+}
+
+#[test]
+fn err_library_without_module_declaration() {
+    let source = "
+    constants:
+        ONE: 1";
+    assert!(matches!(
+        parse_library(source).unwrap_err()[0].inner(),
+        ParseError::UnrecognizedToken { .. }
+    ));
+}
+
+#[test]
+fn err_trace_columns_in_library() {
+    let source = "
+    mod bitwise
+
+    trace_columns:
+        main: [a]";
+    assert!(matches!(
+        parse_library(source).unwrap_err()[0].inner(),
+        ParseError::UnrecognizedToken { .. }
+    ));
+}
+
+// PROGRAMS
+// ================================================================================================
+
+/// Loads the program whose root module is the specified file in the directory of the module test
+/// inputs, and returns it along with the files which were read.
+fn load_program(file_name: &str) -> (Result<Program, Vec<ProgramError>>, SourceFiles) {
+    let path = Path::new("src/parser/tests/input/modules").join(file_name);
+    let source = fs::read_to_string(&path).expect("Could not read file");
+    let mut files = SourceFiles::default();
+    let program = parse_program(&path, &source, &mut files);
+    (program, files)
+}
+
+#[test]
+fn program_with_libraries() {
+    let (program, files) = load_program("program.air");
+    let program = program.unwrap();
+
+    let libraries: Vec<&String> = program.libraries().keys().collect();
+    assert_eq!(libraries, vec!["bitwise", "hash"]);
+    // the hash module is imported twice, but it is only read once.
+    assert_eq!(files.files().len(), 3);
+}
+
+#[test]
+fn program_spans_identify_files() {
+    let (program, files) = load_program("program.air");
+    let program = program.unwrap();
+
+    let bitwise = &files.files()[1];
+    assert!(bitwise.name().ends_with("bitwise.air"));
+    let LibrarySection::EvaluatorFunction(evaluator) = &program.libraries()["bitwise"].sections[1]
+    else {
+        panic!("expected an evaluator function");
+    };
+    let start = bitwise.source().find("ev is_binary").unwrap();
+    assert_eq!(evaluator.span().start(), bitwise.offset() + start);
+}
+
+#[test]
+fn err_program_import_cycle() {
+    let (program, _) = load_program("cycle.air");
+    let errors = program.unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    let ProgramError::ImportCycle(msg, _) = &errors[0] else {
+        panic!("expected an import cycle error, found {:?}", errors[0]);
+    };
+    assert_eq!(
+        msg,
+        "Module cycle_a imports itself: cycle_a -> cycle_b -> cycle_a"
+    );
+}
+
+#[test]
+fn err_program_missing_module() {
+    let (program, _) = load_program("missing.air");
+    let errors = program.unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0], ProgramError::ModuleNotFound(..)));
+}
+
+#[test]
+fn err_program_invalid_module_name() {
+    let (program, _) = load_program("misnamed.air");
+    let errors = program.unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0], ProgramError::InvalidModuleName(..)));
+}
+
+#[test]
+fn err_program_syntax_error_in_library() {
+    let (program, files) = load_program("invalid_library.air");
+    let errors = program.unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0], ProgramError::Syntax(_)));
+    // the error is located in the library file, which follows the root file.
+    let diagnostic = errors[0].to_diagnostic();
+    assert!(diagnostic.labels()[0].span().start() > files.files()[1].offset());
+}
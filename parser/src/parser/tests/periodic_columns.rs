@@ -1,4 +1,6 @@
-use super::{build_parse_test, Identifier, PeriodicColumn, Source, SourceSection};
+use super::{
+    build_parse_test, ident, literals, Literal, PeriodicColumn, Source, SourceSection, SourceSpan,
+};
 
 #[test]
 fn periodic_columns() {
@@ -7,8 +9,32 @@ periodic_columns:
     k0: [1, 0, 0, 0]
     k1: [0, 0, 0, 0, 0, 0, 0, 1]";
     let expected = Source(vec![SourceSection::PeriodicColumns(vec![
-        PeriodicColumn::new(Identifier("k0".to_string()), vec![1, 0, 0, 0]),
-        PeriodicColumn::new(Identifier("k1".to_string()), vec![0, 0, 0, 0, 0, 0, 0, 1]),
+        PeriodicColumn::new(ident("k0"), literals(&[1, 0, 0, 0]), SourceSpan::default()),
+        PeriodicColumn::new(
+            ident("k1"),
+            literals(&[0, 0, 0, 0, 0, 0, 0, 1]),
+            SourceSpan::default(),
+        ),
+    ])]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn periodic_columns_with_modulus_literals() {
+    let source = "
+periodic_columns:
+    k0: [0b1, p - 1, 0x0, p - 2]";
+    let expected = Source(vec![SourceSection::PeriodicColumns(vec![
+        PeriodicColumn::new(
+            ident("k0"),
+            vec![
+                Literal::Int(1),
+                Literal::ModulusMinus(1),
+                Literal::Int(0),
+                Literal::ModulusMinus(2),
+            ],
+            SourceSpan::default(),
+        ),
     ])]);
     build_parse_test!(source).expect_ast(expected);
 }
@@ -27,7 +53,7 @@ fn error_periodic_columns_length() {
 periodic_columns:
     k0: [1, 0, 0]";
     let expected = Source(vec![SourceSection::PeriodicColumns(vec![
-        PeriodicColumn::new(Identifier("k0".to_string()), vec![1, 0, 0]),
+        PeriodicColumn::new(ident("k0"), literals(&[1, 0, 0]), SourceSpan::default()),
     ])]);
     build_parse_test!(source).expect_ast(expected);
 }
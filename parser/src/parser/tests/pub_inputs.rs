@@ -1,4 +1,4 @@
-use super::{build_parse_test, Identifier, PublicInput, Source, SourceSection};
+use super::{build_parse_test, ident, PublicInput, Source, SourceSection, SourceSpan};
 
 // PUBLIC INPUTS
 // ================================================================================================
@@ -10,8 +10,8 @@ fn public_inputs() {
         program_hash: [4]
         stack_inputs: [16]";
     let expected = Source(vec![SourceSection::PublicInputs(vec![
-        PublicInput::new(Identifier("program_hash".to_string()), 4),
-        PublicInput::new(Identifier("stack_inputs".to_string()), 16),
+        PublicInput::new(ident("program_hash"), 4, SourceSpan::default()),
+        PublicInput::new(ident("stack_inputs"), 16, SourceSpan::default()),
     ])]);
     build_parse_test!(source).expect_ast(expected);
 }
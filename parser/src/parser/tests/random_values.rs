@@ -0,0 +1,42 @@
+use super::{build_parse_test, ident, RandomValues, Source, SourceSection, SourceSpan};
+
+// RANDOM VALUES
+// ================================================================================================
+
+#[test]
+fn random_values() {
+    let source = "
+    random_values:
+        $rand: [4]";
+    let expected = Source(vec![SourceSection::RandomValues(vec![RandomValues::new(
+        None,
+        4,
+        SourceSpan::default(),
+    )])]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn random_values_with_bindings() {
+    let source = "
+    random_values:
+        $rand: [2]
+        $rand.bus: [alpha, beta]";
+    let expected = Source(vec![SourceSection::RandomValues(vec![
+        RandomValues::new(None, 2, SourceSpan::default()),
+        RandomValues::with_bindings(
+            Some(ident("bus")),
+            vec![ident("alpha"), ident("beta")],
+            SourceSpan::default(),
+        ),
+    ])]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn error_random_values_without_segment() {
+    let source = "
+    random_values:
+        alpha: [2]";
+    build_parse_test!(source).expect_unrecognized_token();
+}
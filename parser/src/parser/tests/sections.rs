@@ -1,4 +1,4 @@
-use super::build_parse_test;
+use super::{build_parse_test, ident, Source, SourceSection};
 
 // SECTIONS
 // ================================================================================================
@@ -9,3 +9,24 @@ fn error_constraint_without_section() {
     let source = "enf clk' = clk + 1";
     build_parse_test!(source).expect_unrecognized_token();
 }
+
+#[test]
+fn field_declaration() {
+    let source = "
+    def FieldAir
+    field: f128";
+    let expected = Source(vec![
+        SourceSection::AirDef(ident("FieldAir")),
+        SourceSection::Field(ident("f128")),
+    ]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn error_field_declaration_without_name() {
+    let source = "
+    field:
+    trace_columns:
+        main: [clk]";
+    build_parse_test!(source).expect_unrecognized_token();
+}
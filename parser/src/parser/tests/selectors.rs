@@ -0,0 +1,179 @@
+use super::{
+    build_parse_test, ident, ConstraintMatch, Source, SourceSection, SourceSpan,
+    TransitionConstraint, TransitionConstraints, TransitionExpr, TransitionStmt, VectorAccess,
+};
+
+// CONDITIONAL CONSTRAINTS
+// ================================================================================================
+
+#[test]
+fn constraint_with_selector() {
+    let source = "
+    transition_constraints:
+        enf clk' = clk + 1 when s[0] * s[1]";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(
+                TransitionConstraint::conditional(
+                    TransitionExpr::Next(ident("clk")),
+                    TransitionExpr::Add(
+                        Box::new(TransitionExpr::Var(ident("clk"))),
+                        Box::new(TransitionExpr::Const(1)),
+                    ),
+                    TransitionExpr::Mul(
+                        Box::new(TransitionExpr::VectorAccess(VectorAccess::new(
+                            ident("s"),
+                            0,
+                        ))),
+                        Box::new(TransitionExpr::VectorAccess(VectorAccess::new(
+                            ident("s"),
+                            1,
+                        ))),
+                    ),
+                    SourceSpan::default(),
+                ),
+            )],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn constraint_comprehension_with_selector() {
+    let source = "
+    transition_constraints:
+        enf x' = x when s for x in state";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::ConstraintComprehension(
+                TransitionConstraint::conditional(
+                    TransitionExpr::Next(ident("x")),
+                    TransitionExpr::Var(ident("x")),
+                    TransitionExpr::Var(ident("s")),
+                    SourceSpan::default(),
+                ),
+                vec![(ident("x"), ident("state"))],
+            )],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn selector_multiplies_constraint_expr() {
+    let constraint = TransitionConstraint::conditional(
+        TransitionExpr::Next(ident("clk")),
+        TransitionExpr::Var(ident("clk")),
+        TransitionExpr::Var(ident("s")),
+        SourceSpan::default(),
+    );
+    let expected = TransitionExpr::Mul(
+        Box::new(TransitionExpr::Var(ident("s"))),
+        Box::new(TransitionExpr::Sub(
+            Box::new(TransitionExpr::Next(ident("clk"))),
+            Box::new(TransitionExpr::Var(ident("clk"))),
+        )),
+    );
+    assert_eq!(constraint.expr(), expected);
+}
+
+// MATCH CONSTRAINTS
+// ================================================================================================
+
+#[test]
+fn match_constraints() {
+    let source = "
+    transition_constraints:
+        enf match {
+            case s[0]: clk' = clk + 1,
+            case s[1]: clk' = clk
+        }";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Match(ConstraintMatch::new(
+                vec![
+                    TransitionConstraint::conditional(
+                        TransitionExpr::Next(ident("clk")),
+                        TransitionExpr::Add(
+                            Box::new(TransitionExpr::Var(ident("clk"))),
+                            Box::new(TransitionExpr::Const(1)),
+                        ),
+                        TransitionExpr::VectorAccess(VectorAccess::new(ident("s"), 0)),
+                        SourceSpan::default(),
+                    ),
+                    TransitionConstraint::conditional(
+                        TransitionExpr::Next(ident("clk")),
+                        TransitionExpr::Var(ident("clk")),
+                        TransitionExpr::VectorAccess(VectorAccess::new(ident("s"), 1)),
+                        SourceSpan::default(),
+                    ),
+                ],
+                false,
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn exclusive_match_constraints() {
+    let source = "
+    transition_constraints:
+        enf match exclusive {
+            case a: clk' = 0,
+            case b: clk' = 1
+        }";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Match(ConstraintMatch::new(
+                vec![
+                    TransitionConstraint::conditional(
+                        TransitionExpr::Next(ident("clk")),
+                        TransitionExpr::Const(0),
+                        TransitionExpr::Var(ident("a")),
+                        SourceSpan::default(),
+                    ),
+                    TransitionConstraint::conditional(
+                        TransitionExpr::Next(ident("clk")),
+                        TransitionExpr::Const(1),
+                        TransitionExpr::Var(ident("b")),
+                        SourceSpan::default(),
+                    ),
+                ],
+                true,
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+// ERRORS
+// ================================================================================================
+
+#[test]
+fn err_empty_match() {
+    let source = "
+    transition_constraints:
+        enf match {}";
+    build_parse_test!(source).expect_unrecognized_token();
+}
+
+#[test]
+fn err_match_case_without_selector() {
+    let source = "
+    transition_constraints:
+        enf match {
+            case: clk' = clk
+        }";
+    build_parse_test!(source).expect_unrecognized_token();
+}
+
+#[test]
+fn err_selector_in_boundary_constraint() {
+    let source = "
+    boundary_constraints:
+        enf clk.first = 0 when s";
+    build_parse_test!(source).expect_unrecognized_token();
+}
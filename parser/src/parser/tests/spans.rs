@@ -0,0 +1,83 @@
+use super::{build_parse_test, BoundaryStmt, SourceSection, SourceSpan, TransitionStmt};
+
+// SPANS
+// ================================================================================================
+
+#[test]
+fn identifier_spans() {
+    let source = "def SystemAir
+trace_columns:
+    main: [clk, fmp]";
+    let parsed = build_parse_test!(source).parse().unwrap();
+
+    let SourceSection::AirDef(name) = &parsed.0[0] else {
+        panic!("expected an air definition");
+    };
+    assert_eq!(name.span(), SourceSpan::new(4, 13));
+
+    let SourceSection::TraceCols(columns) = &parsed.0[1] else {
+        panic!("expected a trace columns declaration");
+    };
+    let fmp = &columns.main_cols[1];
+    assert_eq!(
+        &source[fmp.ident().span().start()..fmp.ident().span().end()],
+        "fmp"
+    );
+    let location = fmp.ident().span().start_location(source);
+    assert_eq!((location.line(), location.column()), (3, 17));
+    assert_eq!(columns.span, SourceSpan::new(14, source.len()));
+}
+
+#[test]
+fn declaration_spans() {
+    let source = "
+public_inputs:
+    stack_inputs: [16]
+periodic_columns:
+    k0: [1, 0]";
+    let parsed = build_parse_test!(source).parse().unwrap();
+
+    let SourceSection::PublicInputs(inputs) = &parsed.0[0] else {
+        panic!("expected a public inputs declaration");
+    };
+    let span = inputs[0].span();
+    assert_eq!(&source[span.start()..span.end()], "stack_inputs: [16]");
+
+    let SourceSection::PeriodicColumns(columns) = &parsed.0[1] else {
+        panic!("expected a periodic columns declaration");
+    };
+    let span = columns[0].span();
+    assert_eq!(&source[span.start()..span.end()], "k0: [1, 0]");
+    assert_eq!(span.start_location(source).to_string(), "5:5");
+}
+
+#[test]
+fn constraint_spans() {
+    let source = "
+boundary_constraints:
+    enf clk.first = 0
+transition_constraints:
+    enf clk' = clk + 1";
+    let parsed = build_parse_test!(source).parse().unwrap();
+
+    let SourceSection::BoundaryConstraints(constraints) = &parsed.0[0] else {
+        panic!("expected boundary constraints");
+    };
+    let BoundaryStmt::Constraint(constraint) = &constraints.stmts[0] else {
+        panic!("expected a boundary constraint");
+    };
+    let span = constraint.span();
+    assert_eq!(&source[span.start()..span.end()], "enf clk.first = 0");
+    let span = constraint.column().span();
+    assert_eq!(&source[span.start()..span.end()], "clk");
+
+    let SourceSection::TransitionConstraints(constraints) = &parsed.0[1] else {
+        panic!("expected transition constraints");
+    };
+    let TransitionStmt::Constraint(constraint) = &constraints.stmts[0] else {
+        panic!("expected a transition constraint");
+    };
+    let span = constraint.span();
+    assert_eq!(&source[span.start()..span.end()], "enf clk' = clk + 1");
+    assert_eq!(span.end_location(source).to_string(), "5:23");
+}
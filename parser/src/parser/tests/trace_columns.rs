@@ -1,4 +1,7 @@
-use super::{build_parse_test, Error, Identifier, ParseError, Source, SourceSection, TraceCols};
+use super::{
+    build_parse_test, ident, AuxSegment, Error, ParseError, Source, SourceSection, SourceSpan,
+    TraceCols, TraceColumn,
+};
 
 // TRACE COLUMNS
 // ================================================================================================
@@ -10,11 +13,12 @@ fn trace_columns() {
         main: [clk, fmp, ctx]";
     let expected = Source(vec![SourceSection::TraceCols(TraceCols {
         main_cols: vec![
-            Identifier("clk".to_string()),
-            Identifier("fmp".to_string()),
-            Identifier("ctx".to_string()),
+            TraceColumn::Single(ident("clk")),
+            TraceColumn::Single(ident("fmp")),
+            TraceColumn::Single(ident("ctx")),
         ],
-        aux_cols: vec![],
+        aux_segments: vec![],
+        span: SourceSpan::default(),
     })]);
     build_parse_test!(source).expect_ast(expected);
 }
@@ -27,26 +31,113 @@ fn trace_columns_main_and_aux() {
         aux: [rc_bus, ch_bus]";
     let expected = Source(vec![SourceSection::TraceCols(TraceCols {
         main_cols: vec![
-            Identifier("clk".to_string()),
-            Identifier("fmp".to_string()),
-            Identifier("ctx".to_string()),
+            TraceColumn::Single(ident("clk")),
+            TraceColumn::Single(ident("fmp")),
+            TraceColumn::Single(ident("ctx")),
         ],
-        aux_cols: vec![
-            Identifier("rc_bus".to_string()),
-            Identifier("ch_bus".to_string()),
+        aux_segments: vec![AuxSegment::new(
+            None,
+            vec![
+                TraceColumn::Single(ident("rc_bus")),
+                TraceColumn::Single(ident("ch_bus")),
+            ],
+            SourceSpan::default(),
+        )],
+        span: SourceSpan::default(),
+    })]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn trace_columns_groups() {
+    let source = "
+    trace_columns:
+        main: [clk, state[12]]
+        aux: [p[2], q]";
+    let expected = Source(vec![SourceSection::TraceCols(TraceCols {
+        main_cols: vec![
+            TraceColumn::Single(ident("clk")),
+            TraceColumn::Group(ident("state"), 12),
+        ],
+        aux_segments: vec![AuxSegment::new(
+            None,
+            vec![
+                TraceColumn::Group(ident("p"), 2),
+                TraceColumn::Single(ident("q")),
+            ],
+            SourceSpan::default(),
+        )],
+        span: SourceSpan::default(),
+    })]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn trace_columns_aux_segments() {
+    let source = "
+    trace_columns:
+        main: [clk]
+        aux bus: [p[2], q]
+        aux: [r]";
+    let expected = Source(vec![SourceSection::TraceCols(TraceCols {
+        main_cols: vec![TraceColumn::Single(ident("clk"))],
+        aux_segments: vec![
+            AuxSegment::new(
+                Some(ident("bus")),
+                vec![
+                    TraceColumn::Group(ident("p"), 2),
+                    TraceColumn::Single(ident("q")),
+                ],
+                SourceSpan::default(),
+            ),
+            AuxSegment::new(
+                None,
+                vec![TraceColumn::Single(ident("r"))],
+                SourceSpan::default(),
+            ),
         ],
+        span: SourceSpan::default(),
     })]);
     build_parse_test!(source).expect_ast(expected);
 }
 
+#[test]
+fn trace_columns_named_like_boundaries() {
+    let source = "
+    trace_columns:
+        main: [row, every]";
+    let expected = Source(vec![SourceSection::TraceCols(TraceCols {
+        main_cols: vec![
+            TraceColumn::Single(ident("row")),
+            TraceColumn::Single(ident("every")),
+        ],
+        aux_segments: vec![],
+        span: SourceSpan::default(),
+    })]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn empty_trace_column_group_error() {
+    let source = "
+    trace_columns:
+        main: [clk, state[0]]";
+    let error = Error::ParseError(
+        ParseError::InvalidTraceCols("Trace column group state cannot be empty".to_string()),
+        SourceSpan::new(40, 48),
+    );
+    build_parse_test!(source).expect_error(error);
+}
+
 #[test]
 fn empty_trace_columns_error() {
     let source = "
     trace_columns:";
     // Trace columns cannot be empty
-    let error = Error::ParseError(ParseError::InvalidTraceCols(
-        "Trace Columns cannot be empty".to_string(),
-    ));
+    let error = Error::ParseError(
+        ParseError::InvalidTraceCols("Trace Columns cannot be empty".to_string()),
+        SourceSpan::new(5, 19),
+    );
     build_parse_test!(source).expect_error(error);
 }
 
@@ -63,8 +154,11 @@ fn main_trace_cols_missing_error() {
     boundary_constraints:
         enf clk.first = 0";
 
-    let error = Error::ParseError(ParseError::MissingMainTraceCols(
-        "Declaration of main trace columns is required".to_string(),
-    ));
+    let error = Error::ParseError(
+        ParseError::MissingMainTraceCols(
+            "Declaration of main trace columns is required".to_string(),
+        ),
+        SourceSpan::new(5, 38),
+    );
     build_parse_test!(source).expect_error(error);
 }
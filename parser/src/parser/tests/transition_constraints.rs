@@ -1,6 +1,7 @@
 use super::{
-    build_parse_test, Identifier, Source, SourceSection, TransitionConstraint,
-    TransitionConstraints, TransitionExpr,
+    build_parse_test, ident, MatrixAccess, RandomValueAccess, Source, SourceSection, SourceSpan,
+    TransitionConstraint, TransitionConstraints, TransitionExemptions, TransitionExpr,
+    TransitionStmt, TransitionVariable, VectorAccess,
 };
 
 // TRANSITION CONSTRAINTS
@@ -13,13 +14,14 @@ fn transition_constraints() {
         enf clk' = clk + 1";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
-                TransitionExpr::Next(Identifier("clk".to_string())),
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Next(ident("clk")),
                 TransitionExpr::Add(
-                    Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                    Box::new(TransitionExpr::Var(ident("clk"))),
                     Box::new(TransitionExpr::Const(1)),
                 ),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -40,21 +42,23 @@ fn multiple_transition_constraints() {
         enf clk' - clk = 1";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![
-                TransitionConstraint::new(
-                    TransitionExpr::Next(Identifier("clk".to_string())),
+            stmts: vec![
+                TransitionStmt::Constraint(TransitionConstraint::new(
+                    TransitionExpr::Next(ident("clk")),
                     TransitionExpr::Add(
-                        Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                        Box::new(TransitionExpr::Var(ident("clk"))),
                         Box::new(TransitionExpr::Const(1)),
                     ),
-                ),
-                TransitionConstraint::new(
+                    SourceSpan::default(),
+                )),
+                TransitionStmt::Constraint(TransitionConstraint::new(
                     TransitionExpr::Sub(
-                        Box::new(TransitionExpr::Next(Identifier("clk".to_string()))),
-                        Box::new(TransitionExpr::Var(Identifier("clk".to_string()))),
+                        Box::new(TransitionExpr::Next(ident("clk"))),
+                        Box::new(TransitionExpr::Var(ident("clk"))),
                     ),
                     TransitionExpr::Const(1),
-                ),
+                    SourceSpan::default(),
+                )),
             ],
         },
     )]);
@@ -68,13 +72,14 @@ fn transition_constraint_with_periodic_col() {
         enf k0 + b = 0";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Add(
-                    Box::new(TransitionExpr::Var(Identifier("k0".to_string()))),
-                    Box::new(TransitionExpr::Var(Identifier("b".to_string()))),
+                    Box::new(TransitionExpr::Var(ident("k0"))),
+                    Box::new(TransitionExpr::Var(ident("b"))),
                 ),
                 TransitionExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -87,13 +92,190 @@ fn transition_constraint_with_random_value() {
         enf a + $rand[1] = 0";
     let expected = Source(vec![SourceSection::TransitionConstraints(
         TransitionConstraints {
-            transition_constraints: vec![TransitionConstraint::new(
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
                 TransitionExpr::Add(
-                    Box::new(TransitionExpr::Var(Identifier("a".to_string()))),
-                    Box::new(TransitionExpr::Rand(1)),
+                    Box::new(TransitionExpr::Var(ident("a"))),
+                    Box::new(TransitionExpr::Rand(RandomValueAccess::new(
+                        None,
+                        1,
+                        SourceSpan::default(),
+                    ))),
                 ),
                 TransitionExpr::Const(0),
-            )],
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn transition_constraint_with_random_value_of_named_segment() {
+    let source = "
+    transition_constraints:
+        enf a + $rand.bus[1] = 0";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Add(
+                    Box::new(TransitionExpr::Var(ident("a"))),
+                    Box::new(TransitionExpr::Rand(RandomValueAccess::new(
+                        Some(ident("bus")),
+                        1,
+                        SourceSpan::default(),
+                    ))),
+                ),
+                TransitionExpr::Const(0),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn transition_constraint_with_constants() {
+    let source = "
+    transition_constraints:
+        enf clk' = clk + A * B[1] + C[1][0]";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Next(ident("clk")),
+                TransitionExpr::Add(
+                    Box::new(TransitionExpr::Add(
+                        Box::new(TransitionExpr::Var(ident("clk"))),
+                        Box::new(TransitionExpr::Mul(
+                            Box::new(TransitionExpr::Var(ident("A"))),
+                            Box::new(TransitionExpr::VectorAccess(VectorAccess::new(
+                                ident("B"),
+                                1,
+                            ))),
+                        )),
+                    )),
+                    Box::new(TransitionExpr::MatrixAccess(MatrixAccess::new(
+                        ident("C"),
+                        1,
+                        0,
+                    ))),
+                ),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn transition_constraint_with_variables() {
+    let source = "
+    transition_constraints:
+        let x = a + b
+        let y = x * x
+        enf a' = y + x";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![
+                TransitionStmt::Variable(TransitionVariable::new(
+                    ident("x"),
+                    TransitionExpr::Add(
+                        Box::new(TransitionExpr::Var(ident("a"))),
+                        Box::new(TransitionExpr::Var(ident("b"))),
+                    ),
+                    SourceSpan::default(),
+                )),
+                TransitionStmt::Variable(TransitionVariable::new(
+                    ident("y"),
+                    TransitionExpr::Mul(
+                        Box::new(TransitionExpr::Var(ident("x"))),
+                        Box::new(TransitionExpr::Var(ident("x"))),
+                    ),
+                    SourceSpan::default(),
+                )),
+                TransitionStmt::Constraint(TransitionConstraint::new(
+                    TransitionExpr::Next(ident("a")),
+                    TransitionExpr::Add(
+                        Box::new(TransitionExpr::Var(ident("y"))),
+                        Box::new(TransitionExpr::Var(ident("x"))),
+                    ),
+                    SourceSpan::default(),
+                )),
+            ],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn transition_constraint_with_column_groups() {
+    let source = "
+    transition_constraints:
+        enf state[1]' = state[0] + clk";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::NextVectorAccess(VectorAccess::new(ident("state"), 1)),
+                TransitionExpr::Add(
+                    Box::new(TransitionExpr::VectorAccess(VectorAccess::new(
+                        ident("state"),
+                        0,
+                    ))),
+                    Box::new(TransitionExpr::Var(ident("clk"))),
+                ),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn transition_constraint_with_row_offsets() {
+    let source = "
+    transition_constraints:
+        enf clk'' = clk[+1] + state[1]''' * state[0][+3]";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::RowOffset(ident("clk"), 2),
+                TransitionExpr::Add(
+                    Box::new(TransitionExpr::RowOffset(ident("clk"), 1)),
+                    Box::new(TransitionExpr::Mul(
+                        Box::new(TransitionExpr::VectorRowOffset(
+                            VectorAccess::new(ident("state"), 1),
+                            3,
+                        )),
+                        Box::new(TransitionExpr::VectorRowOffset(
+                            VectorAccess::new(ident("state"), 0),
+                            3,
+                        )),
+                    )),
+                ),
+                SourceSpan::default(),
+            ))],
+        },
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn transition_constraint_with_previous_row_offsets() {
+    let source = "
+    transition_constraints:
+        enf clk = clk[-1] + state[0][-2]";
+    let expected = Source(vec![SourceSection::TransitionConstraints(
+        TransitionConstraints {
+            stmts: vec![TransitionStmt::Constraint(TransitionConstraint::new(
+                TransitionExpr::Var(ident("clk")),
+                TransitionExpr::Add(
+                    Box::new(TransitionExpr::RowOffset(ident("clk"), -1)),
+                    Box::new(TransitionExpr::VectorRowOffset(
+                        VectorAccess::new(ident("state"), 0),
+                        -2,
+                    )),
+                ),
+                SourceSpan::default(),
+            ))],
         },
     )]);
     build_parse_test!(source).expect_ast(expected);
@@ -106,7 +288,16 @@ fn transition_constraint_with_random_value() {
 fn error_invalid_next_usage() {
     let source = "
     transition_constraints:
-        enf clk'' = clk + 1";
+        enf clk'[+1] = clk + 1";
+    build_parse_test!(source).expect_unrecognized_token();
+}
+
+#[test]
+fn err_variable_without_value() {
+    let source = "
+    transition_constraints:
+        let x =
+        enf clk' = clk + 1";
     build_parse_test!(source).expect_unrecognized_token();
 }
 
@@ -119,3 +310,23 @@ fn err_empty_transition_constraints() {
         enf clk.first = 1";
     build_parse_test!(source).expect_unrecognized_token();
 }
+
+// TRANSITION EXEMPTIONS
+// ================================================================================================
+
+#[test]
+fn transition_exemptions() {
+    let source = "
+    transition_exemptions: 2";
+    let expected = Source(vec![SourceSection::TransitionExemptions(
+        TransitionExemptions::new(2, SourceSpan::default()),
+    )]);
+    build_parse_test!(source).expect_ast(expected);
+}
+
+#[test]
+fn err_transition_exemptions_not_a_number() {
+    let source = "
+    transition_exemptions: clk";
+    build_parse_test!(source).expect_unrecognized_token();
+}
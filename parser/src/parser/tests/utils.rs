@@ -1,11 +1,6 @@
 use lalrpop_util::ParseError;
 
-use super::SourceParser;
-use crate::{
-    ast::Source,
-    error::Error,
-    lexer::{Lexer, Token},
-};
+use crate::{ast::Source, error::Error, SyntaxError};
 
 // TEST HANDLER
 // ================================================================================================
@@ -43,12 +38,8 @@ impl ParseTest {
         }
     }
 
-    pub fn parse(&self) -> Result<Source, lalrpop_util::ParseError<usize, Token, Error>> {
-        let lex = Lexer::new(self.source.as_str())
-            .spanned()
-            .map(Token::to_spanned);
-
-        SourceParser::new().parse(lex)
+    pub fn parse(&self) -> Result<Source, Vec<SyntaxError>> {
+        crate::parse(self.source.as_str())
     }
 
     // TEST METHODS
@@ -57,34 +48,31 @@ impl ParseTest {
     /// Checks that source is valid and asserts that appropriate error is returned if there
     /// is a problem while parsing the source.
     pub fn expect_error(&self, error: Error) {
-        let lex = Lexer::new(self.source.as_str())
-            .spanned()
-            .map(Token::to_spanned);
-
-        let source_parsed = SourceParser::new().parse(lex);
-        let expected_error = Err(ParseError::User { error });
-        assert_eq!(source_parsed, expected_error);
+        let errors = self.parse().unwrap_err();
+        let expected_error = ParseError::User { error };
+        assert_eq!(errors[0].inner(), &expected_error);
     }
 
     /// If an unrecognized token is present in the source string, return UnrecognizedToken error.
     pub fn expect_unrecognized_token(&self) {
-        let lex = Lexer::new(self.source.as_str())
-            .spanned()
-            .map(Token::to_spanned);
-        let source_parsed = SourceParser::new().parse(lex);
+        let errors = self.parse().unwrap_err();
         assert!(matches!(
-            source_parsed,
-            Err(ParseError::UnrecognizedToken { .. })
+            errors[0].inner(),
+            ParseError::UnrecognizedToken { .. }
         ));
     }
 
+    /// Asserts that parsing the source fails with the specified number of errors, i.e. that the
+    /// parser recovers from each error and reports the following ones.
+    pub fn expect_error_count(&self, count: usize) {
+        let errors = self.parse().unwrap_err();
+        assert_eq!(errors.len(), count, "unexpected errors: {:?}", errors);
+    }
+
     /// Builds an AST from the given source string and asserts that executing the test will result
     /// in the expected AST.
     pub fn expect_ast(&self, expected: Source) {
-        let lex = Lexer::new(self.source.as_str())
-            .spanned()
-            .map(Token::to_spanned);
-        let source_parsed = SourceParser::new().parse(lex).unwrap();
+        let source_parsed = self.parse().unwrap();
         assert_eq!(source_parsed, expected);
     }
 }
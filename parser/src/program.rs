@@ -0,0 +1,192 @@
+use crate::{ast, parse_library_at, parse_source_at, ProgramError};
+use diagnostics::SourceFiles;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+// PROGRAM
+// ================================================================================================
+
+/// The ASTs of all of the modules of an AirScript program: the root module, which declares the
+/// AIR, and the library modules which are imported by it, either directly or through other
+/// libraries.
+#[derive(Debug, PartialEq)]
+pub struct Program {
+    root: ast::Source,
+    libraries: BTreeMap<String, ast::Library>,
+}
+
+impl Program {
+    pub fn new(root: ast::Source, libraries: BTreeMap<String, ast::Library>) -> Self {
+        Self { root, libraries }
+    }
+
+    /// Returns the AST of the root module.
+    pub fn root(&self) -> &ast::Source {
+        &self.root
+    }
+
+    /// Returns the ASTs of the library modules, keyed by module name.
+    pub fn libraries(&self) -> &BTreeMap<String, ast::Library> {
+        &self.libraries
+    }
+}
+
+/// Parses the provided source of the root module of a program, which was read from the specified
+/// path, along with all of the library modules it imports.
+///
+/// A module imported as `use <module>::...` is loaded from the file `<module>.air` in the directory
+/// of the root file. Every file which is read is added to the provided set of source files, so
+/// that the spans of the returned ASTs, and of any errors, identify the file they refer to.
+///
+/// # Errors
+/// Returns a list of [ProgramError]s if:
+/// - Any of the files of the program contains syntax errors.
+/// - The file of an imported module can't be read.
+/// - A library module declares a different name than the name under which it was imported.
+/// - A library module imports itself, either directly or through the modules it imports.
+pub fn parse_program(
+    root_path: &Path,
+    root_source: &str,
+    files: &mut SourceFiles,
+) -> Result<Program, Vec<ProgramError>> {
+    let offset = files.add(&root_path.display().to_string(), root_source);
+    let root = parse_source_at(root_source, offset).map_err(|errors| {
+        errors
+            .into_iter()
+            .map(ProgramError::from)
+            .collect::<Vec<_>>()
+    })?;
+
+    let imports: Vec<&ast::Import> = root
+        .0
+        .iter()
+        .filter_map(|section| match section {
+            ast::SourceSection::Import(import) => Some(import),
+            _ => None,
+        })
+        .collect();
+    let mut loader = ModuleLoader {
+        dir: root_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default(),
+        files,
+        libraries: BTreeMap::new(),
+        failed: Vec::new(),
+        path: Vec::new(),
+        errors: Vec::new(),
+    };
+    loader.load_imports(&imports);
+
+    if loader.errors.is_empty() {
+        Ok(Program::new(root, loader.libraries))
+    } else {
+        Err(loader.errors)
+    }
+}
+
+// MODULE LOADER
+// ================================================================================================
+
+/// Loads the library modules imported by a program, depth first, so that import cycles can be
+/// detected from the path of modules which are being loaded.
+struct ModuleLoader<'a> {
+    /// The directory from which the library modules are loaded.
+    dir: PathBuf,
+    files: &'a mut SourceFiles,
+    libraries: BTreeMap<String, ast::Library>,
+    /// The modules which could not be loaded, so that the errors are only reported once.
+    failed: Vec<String>,
+    /// The modules which are being loaded, in the order in which they were imported.
+    path: Vec<String>,
+    errors: Vec<ProgramError>,
+}
+
+impl ModuleLoader<'_> {
+    /// Loads the modules of the provided imports which haven't been loaded yet, along with the
+    /// modules they import.
+    fn load_imports(&mut self, imports: &[&ast::Import]) {
+        for import in imports {
+            let name = import.module().name();
+            if let Some(idx) = self.path.iter().position(|module| module == name) {
+                let mut cycle = self.path[idx..].to_vec();
+                cycle.push(name.to_string());
+                self.errors.push(ProgramError::ImportCycle(
+                    format!("Module {} imports itself: {}", name, cycle.join(" -> ")),
+                    import.module().span(),
+                ));
+                continue;
+            }
+            if self.libraries.contains_key(name) || self.failed.iter().any(|m| m == name) {
+                continue;
+            }
+
+            match self.load_library(import.module()) {
+                Some(library) => {
+                    let imports: Vec<&ast::Import> = library
+                        .sections
+                        .iter()
+                        .filter_map(|section| match section {
+                            ast::LibrarySection::Import(import) => Some(import),
+                            _ => None,
+                        })
+                        .collect();
+                    self.path.push(name.to_string());
+                    self.load_imports(&imports);
+                    self.path.pop();
+                    self.libraries.insert(name.to_string(), library);
+                }
+                None => self.failed.push(name.to_string()),
+            }
+        }
+    }
+
+    /// Reads and parses the file of the specified module, or records the errors and returns None
+    /// if the module could not be loaded.
+    fn load_library(&mut self, module: &ast::Identifier) -> Option<ast::Library> {
+        let path = self.dir.join(format!("{}.air", module.name()));
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(err) => {
+                self.errors.push(ProgramError::ModuleNotFound(
+                    format!(
+                        "Module {} could not be loaded from {}: {}",
+                        module,
+                        path.display(),
+                        err
+                    ),
+                    module.span(),
+                ));
+                return None;
+            }
+        };
+
+        let offset = self.files.add(&path.display().to_string(), &source);
+        let library = match parse_library_at(&source, offset) {
+            Ok(library) => library,
+            Err(errors) => {
+                self.errors
+                    .extend(errors.into_iter().map(ProgramError::from));
+                return None;
+            }
+        };
+
+        if library.name != *module {
+            self.errors.push(ProgramError::InvalidModuleName(
+                format!(
+                    "Module {} was imported from {}, which declares module {}",
+                    module,
+                    path.display(),
+                    library.name
+                ),
+                library.name.span(),
+            ));
+            return None;
+        }
+
+        Some(library)
+    }
+}